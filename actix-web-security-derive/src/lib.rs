@@ -0,0 +1,221 @@
+//! # actix-web-security-derive
+//! Derive macros for [actix-web-security](https://crates.io/crates/actix-web-security).
+//! The macros are re-exported by `actix-web-security` if the `derive` feature is activated.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::meta::ParseNestedMeta;
+use syn::{parse_macro_input, Data, DeriveInput, Field, Fields, LitStr, Token, Type};
+
+/// Derives the `Claims` trait for a struct with named fields.
+///
+/// The accessors of the registered claims are implemented for the fields with the names of the claims
+/// (considering `#[serde(rename = "...")]`):
+/// * `iss`, `sub` and `jti` (types implementing `AsRef<str>`, e.g. `String`)
+/// * `aud` (types convertible into `Audience`, e.g. `String`, `Vec<String>` or `Audience`)
+/// * `exp`, `nbf` and `iat` (integer types, values that don't fit into `usize` are ignored)
+/// * `scope` and `scp` (`Scopes`)
+///
+/// Each of the fields can be wrapped into an `Option`.
+/// The struct must implement `serde::Serialize` to convert the claims to JSON.
+#[proc_macro_derive(Claims)]
+pub fn derive_claims(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match expand_claims(&input) {
+        Ok(output) => output.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+fn expand_claims(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => fields.named.iter().collect::<Vec<&Field>>(),
+            Fields::Unnamed(_) => {
+                return Err(syn::Error::new_spanned(
+                    input,
+                    "Claims can only be derived for structs with named fields",
+                ))
+            }
+            Fields::Unit => Vec::new(),
+        },
+        _ => {
+            return Err(syn::Error::new_spanned(
+                input,
+                "Claims can only be derived for structs",
+            ))
+        }
+    };
+    let claim_names = fields
+        .iter()
+        .map(|field| claim_name(field))
+        .collect::<Vec<String>>();
+    let field = |name: &str| {
+        fields
+            .iter()
+            .zip(&claim_names)
+            .find(|(_, claim_name)| *claim_name == name)
+            .map(|(field, _)| *field)
+    };
+
+    let token = quote!(::actix_web_security::authentication::scheme::bearer::jwt::token);
+    let serde_json = quote!(::actix_web_security::__private::serde_json);
+
+    let mut accessors = Vec::new();
+    for (method, claim) in [("issuer", "iss"), ("subject", "sub"), ("jwt_id", "jti")] {
+        if let Some(field) = field(claim) {
+            accessors.push(string_accessor(method, field));
+        }
+    }
+    for (method, claim) in [
+        ("expiration_time", "exp"),
+        ("not_before", "nbf"),
+        ("issued_at", "iat"),
+    ] {
+        if let Some(field) = field(claim) {
+            accessors.push(numeric_accessor(method, field));
+        }
+    }
+    if let Some(field) = field("aud") {
+        accessors.push(audience_accessor(&token, field));
+    }
+    let scope_fields: Vec<&Field> = ["scope", "scp"]
+        .iter()
+        .filter_map(|claim| field(claim))
+        .collect();
+    if !scope_fields.is_empty() {
+        accessors.push(scopes_accessor(&token, &scope_fields));
+    }
+
+    let name = &input.ident;
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics #token::Claims for #name #type_generics #where_clause {
+            #(#accessors)*
+
+            fn to_json(&self) -> ::core::option::Option<#serde_json::Value> {
+                #serde_json::to_value(self).ok()
+            }
+        }
+    })
+}
+
+/// Returns the name of the claim of the field, i.e. the name the field is deserialized from.
+fn claim_name(field: &Field) -> String {
+    let mut name = field
+        .ident
+        .as_ref()
+        .map(|ident| ident.to_string().trim_start_matches("r#").to_string())
+        .unwrap_or_default();
+    for attr in field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("serde"))
+    {
+        // Invalid serde attributes are reported by serde, therefore parse errors are ignored here
+        let _ = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("rename") {
+                if meta.input.peek(Token![=]) {
+                    name = meta.value()?.parse::<LitStr>()?.value();
+                } else {
+                    meta.parse_nested_meta(|meta| {
+                        if meta.path.is_ident("deserialize") {
+                            name = meta.value()?.parse::<LitStr>()?.value();
+                            Ok(())
+                        } else {
+                            skip_meta(&meta)
+                        }
+                    })?;
+                }
+                Ok(())
+            } else {
+                skip_meta(&meta)
+            }
+        });
+    }
+    name
+}
+
+/// Skips the value (`= ...` or `(...)`) of an attribute item that is not evaluated.
+fn skip_meta(meta: &ParseNestedMeta) -> syn::Result<()> {
+    if meta.input.peek(Token![=]) {
+        meta.value()?.parse::<syn::Expr>()?;
+    } else if meta.input.peek(syn::token::Paren) {
+        meta.input.parse::<proc_macro2::Group>()?;
+    }
+    Ok(())
+}
+
+fn is_option(field: &Field) -> bool {
+    match &field.ty {
+        Type::Path(type_path) => type_path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "Option"),
+        _ => false,
+    }
+}
+
+fn string_accessor(method: &str, field: &Field) -> TokenStream2 {
+    let method = syn::Ident::new(method, proc_macro2::Span::call_site());
+    let ident = &field.ident;
+    let value = if is_option(field) {
+        quote!(self.#ident.as_ref().map(|value| ::core::convert::AsRef::<str>::as_ref(value)))
+    } else {
+        quote!(::core::option::Option::Some(::core::convert::AsRef::<str>::as_ref(&self.#ident)))
+    };
+    quote! {
+        fn #method(&self) -> ::core::option::Option<&str> {
+            #value
+        }
+    }
+}
+
+fn numeric_accessor(method: &str, field: &Field) -> TokenStream2 {
+    let method = syn::Ident::new(method, proc_macro2::Span::call_site());
+    let ident = &field.ident;
+    let value = if is_option(field) {
+        quote!(self.#ident.and_then(|value| ::core::convert::TryFrom::try_from(value).ok()))
+    } else {
+        quote!(::core::convert::TryFrom::try_from(self.#ident).ok())
+    };
+    quote! {
+        fn #method(&self) -> ::core::option::Option<usize> {
+            #value
+        }
+    }
+}
+
+fn audience_accessor(token: &TokenStream2, field: &Field) -> TokenStream2 {
+    let ident = &field.ident;
+    let value = if is_option(field) {
+        quote!(self.#ident.clone().map(::core::convert::Into::into))
+    } else {
+        quote!(::core::option::Option::Some(::core::convert::Into::into(self.#ident.clone())))
+    };
+    quote! {
+        fn audience(&self) -> ::core::option::Option<#token::audience::Audience> {
+            #value
+        }
+    }
+}
+
+fn scopes_accessor(token: &TokenStream2, fields: &[&Field]) -> TokenStream2 {
+    let iterators = fields.iter().map(|field| {
+        let ident = &field.ident;
+        if is_option(field) {
+            quote!(self.#ident.iter().flat_map(|scopes| scopes.iter()))
+        } else {
+            quote!(self.#ident.iter())
+        }
+    });
+    quote! {
+        fn scopes(&self) -> #token::scopes::Scopes {
+            let scopes = ::core::iter::empty::<&::std::string::String>();
+            #(let scopes = scopes.chain(#iterators);)*
+            scopes.cloned().collect()
+        }
+    }
+}
@@ -0,0 +1,162 @@
+//! An in-memory implementation of a `LoginAttemptStore`.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+
+use async_trait::async_trait;
+
+use crate::authentication::brute_force::{LoginAttemptKey, LoginAttemptStore, LoginAttempts};
+
+/// The default maximum number of tracked keys.
+const DEFAULT_MAX_ENTRIES: usize = 100_000;
+
+/// A `LoginAttemptStore` keeping the failed attempts in memory. The attempts are shared between all clones
+/// (and therefore all workers), but not between multiple instances of the application.
+///
+/// The number of tracked keys is limited (100,000 by default). If the limit is reached, the oldest tenth of the
+/// entries (by the time of the last failure or reservation) is evicted at once, so that the eviction costs are amortized over
+/// many failures.
+#[derive(Clone)]
+pub struct InMemoryLoginAttemptStore {
+    attempts: Arc<Mutex<HashMap<LoginAttemptKey, LoginAttempts>>>,
+    max_entries: usize,
+}
+
+impl InMemoryLoginAttemptStore {
+    /// Constructs a new empty instance.
+    pub fn new() -> InMemoryLoginAttemptStore {
+        InMemoryLoginAttemptStore::default()
+    }
+
+    /// Sets the maximum number of tracked keys (at least 1).
+    pub fn with_max_entries(mut self, max_entries: usize) -> InMemoryLoginAttemptStore {
+        self.max_entries = max_entries.max(1);
+        self
+    }
+
+    /// Returns the number of tracked keys.
+    pub fn len(&self) -> usize {
+        self.attempts.lock().expect("Failed to acquire lock").len()
+    }
+
+    /// Returns **true** if no keys are tracked.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Default for InMemoryLoginAttemptStore {
+    fn default() -> Self {
+        InMemoryLoginAttemptStore {
+            attempts: Arc::new(Mutex::new(HashMap::new())),
+            max_entries: DEFAULT_MAX_ENTRIES,
+        }
+    }
+}
+
+impl InMemoryLoginAttemptStore {
+    /// Executes the function with the attempts of the key, which are created if the key is not tracked yet.
+    /// Entries without failures and pending attempts are removed afterwards.
+    fn update<R, F: FnOnce(&mut LoginAttempts) -> R>(
+        &self,
+        key: &LoginAttemptKey,
+        time: SystemTime,
+        update: F,
+    ) -> R {
+        let mut all_attempts = self.attempts.lock().expect("Failed to acquire lock");
+        if all_attempts.len() >= self.max_entries && !all_attempts.contains_key(key) {
+            evict(&mut all_attempts);
+        }
+        let attempts = all_attempts.entry(key.clone()).or_insert(LoginAttempts {
+            failures: 0,
+            last_failure: time,
+            pending: 0,
+            last_reservation: time,
+        });
+        let result = update(attempts);
+        if attempts.failures == 0 && attempts.pending == 0 {
+            all_attempts.remove(key);
+        }
+        result
+    }
+}
+
+/// Removes the oldest tenth of the entries (by the time of the last failure or reservation).
+fn evict(all_attempts: &mut HashMap<LoginAttemptKey, LoginAttempts>) {
+    let last_activity =
+        |attempts: &LoginAttempts| attempts.last_failure.max(attempts.last_reservation);
+    let mut last_activities: Vec<SystemTime> = all_attempts.values().map(last_activity).collect();
+    let index = last_activities.len() / 10;
+    let (_, cutoff, _) = last_activities.select_nth_unstable(index);
+    let cutoff = *cutoff;
+    all_attempts.retain(|_, attempts| last_activity(attempts) > cutoff);
+}
+
+#[async_trait]
+impl LoginAttemptStore for InMemoryLoginAttemptStore {
+    async fn find_attempts(&self, key: &LoginAttemptKey) -> Option<LoginAttempts> {
+        self.attempts
+            .lock()
+            .expect("Failed to acquire lock")
+            .get(key)
+            .copied()
+    }
+
+    async fn reserve_attempt(
+        &self,
+        key: &LoginAttemptKey,
+        time: SystemTime,
+        max_pending: u32,
+        pending_timeout: Duration,
+    ) -> bool {
+        self.update(key, time, |attempts| {
+            let is_outdated = time
+                .duration_since(attempts.last_reservation)
+                .is_ok_and(|elapsed| elapsed >= pending_timeout);
+            if is_outdated {
+                attempts.pending = 0;
+            }
+            if attempts.pending >= max_pending {
+                return false;
+            }
+            attempts.pending += 1;
+            attempts.last_reservation = time;
+            true
+        })
+    }
+
+    async fn record_failure(&self, key: &LoginAttemptKey, time: SystemTime, reset_after: Duration) {
+        self.update(key, time, |attempts| {
+            let is_outdated = time
+                .duration_since(attempts.last_failure)
+                .is_ok_and(|elapsed| elapsed >= reset_after);
+            if is_outdated {
+                attempts.failures = 0;
+            }
+            attempts.failures = attempts.failures.saturating_add(1);
+            attempts.last_failure = time;
+            attempts.pending = attempts.pending.saturating_sub(1);
+        })
+    }
+
+    async fn release_attempt(&self, key: &LoginAttemptKey) {
+        let mut all_attempts = self.attempts.lock().expect("Failed to acquire lock");
+        if let Some(attempts) = all_attempts.get_mut(key) {
+            attempts.pending = attempts.pending.saturating_sub(1);
+            if attempts.failures == 0 && attempts.pending == 0 {
+                all_attempts.remove(key);
+            }
+        }
+    }
+
+    async fn reset(&self, key: &LoginAttemptKey) {
+        let mut all_attempts = self.attempts.lock().expect("Failed to acquire lock");
+        if let Some(attempts) = all_attempts.get_mut(key) {
+            attempts.failures = 0;
+            if attempts.pending == 0 {
+                all_attempts.remove(key);
+            }
+        }
+    }
+}
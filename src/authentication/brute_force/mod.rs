@@ -0,0 +1,283 @@
+//! Brute-force protection for basic authentication. Failed login attempts are tracked per username and client IP
+//! in a `LoginAttemptStore`. After a configurable number of failures further attempts are delayed with an exponential
+//! back-off and finally the username is locked for the client IP for a while. Blocked attempts are rejected by the
+//! `HttpAuthenticationMiddleware` with a `TooManyAttemptsError` (`429` with a `Retry-After` header) without
+//! verifying the credentials. Attempts are reserved before the credentials are verified and the number of pending
+//! attempts of a username and client IP is limited, so that concurrent attempts cannot bypass the back-off.
+
+use std::net::IpAddr;
+use std::time::{Duration, SystemTime};
+
+use actix_web::dev::HttpResponseBuilder;
+use actix_web::http::{header, StatusCode};
+use actix_web::{error, HttpResponse};
+use async_trait::async_trait;
+use derive_more::{Display, Error};
+
+use crate::authentication::error::error_handler::auth_error_content_type;
+
+pub mod in_memory_store;
+
+/// The key failed login attempts are tracked for.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct LoginAttemptKey {
+    /// The username of the login attempt
+    pub username: String,
+    /// The IP address of the client (`None` if unknown)
+    pub client_ip: Option<IpAddr>,
+}
+
+/// The failed and pending login attempts of a `LoginAttemptKey`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LoginAttempts {
+    /// The number of consecutive failed attempts
+    pub failures: u32,
+    /// The time of the last failed attempt (only meaningful if there are failures)
+    pub last_failure: SystemTime,
+    /// The number of reserved attempts whose credentials are being verified
+    pub pending: u32,
+    /// The time of the last reserved attempt (only meaningful if there are pending attempts)
+    pub last_reservation: SystemTime,
+}
+
+/// Trait definition of a store for failed login attempts.
+#[async_trait]
+pub trait LoginAttemptStore: LoginAttemptStoreClone {
+    /// Returns the failed and pending attempts of the key.
+    async fn find_attempts(&self, key: &LoginAttemptKey) -> Option<LoginAttempts>;
+
+    /// Reserves an attempt at the given time if fewer than `max_pending` attempts of the key are pending and returns
+    /// **true** if the attempt has been reserved. Pending attempts reserved more than `pending_timeout` before the
+    /// given time (e.g. of cancelled requests) have to be discarded. Implementations have to update the attempts
+    /// atomically.
+    async fn reserve_attempt(
+        &self,
+        key: &LoginAttemptKey,
+        time: SystemTime,
+        max_pending: u32,
+        pending_timeout: Duration,
+    ) -> bool;
+
+    /// Records a failed attempt at the given time and resolves a pending attempt of the key (if any).
+    /// The number of failures has to restart at one if the last failure is older than `reset_after`.
+    /// Implementations have to update the attempts atomically.
+    async fn record_failure(&self, key: &LoginAttemptKey, time: SystemTime, reset_after: Duration);
+
+    /// Resolves a pending attempt of the key without counting it as failure.
+    async fn release_attempt(&self, key: &LoginAttemptKey);
+
+    /// Removes the failed attempts of the key (after a successful login). Pending attempts are kept.
+    async fn reset(&self, key: &LoginAttemptKey);
+}
+
+/// A login attempt store must be cloneable, `send` and `sync`.
+/// Therefore it has to implement the `LoginAttemptStoreClone` trait to be cloneable as a boxed object.
+pub trait LoginAttemptStoreClone: Send + Sync {
+    fn clone_box(&self) -> Box<dyn LoginAttemptStore>;
+}
+
+impl<U> LoginAttemptStoreClone for U
+where
+    U: 'static + LoginAttemptStore + Clone,
+{
+    fn clone_box(&self) -> Box<dyn LoginAttemptStore> {
+        Box::new(self.clone())
+    }
+}
+
+impl Clone for Box<dyn LoginAttemptStore> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
+/// The brute-force protection policy. The defaults are:
+/// * Exponential back-off starting with 1 second after 3 failures, doubled with every further failure
+///   and capped at 5 minutes
+/// * Lockout for 15 minutes after 10 failures
+/// * Failures are forgotten 1 hour after the last failure
+/// * At most 10 pending attempts, pending attempts are discarded after 1 minute
+#[derive(Clone)]
+pub struct BruteForceProtection {
+    store: Box<dyn LoginAttemptStore>,
+    backoff_threshold: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    lockout_threshold: u32,
+    lockout_duration: Duration,
+    reset_after: Duration,
+    max_pending_attempts: u32,
+    pending_timeout: Duration,
+}
+
+impl BruteForceProtection {
+    /// Constructs an instance with the default policy for a boxed `LoginAttemptStore`.
+    pub fn new(store: Box<dyn LoginAttemptStore>) -> BruteForceProtection {
+        BruteForceProtection {
+            store,
+            backoff_threshold: 3,
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(300),
+            lockout_threshold: 10,
+            lockout_duration: Duration::from_secs(900),
+            reset_after: Duration::from_secs(3600),
+            max_pending_attempts: 10,
+            pending_timeout: Duration::from_secs(60),
+        }
+    }
+
+    /// Sets the number of failures after which the back-off starts, the back-off after the first of these failures
+    /// and the maximum back-off.
+    pub fn with_backoff(
+        mut self,
+        threshold: u32,
+        initial_backoff: Duration,
+        max_backoff: Duration,
+    ) -> BruteForceProtection {
+        self.backoff_threshold = threshold;
+        self.initial_backoff = initial_backoff;
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    /// Sets the number of failures after which the username is locked for the client IP and the lockout duration.
+    pub fn with_lockout(mut self, threshold: u32, duration: Duration) -> BruteForceProtection {
+        self.lockout_threshold = threshold;
+        self.lockout_duration = duration;
+        self
+    }
+
+    /// Sets the duration after the last failure after which the failures are forgotten.
+    pub fn with_reset_after(mut self, reset_after: Duration) -> BruteForceProtection {
+        self.reset_after = reset_after;
+        self
+    }
+
+    /// Sets the number of attempts whose credentials may be verified concurrently and the time after which
+    /// unresolved attempts (e.g. of cancelled requests) are discarded. Further attempts are blocked for the initial
+    /// back-off. Concurrent attempts are only counted as failures once they are rejected, so up to `max_pending`
+    /// attempts may be made before the back-off applies.
+    pub fn with_max_pending_attempts(
+        mut self,
+        max_pending: u32,
+        timeout: Duration,
+    ) -> BruteForceProtection {
+        self.max_pending_attempts = max_pending;
+        self.pending_timeout = timeout;
+        self
+    }
+
+    /// Returns the remaining time the key is blocked or `None` if a login attempt is allowed.
+    pub async fn check(&self, key: &LoginAttemptKey) -> Option<Duration> {
+        let attempts = self.store.find_attempts(key).await?;
+        self.remaining_block_duration(&attempts, SystemTime::now())
+    }
+
+    /// Reserves a login attempt before the credentials are verified. Returns the remaining time the key is blocked
+    /// or `None` if the attempt is allowed. Allowed attempts are pending until they are resolved with
+    /// `record_failure` or `release_attempt`.
+    pub async fn reserve_attempt(&self, key: &LoginAttemptKey) -> Option<Duration> {
+        // Blocked attempts are rejected without being reserved
+        if let Some(retry_after) = self.check(key).await {
+            return Some(retry_after);
+        }
+        let reserved = self
+            .store
+            .reserve_attempt(
+                key,
+                SystemTime::now(),
+                self.max_pending_attempts,
+                self.pending_timeout,
+            )
+            .await;
+        if reserved {
+            None
+        } else {
+            Some(self.initial_backoff)
+        }
+    }
+
+    /// Releases a reserved attempt without counting it as failure (after a successful login or if the credentials
+    /// could not be verified, e.g. because the user store is unavailable).
+    pub async fn release_attempt(&self, key: &LoginAttemptKey) {
+        self.store.release_attempt(key).await;
+    }
+
+    /// Records a failed login attempt (and resolves a reserved attempt).
+    pub async fn record_failure(&self, key: &LoginAttemptKey) {
+        self.store
+            .record_failure(key, SystemTime::now(), self.reset_after)
+            .await;
+    }
+
+    /// Resets the failed login attempts after a successful login.
+    pub async fn record_success(&self, key: &LoginAttemptKey) {
+        self.store.reset(key).await;
+    }
+
+    fn remaining_block_duration(
+        &self,
+        attempts: &LoginAttempts,
+        now: SystemTime,
+    ) -> Option<Duration> {
+        if attempts.failures == 0
+            || now.duration_since(attempts.last_failure).ok()? >= self.reset_after
+        {
+            return None;
+        }
+        let blocked_until = attempts.last_failure + self.block_duration(attempts.failures)?;
+        blocked_until.duration_since(now).ok()
+    }
+
+    fn block_duration(&self, failures: u32) -> Option<Duration> {
+        if failures >= self.lockout_threshold {
+            Some(self.lockout_duration)
+        } else if failures >= self.backoff_threshold {
+            let exponent = (failures - self.backoff_threshold).min(31);
+            Some(
+                self.initial_backoff
+                    .checked_mul(1 << exponent)
+                    .map_or(self.max_backoff, |backoff| backoff.min(self.max_backoff)),
+            )
+        } else {
+            None
+        }
+    }
+}
+
+/// Error returned if a login attempt is blocked by the `BruteForceProtection`.
+/// The error response has the status code `429` and a `Retry-After` header with the remaining seconds.
+#[derive(Debug, Display, Error, PartialEq, Eq, Clone)]
+#[display(fmt = "Too many failed login attempts")]
+pub struct TooManyAttemptsError {
+    #[error(not(source))]
+    retry_after: Duration,
+}
+
+impl TooManyAttemptsError {
+    /// Constructs a new instance for the time after which the login can be retried.
+    pub fn new(retry_after: Duration) -> TooManyAttemptsError {
+        TooManyAttemptsError { retry_after }
+    }
+
+    /// Returns the time after which the login can be retried.
+    pub fn retry_after(&self) -> Duration {
+        self.retry_after
+    }
+}
+
+impl error::ResponseError for TooManyAttemptsError {
+    fn status_code(&self) -> StatusCode {
+        StatusCode::TOO_MANY_REQUESTS
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        // Round up, so that a retry is not rejected again
+        let retry_after =
+            self.retry_after.as_secs() + u64::from(self.retry_after.subsec_nanos() > 0);
+        HttpResponseBuilder::new(self.status_code())
+            .set_header(header::CONTENT_TYPE, auth_error_content_type())
+            .set_header(header::RETRY_AFTER, retry_after.to_string())
+            .body("too many failed login attempts")
+    }
+}
@@ -25,6 +25,15 @@ static AUTH_ERROR_STATUS_CODE_MAPPING: Lazy<HashMap<AuthenticationError, u16>> =
         AuthenticationError::AuthorizationHeaderNotSet,
         &mut error_codes,
     );
+    add_env_error_code(AuthenticationError::AccountDisabled, &mut error_codes);
+    add_env_error_code(AuthenticationError::AccountLocked, &mut error_codes);
+    add_env_error_code(AuthenticationError::AccountExpired, &mut error_codes);
+    add_env_error_code(AuthenticationError::CredentialsExpired, &mut error_codes);
+    add_env_error_code_with_default(
+        AuthenticationError::ServiceUnavailable,
+        503,
+        &mut error_codes,
+    );
     error_codes
 });
 
@@ -55,6 +64,31 @@ static AUTH_ERROR_MESSAGE_MAPPING: Lazy<HashMap<AuthenticationError, String>> =
         "authorization header not set".to_string(),
         &mut error_messages,
     );
+    add_env_error_message(
+        AuthenticationError::AccountDisabled,
+        "account disabled".to_string(),
+        &mut error_messages,
+    );
+    add_env_error_message(
+        AuthenticationError::AccountLocked,
+        "account locked".to_string(),
+        &mut error_messages,
+    );
+    add_env_error_message(
+        AuthenticationError::AccountExpired,
+        "account expired".to_string(),
+        &mut error_messages,
+    );
+    add_env_error_message(
+        AuthenticationError::CredentialsExpired,
+        "credentials expired".to_string(),
+        &mut error_messages,
+    );
+    add_env_error_message(
+        AuthenticationError::ServiceUnavailable,
+        "service unavailable".to_string(),
+        &mut error_messages,
+    );
     error_messages
 });
 
@@ -67,13 +101,21 @@ static AUTH_ERROR_CONTENT_TYPE: Lazy<String> =
 fn add_env_error_code(
     error: AuthenticationError,
     error_codes: &mut HashMap<AuthenticationError, u16>,
+) {
+    add_env_error_code_with_default(error, 401, error_codes);
+}
+
+fn add_env_error_code_with_default(
+    error: AuthenticationError,
+    default_code: u16,
+    error_codes: &mut HashMap<AuthenticationError, u16>,
 ) {
     match std::env::var(format!("{}_code", error)) {
         Ok(code) => error_codes.insert(
             error,
             code.parse::<u16>().expect("Invalid status code mapping"),
         ),
-        _ => error_codes.insert(error, 401),
+        _ => error_codes.insert(error, default_code),
     };
 }
 
@@ -126,16 +168,36 @@ impl error::ResponseError for AuthenticationError {
             AuthenticationError::InvalidToken => {
                 dynamic_status_code(&AuthenticationError::InvalidToken)
             }
+            AuthenticationError::AccountDisabled => {
+                dynamic_status_code(&AuthenticationError::AccountDisabled)
+            }
+            AuthenticationError::AccountLocked => {
+                dynamic_status_code(&AuthenticationError::AccountLocked)
+            }
+            AuthenticationError::AccountExpired => {
+                dynamic_status_code(&AuthenticationError::AccountExpired)
+            }
+            AuthenticationError::CredentialsExpired => {
+                dynamic_status_code(&AuthenticationError::CredentialsExpired)
+            }
+            AuthenticationError::ServiceUnavailable => {
+                dynamic_status_code(&AuthenticationError::ServiceUnavailable)
+            }
         }
     }
 
     fn error_response(&self) -> HttpResponse {
         HttpResponseBuilder::new(self.status_code())
-            .set_header(header::CONTENT_TYPE, AUTH_ERROR_CONTENT_TYPE.to_string())
+            .set_header(header::CONTENT_TYPE, auth_error_content_type())
             .body(dynamic_error_message(self))
     }
 }
 
+/// Returns the content type of error responses.
+pub(crate) fn auth_error_content_type() -> String {
+    AUTH_ERROR_CONTENT_TYPE.to_string()
+}
+
 fn dynamic_status_code(error: &AuthenticationError) -> StatusCode {
     StatusCode::from_u16(
         *AUTH_ERROR_STATUS_CODE_MAPPING
@@ -3,7 +3,9 @@
 use derive_more::{Display, Error};
 
 /// Authentication related errors.
+/// The enum is non-exhaustive, so that further errors can be added without breaking matches on it.
 #[derive(Debug, Display, Error, PartialEq, Eq, Hash, Clone)]
+#[non_exhaustive]
 pub enum AuthenticationError {
     /// No `Authorization` header is set.
     #[display(fmt = "Authorization header not set")]
@@ -24,6 +26,26 @@ pub enum AuthenticationError {
     /// A user to be authenticated cannot be found or cannot be retrieved from the request context/extensions.
     #[display(fmt = "Access denied")]
     UsernameNotFound,
+
+    /// The account of the authenticated user has been disabled.
+    #[display(fmt = "Account disabled")]
+    AccountDisabled,
+
+    /// The account of the authenticated user has been locked.
+    #[display(fmt = "Account locked")]
+    AccountLocked,
+
+    /// The account of the authenticated user has expired.
+    #[display(fmt = "Account expired")]
+    AccountExpired,
+
+    /// The credentials of the authenticated user have expired.
+    #[display(fmt = "Credentials expired")]
+    CredentialsExpired,
+
+    /// The authentication could not be completed because a required service (e.g. the user store) is unavailable.
+    #[display(fmt = "Authentication service unavailable")]
+    ServiceUnavailable,
 }
 
 /// Errors related to JWT loading problems.
@@ -45,3 +67,31 @@ pub enum JwkLoaderError {
     #[display(fmt = "JWKs could not be downloaded")]
     JwksCouldNotBeDownloaded,
 }
+
+/// Errors related to password encoding.
+#[derive(Debug, Display, Error, PartialEq, Eq, Hash, Clone)]
+pub enum PasswordEncoderError {
+    /// The parameters of the password encoder are invalid.
+    #[display(fmt = "Invalid password encoder parameters")]
+    InvalidParameters,
+
+    /// The password could not be encoded.
+    #[display(fmt = "The password could not be encoded")]
+    EncodingFailed,
+}
+
+/// Errors related to LDAP directory operations.
+#[derive(Debug, Display, Error, PartialEq, Eq, Hash, Clone)]
+pub enum LdapError {
+    /// The connection to the directory could not be established.
+    #[display(fmt = "The connection to the LDAP server failed")]
+    ConnectionFailed,
+
+    /// The bind was rejected because of invalid credentials.
+    #[display(fmt = "Invalid LDAP credentials")]
+    InvalidCredentials,
+
+    /// A directory operation failed.
+    #[display(fmt = "The LDAP operation failed")]
+    OperationFailed,
+}
@@ -0,0 +1,90 @@
+//! An `AuthenticationEventListener` writing the events as JSON lines (e.g. to an audit log file).
+
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+use serde_json::{json, Value};
+use time::OffsetDateTime;
+
+use crate::authentication::event::{
+    AuthenticationEvent, AuthenticationEventListener, AuthenticationOutcome,
+};
+
+/// An `AuthenticationEventListener` writing every event as a JSON object on a separate line, e.g.  
+/// `{"timestamp":"2021-05-02T10:15:30.123Z","outcome":"failure","error":"UsernameNotFound","principal":"admin",
+/// "scheme":"Basic","method":"GET","endpoint":"/api","client_ip":"10.0.0.1"}`  
+/// Write errors are ignored, so that the authentication is not affected by an unavailable audit log.
+///
+/// The lines are written and flushed synchronously on the event loop of the worker, i.e. the sink blocks the worker
+/// while writing. It is therefore only suited for fast writers such as local files. Slow writers (e.g. network shares)
+/// should be wrapped in a writer that hands the data over to a background thread.
+#[derive(Clone)]
+pub struct JsonLinesAuditSink {
+    writer: Arc<Mutex<Box<dyn Write + Send>>>,
+}
+
+impl JsonLinesAuditSink {
+    /// Constructs a new instance writing to the given writer. Every line is flushed immediately.
+    pub fn new<W: Write + Send + 'static>(writer: W) -> JsonLinesAuditSink {
+        JsonLinesAuditSink {
+            writer: Arc::new(Mutex::new(Box::new(writer))),
+        }
+    }
+
+    /// Constructs a new instance appending to the file at the given path (the file is created if it does not exist).
+    pub fn to_file<P: AsRef<Path>>(path: P) -> io::Result<JsonLinesAuditSink> {
+        let file: File = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(JsonLinesAuditSink::new(file))
+    }
+}
+
+impl AuthenticationEventListener for JsonLinesAuditSink {
+    fn on_event(&self, event: &AuthenticationEvent) {
+        let line = to_json(event).to_string();
+        let mut writer = self.writer.lock().expect("Failed to acquire lock");
+        let _ = writeln!(writer, "{}", line).and_then(|_| writer.flush());
+    }
+}
+
+fn to_json(event: &AuthenticationEvent) -> Value {
+    let mut json = json!({
+        "timestamp": format_timestamp(event.timestamp),
+        "principal": event.principal,
+        "scheme": event.scheme,
+        "method": event.method,
+        "endpoint": event.endpoint,
+        "client_ip": event.client_ip.map(|ip| ip.to_string()),
+    });
+    match &event.outcome {
+        AuthenticationOutcome::Success => {
+            json["outcome"] = json!("success");
+        }
+        AuthenticationOutcome::Failure(error) => {
+            json["outcome"] = json!("failure");
+            json["error"] = json!(format!("{:?}", error));
+        }
+        AuthenticationOutcome::Blocked { retry_after } => {
+            json["outcome"] = json!("blocked");
+            json["retry_after"] = json!(retry_after.as_secs());
+        }
+    }
+    json
+}
+
+/// Formats the time as RFC 3339 timestamp in UTC with millisecond precision.
+fn format_timestamp(time: SystemTime) -> String {
+    let time = OffsetDateTime::from(time);
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+        time.year(),
+        u8::from(time.month()),
+        time.day(),
+        time.hour(),
+        time.minute(),
+        time.second(),
+        time.millisecond()
+    )
+}
@@ -0,0 +1,85 @@
+//! Authentication events for auditing and monitoring. The `HttpAuthenticationMiddleware` publishes an
+//! `AuthenticationEvent` for every authentication of a matched endpoint to the registered
+//! `AuthenticationEventListener`s (see `HttpAuthenticationMiddleware::with_event_listener`).
+
+use std::net::IpAddr;
+use std::time::{Duration, SystemTime};
+
+use crate::authentication::error::error_type::AuthenticationError;
+use crate::authentication::scheme::authentication::Authentication;
+use crate::authentication::scheme::basic::BasicAuthentication;
+use crate::authentication::scheme::bearer::jwt::JwtBearerAuthentication;
+
+pub mod json_lines_sink;
+
+/// The outcome of an authentication.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AuthenticationOutcome {
+    /// The authentication succeeded.
+    Success,
+    /// The authentication failed (including missing or invalid authorization headers).
+    Failure(AuthenticationError),
+    /// The authentication was not attempted because of too many failed attempts (see `BruteForceProtection`).
+    Blocked {
+        /// The time after which the client may try again
+        retry_after: Duration,
+    },
+}
+
+/// An authentication event.
+#[derive(Clone, Debug)]
+pub struct AuthenticationEvent {
+    /// The outcome of the authentication
+    pub outcome: AuthenticationOutcome,
+    /// The username (basic authentication) or token subject (bearer authentication) if available
+    pub principal: Option<String>,
+    /// The scheme of the authorization header (e.g. `Basic` or `Bearer`) if set
+    pub scheme: Option<String>,
+    /// The HTTP method of the request
+    pub method: String,
+    /// The path of the requested endpoint
+    pub endpoint: String,
+    /// The IP address of the client (taken from the forwarded headers if
+    /// `HttpAuthenticationMiddleware::with_forwarded_client_ip` is enabled, otherwise the IP address of the peer)
+    pub client_ip: Option<IpAddr>,
+    /// The time of the authentication
+    pub timestamp: SystemTime,
+}
+
+/// Trait definition of a listener for `AuthenticationEvent`s.
+/// The listeners are called on the event loop of the worker and must therefore not block for long.
+pub trait AuthenticationEventListener: AuthenticationEventListenerClone {
+    fn on_event(&self, event: &AuthenticationEvent);
+}
+
+/// An event listener must be cloneable, `send` and `sync`.
+/// Therefore it has to implement the `AuthenticationEventListenerClone` trait to be cloneable as a boxed object.
+pub trait AuthenticationEventListenerClone: Send + Sync {
+    fn clone_box(&self) -> Box<dyn AuthenticationEventListener>;
+}
+
+impl<U> AuthenticationEventListenerClone for U
+where
+    U: 'static + AuthenticationEventListener + Clone,
+{
+    fn clone_box(&self) -> Box<dyn AuthenticationEventListener> {
+        Box::new(self.clone())
+    }
+}
+
+impl Clone for Box<dyn AuthenticationEventListener> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
+/// Returns the username or token subject of the built-in `Authentication` types.
+pub(crate) fn principal_name(authentication: &dyn Authentication) -> Option<String> {
+    if let Some(basic_auth) = authentication.downcast_ref::<BasicAuthentication>() {
+        Some(basic_auth.username.clone())
+    } else {
+        authentication
+            .downcast_ref::<JwtBearerAuthentication>()
+            .and_then(|jwt_auth| jwt_auth.token.subject().map(String::from))
+    }
+}
@@ -2,18 +2,30 @@
 
 use std::cell::RefCell;
 use std::future::{self, Future, Ready};
+use std::net::{IpAddr, SocketAddr};
 use std::pin::Pin;
 use std::rc::Rc;
 use std::sync::Arc;
 use std::task::{Context, Poll};
+use std::time::SystemTime;
 
 use actix_web::dev::{Service, ServiceRequest, ServiceResponse, Transform};
-use actix_web::Error;
+use actix_web::http::header;
+use actix_web::{Error, HttpMessage};
 
+use crate::authentication::brute_force::{
+    BruteForceProtection, LoginAttemptKey, TooManyAttemptsError,
+};
 use crate::authentication::endpoint_matcher::EndpointMatcher;
 use crate::authentication::error::error_type::AuthenticationError;
+use crate::authentication::event::{
+    principal_name, AuthenticationEvent, AuthenticationEventListener, AuthenticationOutcome,
+};
+use crate::authentication::scheme::authentication::Authentication;
+use crate::authentication::scheme::basic::BasicAuthentication;
 use crate::authentication::scheme::header_extractor::AuthorizationHeaderExtractor;
 use crate::authentication::ProviderManager;
+use crate::instrumentation::{self, RequestSpan};
 use crate::user_details::attachment::UserDetailsRequestAttachmentHelper;
 
 /// The `HttpAuthenticationMiddleware` is an actix middleware that wraps client requets, initiates
@@ -28,6 +40,9 @@ where
     authorization_extractor: Box<T>,
     provider_manager: ProviderManager,
     endpoint_matcher: Box<U>,
+    brute_force_protection: Option<BruteForceProtection>,
+    event_listeners: Vec<Box<dyn AuthenticationEventListener>>,
+    use_forwarded_client_ip: bool,
 }
 
 impl<T: AuthorizationHeaderExtractor + Clone, U: EndpointMatcher + Clone>
@@ -45,8 +60,39 @@ impl<T: AuthorizationHeaderExtractor + Clone, U: EndpointMatcher + Clone>
             authorization_extractor,
             provider_manager,
             endpoint_matcher,
+            brute_force_protection: None,
+            event_listeners: Vec::new(),
+            use_forwarded_client_ip: false,
         }
     }
+
+    /// Enables the `BruteForceProtection` for basic authentications. Login attempts of blocked username and
+    /// client IP combinations are rejected with a `TooManyAttemptsError` without verifying the credentials.
+    pub fn with_brute_force_protection(
+        mut self,
+        brute_force_protection: BruteForceProtection,
+    ) -> HttpAuthenticationMiddleware<T, U> {
+        self.brute_force_protection = Some(brute_force_protection);
+        self
+    }
+
+    /// Registers an `AuthenticationEventListener` to be notified about the outcome of every authentication.
+    /// Multiple listeners can be registered and are called in the order of registration.
+    pub fn with_event_listener(
+        mut self,
+        event_listener: Box<dyn AuthenticationEventListener>,
+    ) -> HttpAuthenticationMiddleware<T, U> {
+        self.event_listeners.push(event_listener);
+        self
+    }
+
+    /// Takes the client IP from the `Forwarded` / `X-Forwarded-For` headers instead of the peer address
+    /// (for the `BruteForceProtection` as well as for the `AuthenticationEvent`s).
+    /// Only enable this behind a reverse proxy that sets these headers, as clients can set them as well.
+    pub fn with_forwarded_client_ip(mut self, enabled: bool) -> HttpAuthenticationMiddleware<T, U> {
+        self.use_forwarded_client_ip = enabled;
+        self
+    }
 }
 
 impl<S, B, T, U> Transform<S> for HttpAuthenticationMiddleware<T, U>
@@ -65,13 +111,18 @@ where
     fn new_transform(&self, service: S) -> Self::Future {
         let service = Rc::new(RefCell::new(service));
         let provider_manager = Arc::new(self.provider_manager.clone());
-        let authorization_extractor = Arc::new(self.authorization_extractor.clone());
-        let endpoint_matcher = Arc::new(self.endpoint_matcher.clone());
+        let authorization_extractor = Arc::from(self.authorization_extractor.clone());
+        let endpoint_matcher = Arc::from(self.endpoint_matcher.clone());
+        let brute_force_protection = self.brute_force_protection.clone().map(Arc::new);
+        let event_listeners = Arc::new(self.event_listeners.clone());
         future::ready(Ok(HttpAuthenticationService {
             service,
             provider_manager,
             authorization_extractor,
             endpoint_matcher,
+            brute_force_protection,
+            event_listeners,
+            use_forwarded_client_ip: self.use_forwarded_client_ip,
         }))
     }
 }
@@ -84,8 +135,11 @@ pub struct HttpAuthenticationService<
 > {
     service: Rc<RefCell<S>>,
     provider_manager: Arc<ProviderManager>,
-    authorization_extractor: Arc<Box<T>>,
-    endpoint_matcher: Arc<Box<U>>,
+    authorization_extractor: Arc<T>,
+    endpoint_matcher: Arc<U>,
+    brute_force_protection: Option<Arc<BruteForceProtection>>,
+    event_listeners: Arc<Vec<Box<dyn AuthenticationEventListener>>>,
+    use_forwarded_client_ip: bool,
 }
 
 impl<S, B, T, U> Service for HttpAuthenticationService<S, T, U>
@@ -110,34 +164,58 @@ where
         if handle_request {
             let authorization_extractor = Arc::clone(&self.authorization_extractor);
             let provider_manager = Arc::clone(&self.provider_manager);
-            Box::pin(async move {
-                let error: Option<AuthenticationError>;
+            let brute_force_protection = self.brute_force_protection.clone();
+            let event_listeners = Arc::clone(&self.event_listeners);
+            let client_ip = client_ip(&req, self.use_forwarded_client_ip);
+            let request_span = RequestSpan::new(&req);
+            Box::pin(request_span.instrument(async move {
+                let mut principal: Option<String> = None;
 
-                let extracted_token = authorization_extractor.extract_token(&req.headers()).await;
-                match extracted_token {
+                let extracted_token = authorization_extractor.extract_token(req.headers()).await;
+                instrumentation::header_extracted(&extracted_token);
+                let outcome = match extracted_token {
                     Ok(token) => {
-                        let authentication_result = provider_manager.authenticate(&token).await;
-                        match authentication_result {
-                            Ok(result) => {
-                                req.attach(result);
-                                error = None;
-                            }
-                            Err(e) => error = Some(e),
-                        };
+                        principal = principal_name(token.as_ref());
+                        authenticate_request(
+                            &req,
+                            token,
+                            client_ip,
+                            &provider_manager,
+                            brute_force_protection,
+                        )
+                        .await
                     }
-                    Err(e) => error = Some(e),
+                    Err(e) => AuthenticationOutcome::Failure(e),
                 };
+                // The principal of failed authentications is unverified (and may even be a mistyped password)
+                if let (AuthenticationOutcome::Success, Some(principal)) = (&outcome, &principal) {
+                    instrumentation::record_principal(principal);
+                }
+                instrumentation::authentication_decided(
+                    authentication_scheme(&req).as_deref(),
+                    &outcome,
+                );
 
-                match error {
-                    Some(e) => Err(e.into()),
-                    None => {
+                if !event_listeners.is_empty() {
+                    let event = authentication_event(&req, principal, outcome.clone(), client_ip);
+                    for event_listener in event_listeners.iter() {
+                        event_listener.on_event(&event);
+                    }
+                }
+
+                match outcome {
+                    AuthenticationOutcome::Success => {
                         let fut = service.borrow_mut().call(req);
                         let res = fut.await?;
 
                         Ok(res)
                     }
+                    AuthenticationOutcome::Failure(e) => Err(e.into()),
+                    AuthenticationOutcome::Blocked { retry_after } => {
+                        Err(TooManyAttemptsError::new(retry_after).into())
+                    }
                 }
-            })
+            }))
         } else {
             Box::pin(async move {
                 let fut = service.borrow_mut().call(req);
@@ -148,3 +226,100 @@ where
         }
     }
 }
+
+/// Authenticates the extracted token with the `ProviderManager` (considering the `BruteForceProtection`)
+/// and attaches the user details and the authentication to the request on success.
+async fn authenticate_request(
+    req: &ServiceRequest,
+    token: Box<dyn Authentication>,
+    client_ip: Option<IpAddr>,
+    provider_manager: &ProviderManager,
+    brute_force_protection: Option<Arc<BruteForceProtection>>,
+) -> AuthenticationOutcome {
+    let brute_force_protection = brute_force_protection
+        .and_then(|protection| login_attempt_key(&token, client_ip).map(|key| (protection, key)));
+    if let Some((protection, key)) = &brute_force_protection {
+        if let Some(retry_after) = protection.reserve_attempt(key).await {
+            return AuthenticationOutcome::Blocked { retry_after };
+        }
+    }
+
+    let authentication_result = provider_manager.authenticate(&token).await;
+
+    if let Some((protection, key)) = &brute_force_protection {
+        match authentication_result {
+            Ok(_) => {
+                protection.release_attempt(key).await;
+                protection.record_success(key).await;
+            }
+            // The credentials could not be verified
+            Err(AuthenticationError::ServiceUnavailable) => protection.release_attempt(key).await,
+            Err(_) => protection.record_failure(key).await,
+        }
+    }
+    match authentication_result {
+        Ok(result) => {
+            req.attach(result);
+            token.attach_to_request(&mut req.extensions_mut());
+            AuthenticationOutcome::Success
+        }
+        Err(e) => AuthenticationOutcome::Failure(e),
+    }
+}
+
+/// Creates the `AuthenticationEvent` of the request.
+fn authentication_event(
+    req: &ServiceRequest,
+    principal: Option<String>,
+    outcome: AuthenticationOutcome,
+    client_ip: Option<IpAddr>,
+) -> AuthenticationEvent {
+    AuthenticationEvent {
+        outcome,
+        principal,
+        scheme: authentication_scheme(req),
+        method: req.method().to_string(),
+        endpoint: req.path().to_string(),
+        client_ip,
+        timestamp: SystemTime::now(),
+    }
+}
+
+/// Returns the authentication scheme of the `Authorization` header of the request.
+fn authentication_scheme(req: &ServiceRequest) -> Option<String> {
+    req.headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.split_whitespace().next())
+        .map(String::from)
+}
+
+/// Returns the key to track the login attempts of basic authentications with.
+#[allow(clippy::borrowed_box)]
+fn login_attempt_key(
+    token: &Box<dyn Authentication>,
+    client_ip: Option<IpAddr>,
+) -> Option<LoginAttemptKey> {
+    let basic_auth = token.downcast_ref::<BasicAuthentication>()?;
+    Some(LoginAttemptKey {
+        username: basic_auth.username.clone(),
+        client_ip,
+    })
+}
+
+/// Returns the IP address of the client, taken from the `Forwarded` / `X-Forwarded-For` headers
+/// if `use_forwarded_client_ip` is set and from the peer address otherwise.
+fn client_ip(req: &ServiceRequest, use_forwarded_client_ip: bool) -> Option<IpAddr> {
+    if use_forwarded_client_ip {
+        req.connection_info()
+            .realip_remote_addr()
+            .and_then(|address| {
+                address
+                    .parse::<IpAddr>()
+                    .ok()
+                    .or_else(|| address.parse::<SocketAddr>().ok().map(|a| a.ip()))
+            })
+    } else {
+        req.peer_addr().map(|address| address.ip())
+    }
+}
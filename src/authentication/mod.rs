@@ -5,25 +5,57 @@
 use crate::authentication::error::error_type::AuthenticationError;
 use crate::authentication::scheme::authentication::Authentication;
 use crate::authentication::scheme::authentication_provider::AuthenticationProvider;
+use crate::instrumentation;
 use crate::user_details::UserDetails;
 
+pub mod brute_force;
 pub mod endpoint_matcher;
 pub mod error;
+pub mod event;
 pub mod middleware;
+pub mod resilience;
 pub mod scheme;
 
+/// The strategy a `ProviderManager` applies to the results of its authentication providers.
+/// Providers that abstain from the authentication (see `AuthenticationProvider`) are skipped by all strategies.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProviderStrategy {
+    /// The providers are called in order until one succeeds. Rejections of previous providers are ignored (default).
+    FirstSuccess,
+    /// The providers are called in order until one succeeds or rejects the authentication.
+    StopOnFirstRejection,
+    /// All applicable providers are called and must succeed. The `UserDetails` of the first provider are returned.
+    AllMustSucceed,
+}
+
 /// A provider manager can be used to register one or more authentication providers to be executed in
-/// a chain (until the authentication on a provider succeeds or fails on all providers).
+/// a chain according to the `ProviderStrategy` (until the authentication on a provider succeeds or fails on all
+/// providers by default). Providers are only called for the types of `Authentication` they support.
 /// A provider manager is registered in the middleware to execute the authentication process.
+///
+/// If the authentication fails, the error is selected deterministically: the first rejection of a provider takes
+/// precedence over `AuthenticationError::ServiceUnavailable`, which takes precedence over
+/// `AuthenticationError::InvalidAuthentication` if all providers abstained.
 #[derive(Clone)]
 pub struct ProviderManager {
     providers: Vec<Box<dyn AuthenticationProvider>>,
+    strategy: ProviderStrategy,
 }
 
 impl ProviderManager {
-    /// Constructs a new instance for the given vector of boxed authentication providers.
+    /// Constructs a new instance for the given vector of boxed authentication providers
+    /// with the `ProviderStrategy::FirstSuccess` strategy.
     pub fn new(providers: Vec<Box<dyn AuthenticationProvider>>) -> ProviderManager {
-        ProviderManager { providers }
+        ProviderManager {
+            providers,
+            strategy: ProviderStrategy::FirstSuccess,
+        }
+    }
+
+    /// Sets the `ProviderStrategy`.
+    pub fn with_strategy(mut self, strategy: ProviderStrategy) -> ProviderManager {
+        self.strategy = strategy;
+        self
     }
 
     #[allow(clippy::borrowed_box)]
@@ -31,21 +63,49 @@ impl ProviderManager {
         &self,
         authentication: &Box<dyn Authentication>,
     ) -> Result<Box<dyn UserDetails>, AuthenticationError> {
-        let providers = &self.providers;
-        let mut last_error: Option<AuthenticationError> = None;
-        for provider in providers {
-            let result = provider.authenticate(authentication).await;
+        let mut authenticated_user: Option<Box<dyn UserDetails>> = None;
+        let mut first_rejection: Option<AuthenticationError> = None;
+        let mut service_unavailable = false;
+
+        for (index, provider) in self.providers.iter().enumerate() {
+            if !provider.supports(authentication.as_ref()) {
+                instrumentation::provider_skipped(index);
+                continue;
+            }
+            let result = instrumentation::authenticate_with_provider(
+                index,
+                provider.authenticate(authentication),
+            )
+            .await;
             match result {
-                Ok(user) => return Ok(user),
+                Ok(user) => {
+                    if self.strategy != ProviderStrategy::AllMustSucceed {
+                        return Ok(user);
+                    }
+                    authenticated_user.get_or_insert(user);
+                }
+                // The provider abstains
+                Err(AuthenticationError::InvalidAuthentication) => (),
+                Err(AuthenticationError::ServiceUnavailable) => {
+                    if self.strategy == ProviderStrategy::AllMustSucceed {
+                        return Err(AuthenticationError::ServiceUnavailable);
+                    }
+                    service_unavailable = true;
+                }
                 Err(err) => {
-                    last_error = Some(err);
-                    continue;
+                    if self.strategy != ProviderStrategy::FirstSuccess {
+                        return Err(err);
+                    }
+                    first_rejection.get_or_insert(err);
                 }
             }
         }
-        match last_error {
-            Some(e) => Err(e),
-            None => Err(AuthenticationError::UsernameNotFound),
+
+        match (authenticated_user, first_rejection) {
+            (Some(user), _) => Ok(user),
+            (None, Some(err)) => Err(err),
+            (None, None) if service_unavailable => Err(AuthenticationError::ServiceUnavailable),
+            (None, None) => Err(AuthenticationError::InvalidAuthentication),
         }
     }
 }
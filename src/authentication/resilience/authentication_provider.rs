@@ -0,0 +1,92 @@
+//! An `AuthenticationProvider` decorator with a timeout and an optional circuit breaker.
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use futures_timer::Delay;
+use futures_util::future::{select, Either};
+
+use crate::authentication::error::error_type::AuthenticationError;
+use crate::authentication::resilience::CircuitBreaker;
+use crate::authentication::scheme::authentication::Authentication;
+use crate::authentication::scheme::authentication_provider::AuthenticationProvider;
+use crate::user_details::UserDetails;
+
+/// The default time an authentication provider may take.
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// An `AuthenticationProvider` wrapping another provider. Authentications that do not complete within the timeout
+/// (5 seconds by default) fail with `AuthenticationError::ServiceUnavailable`.
+/// With a `CircuitBreaker` the wrapped provider is not called anymore after repeated timeouts (or
+/// `ServiceUnavailable` errors of the wrapped provider) until the breaker lets a trial request through.
+/// Rejected credentials are regular results and do not count as failures.
+#[derive(Clone)]
+pub struct ResilientAuthenticationProvider {
+    provider: Box<dyn AuthenticationProvider>,
+    timeout: Duration,
+    circuit_breaker: Option<CircuitBreaker>,
+}
+
+impl ResilientAuthenticationProvider {
+    /// Constructs a new instance for the boxed `AuthenticationProvider` to be wrapped.
+    pub fn new(provider: Box<dyn AuthenticationProvider>) -> ResilientAuthenticationProvider {
+        ResilientAuthenticationProvider {
+            provider,
+            timeout: DEFAULT_TIMEOUT,
+            circuit_breaker: None,
+        }
+    }
+
+    /// Sets the time the wrapped provider may take. Defaults to 5 seconds.
+    pub fn with_timeout(mut self, timeout: Duration) -> ResilientAuthenticationProvider {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Sets the `CircuitBreaker`. A circuit breaker can be shared between several providers
+    /// that depend on the same external system.
+    pub fn with_circuit_breaker(
+        mut self,
+        circuit_breaker: CircuitBreaker,
+    ) -> ResilientAuthenticationProvider {
+        self.circuit_breaker = Some(circuit_breaker);
+        self
+    }
+}
+
+#[async_trait]
+impl AuthenticationProvider for ResilientAuthenticationProvider {
+    fn supports(&self, authentication: &dyn Authentication) -> bool {
+        self.provider.supports(authentication)
+    }
+
+    #[allow(clippy::borrowed_box)]
+    async fn authenticate(
+        &self,
+        authentication: &Box<dyn Authentication>,
+    ) -> Result<Box<dyn UserDetails>, AuthenticationError> {
+        if let Some(circuit_breaker) = &self.circuit_breaker {
+            if !circuit_breaker.try_acquire() {
+                return Err(AuthenticationError::ServiceUnavailable);
+            }
+        }
+
+        let result = match select(
+            self.provider.authenticate(authentication),
+            Delay::new(self.timeout),
+        )
+        .await
+        {
+            Either::Left((result, _)) => result,
+            Either::Right(_) => Err(AuthenticationError::ServiceUnavailable),
+        };
+
+        if let Some(circuit_breaker) = &self.circuit_breaker {
+            match result {
+                Err(AuthenticationError::ServiceUnavailable) => circuit_breaker.record_failure(),
+                _ => circuit_breaker.record_success(),
+            }
+        }
+        result
+    }
+}
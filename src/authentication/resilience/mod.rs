@@ -0,0 +1,98 @@
+//! Resilience for authentication providers depending on external systems (e.g. a user database or a directory).
+//! The `ResilientAuthenticationProvider` limits the time an authentication provider may take and stops calling it
+//! with a `CircuitBreaker` after repeated failures, so that an outage of the external system fails fast with
+//! `AuthenticationError::ServiceUnavailable` (`503`) instead of blocking every authenticated request.
+
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+pub mod authentication_provider;
+
+/// The state of a `CircuitBreaker`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CircuitState {
+    /// Requests are passed to the authentication provider.
+    Closed,
+    /// Requests are rejected without calling the authentication provider.
+    Open,
+    /// A single trial request is passed to the authentication provider to check whether it has recovered.
+    HalfOpen,
+}
+
+#[derive(Clone, Copy)]
+enum BreakerState {
+    Closed { failures: u32 },
+    Open { until: Instant },
+    HalfOpen { since: Instant },
+}
+
+/// A circuit breaker opening after a number of consecutive failures (timeouts or unavailable services).
+/// While open, requests are rejected immediately. After the open duration a single trial request is let through:
+/// the circuit closes if it succeeds and opens again if it fails.
+/// The state is shared between all clones (and therefore all workers).
+#[derive(Clone)]
+pub struct CircuitBreaker {
+    state: Arc<Mutex<BreakerState>>,
+    failure_threshold: u32,
+    open_duration: Duration,
+}
+
+impl CircuitBreaker {
+    /// Constructs a new instance opening after `failure_threshold` consecutive failures for `open_duration`.
+    pub fn new(failure_threshold: u32, open_duration: Duration) -> CircuitBreaker {
+        assert!(
+            failure_threshold > 0,
+            "The failure threshold must be positive"
+        );
+        CircuitBreaker {
+            state: Arc::new(Mutex::new(BreakerState::Closed { failures: 0 })),
+            failure_threshold,
+            open_duration,
+        }
+    }
+
+    /// Returns the current state of the circuit.
+    pub fn state(&self) -> CircuitState {
+        match *self.state.lock().expect("Failed to acquire lock") {
+            BreakerState::Closed { .. } => CircuitState::Closed,
+            BreakerState::Open { until } if until > Instant::now() => CircuitState::Open,
+            BreakerState::Open { .. } | BreakerState::HalfOpen { .. } => CircuitState::HalfOpen,
+        }
+    }
+
+    /// Returns **true** if a request may be passed to the authentication provider.
+    /// A trial request that did not complete (e.g. because the request was cancelled) is replaced
+    /// by a new one after the open duration.
+    pub(crate) fn try_acquire(&self) -> bool {
+        let now = Instant::now();
+        let mut state = self.state.lock().expect("Failed to acquire lock");
+        match *state {
+            BreakerState::Closed { .. } => true,
+            BreakerState::Open { until } if until > now => false,
+            BreakerState::HalfOpen { since } if since + self.open_duration > now => false,
+            BreakerState::Open { .. } | BreakerState::HalfOpen { .. } => {
+                *state = BreakerState::HalfOpen { since: now };
+                true
+            }
+        }
+    }
+
+    pub(crate) fn record_success(&self) {
+        *self.state.lock().expect("Failed to acquire lock") = BreakerState::Closed { failures: 0 };
+    }
+
+    pub(crate) fn record_failure(&self) {
+        let mut state = self.state.lock().expect("Failed to acquire lock");
+        let failures = match *state {
+            BreakerState::Closed { failures } => failures.saturating_add(1),
+            _ => self.failure_threshold,
+        };
+        *state = if failures >= self.failure_threshold {
+            BreakerState::Open {
+                until: Instant::now() + self.open_duration,
+            }
+        } else {
+            BreakerState::Closed { failures }
+        };
+    }
+}
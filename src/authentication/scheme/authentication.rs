@@ -1,8 +1,13 @@
 //! The trait definition of authentications.
+use actix_web::dev::Extensions;
 use downcast_rs::impl_downcast;
 use downcast_rs::Downcast;
 
 /// The marker trait of an `Authentication` implementation. A `Authentication` object is a DTO that is used to transfer the extracted
 /// credentials / token data from the header to the actual authentication.
-pub trait Authentication: Downcast + Sync + Send {}
+pub trait Authentication: Downcast + Sync + Send {
+    /// Attaches authorization related data (e.g. granted scopes) of a successful authentication to the request context.
+    /// Nothing is attached by default.
+    fn attach_to_request(self: Box<Self>, _extensions: &mut Extensions) {}
+}
 impl_downcast!(Authentication);
@@ -8,8 +8,21 @@ use crate::user_details::UserDetails;
 
 /// An authentication provider executes the authentication for an `Authentication` object and returns
 /// either a object of `UserDetails` (implementation of the marker trait) or an `AuthenticationError`.
+///
+/// A provider abstains from the authentication if it does not support the type of the `Authentication` object
+/// (see `supports`) or if it returns `AuthenticationError::InvalidAuthentication`. All other errors are
+/// considered as definitive rejection (except `AuthenticationError::ServiceUnavailable`).
+/// `InvalidAuthentication` must therefore only be returned for unsupported types of `Authentication` objects,
+/// failures of the backend (e.g. an unreachable database or directory) must be reported as `ServiceUnavailable`.
 #[async_trait]
 pub trait AuthenticationProvider: AuthenticationProviderClone {
+    /// Returns **true** if the provider can authenticate the type of the `Authentication` object
+    /// (e.g. `authentication.is::<BasicAuthentication>()`). The `ProviderManager` only calls providers for supported
+    /// authentications. All authentications are supported by default.
+    fn supports(&self, _authentication: &dyn Authentication) -> bool {
+        true
+    }
+
     #[allow(clippy::borrowed_box)]
     async fn authenticate(
         &self,
@@ -7,12 +7,15 @@ use crate::authentication::scheme::authentication::Authentication;
 use crate::authentication::scheme::authentication_provider::AuthenticationProvider;
 use crate::authentication::scheme::basic::user_details_service::BasicUserDetailsService;
 use crate::authentication::scheme::basic::BasicAuthentication;
+use crate::user_details::account_status::{AccountStatusChecker, DefaultAccountStatusChecker};
 use crate::user_details::UserDetails;
 
 /// The definition of a `BasicAuthenticationProvider`.
+/// The account status of the found user is verified with the `AccountStatusChecker`.
 #[derive(Clone)]
 pub struct BasicAuthenticationProvider {
     user_details_service: Box<dyn BasicUserDetailsService>,
+    account_status_checker: Box<dyn AccountStatusChecker>,
 }
 
 impl BasicAuthenticationProvider {
@@ -23,12 +26,26 @@ impl BasicAuthenticationProvider {
     ) -> BasicAuthenticationProvider {
         BasicAuthenticationProvider {
             user_details_service,
+            account_status_checker: Box::new(DefaultAccountStatusChecker::new()),
         }
     }
+
+    /// Replaces the `DefaultAccountStatusChecker`.
+    pub fn with_account_status_checker(
+        mut self,
+        account_status_checker: Box<dyn AccountStatusChecker>,
+    ) -> BasicAuthenticationProvider {
+        self.account_status_checker = account_status_checker;
+        self
+    }
 }
 
 #[async_trait]
 impl AuthenticationProvider for BasicAuthenticationProvider {
+    fn supports(&self, authentication: &dyn Authentication) -> bool {
+        authentication.is::<BasicAuthentication>()
+    }
+
     #[allow(clippy::borrowed_box)]
     async fn authenticate(
         &self,
@@ -40,10 +57,13 @@ impl AuthenticationProvider for BasicAuthenticationProvider {
                 .unwrap();
             match self
                 .user_details_service
-                .find_user(&basic_auth.username, &basic_auth.password)
+                .find_user(&basic_auth.username, basic_auth.password.expose_secret())
                 .await
             {
-                Some(user) => Ok(user),
+                Some(user) => {
+                    self.account_status_checker.check(user.as_ref())?;
+                    Ok(user)
+                }
                 None => Err(AuthenticationError::UsernameNotFound),
             }
         } else {
@@ -0,0 +1,41 @@
+//! A `UserDetails` implementation for the users of the built-in basic authentication user details services.
+
+use std::future::Future;
+use std::pin::Pin;
+
+use actix_web::dev::{Payload, PayloadStream};
+use actix_web::{Error, FromRequest, HttpRequest};
+use serde::Serialize;
+
+use crate::authentication::error::error_type::AuthenticationError;
+use crate::authorization::authority::Authorities;
+use crate::user_details::UserDetails;
+
+/// A user authenticated by the `InMemoryUserDetailsService` or the `HtpasswdUserDetailsService`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct BasicUser {
+    /// The username
+    pub username: String,
+    /// The authorities (e.g. roles) granted to the user
+    pub authorities: Authorities,
+}
+
+impl UserDetails for BasicUser {}
+
+impl FromRequest for BasicUser {
+    type Error = Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self, Error>>>>;
+    type Config = ();
+
+    fn from_request(req: &HttpRequest, _payload: &mut Payload<PayloadStream>) -> Self::Future {
+        let req = req.clone();
+
+        Box::pin(async move {
+            req.extensions()
+                .get::<Box<dyn UserDetails>>()
+                .and_then(|user_details| user_details.downcast_ref::<BasicUser>())
+                .cloned()
+                .ok_or_else(|| AuthenticationError::UsernameNotFound.into())
+        })
+    }
+}
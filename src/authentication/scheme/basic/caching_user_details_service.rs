@@ -0,0 +1,111 @@
+//! A caching decorator for a `BasicUserDetailsService`.
+
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use sha2::{Digest, Sha256};
+
+use crate::authentication::scheme::basic::user_details_service::BasicUserDetailsService;
+use crate::user_details::cache::UserDetailsCache;
+use crate::user_details::UserDetails;
+
+/// The key of a cached user: the username and a salted SHA-256 hash of the username and password,
+/// so that the passwords are not kept in memory.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+struct CacheKey {
+    username: String,
+    credentials_hash: [u8; 32],
+}
+
+/// A `BasicUserDetailsService` caching the users loaded by the wrapped user details service by the username and
+/// a salted hash of the credentials. A user is therefore only taken from the cache for the same password.
+///
+/// Found users are cached for 5 minutes and at most 10.000 entries are kept by default. Failed lookups are only
+/// cached if a negative time to live is set with `with_negative_time_to_live`.
+/// The cache is shared between all clones of the service, so that users can be evicted with `evict`
+/// (e.g. after a password change).
+#[derive(Clone)]
+pub struct CachingBasicUserDetailsService {
+    user_details_service: Box<dyn BasicUserDetailsService>,
+    cache: UserDetailsCache<CacheKey>,
+    salt: [u8; 16],
+}
+
+impl CachingBasicUserDetailsService {
+    /// Constructs a new instance for the boxed `BasicUserDetailsService` to load uncached users with.
+    pub fn new(
+        user_details_service: Box<dyn BasicUserDetailsService>,
+    ) -> CachingBasicUserDetailsService {
+        let mut salt = [0; 16];
+        salt[..8].copy_from_slice(&RandomState::new().build_hasher().finish().to_le_bytes());
+        salt[8..].copy_from_slice(&RandomState::new().build_hasher().finish().to_le_bytes());
+        CachingBasicUserDetailsService {
+            user_details_service,
+            cache: UserDetailsCache::new(),
+            salt,
+        }
+    }
+
+    /// Sets the time to live of found users. Defaults to 5 minutes.
+    pub fn with_time_to_live(mut self, time_to_live: Duration) -> CachingBasicUserDetailsService {
+        self.cache.set_time_to_live(time_to_live);
+        self
+    }
+
+    /// Enables caching of failed lookups with the given time to live.
+    pub fn with_negative_time_to_live(
+        mut self,
+        negative_time_to_live: Duration,
+    ) -> CachingBasicUserDetailsService {
+        self.cache
+            .set_negative_time_to_live(Some(negative_time_to_live));
+        self
+    }
+
+    /// Sets the maximum number of cached entries. Defaults to 10.000.
+    pub fn with_max_size(mut self, max_size: usize) -> CachingBasicUserDetailsService {
+        self.cache.set_max_size(max_size);
+        self
+    }
+
+    /// Removes the cached entries of the username.
+    pub fn evict(&self, username: &str) {
+        self.cache.evict_matching(|key| key.username == username);
+    }
+
+    /// Removes all cached entries.
+    pub fn evict_all(&self) {
+        self.cache.evict_all();
+    }
+
+    fn credentials_hash(&self, username: &str, password: &str) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        hasher.update(self.salt);
+        hasher.update((username.len() as u64).to_le_bytes());
+        hasher.update(username.as_bytes());
+        hasher.update(password.as_bytes());
+        hasher.finalize().into()
+    }
+}
+
+#[async_trait]
+impl BasicUserDetailsService for CachingBasicUserDetailsService {
+    async fn find_user(&self, username: &str, password: &str) -> Option<Box<dyn UserDetails>> {
+        let key = CacheKey {
+            username: username.to_string(),
+            credentials_hash: self.credentials_hash(username, password),
+        };
+
+        if let Some(user_details) = self.cache.get(&key) {
+            return user_details;
+        }
+        let user_details = self
+            .user_details_service
+            .find_user(username, password)
+            .await;
+        self.cache.insert(key, user_details.clone());
+        user_details
+    }
+}
@@ -2,24 +2,107 @@
 
 use actix_web::http::{header, HeaderMap, HeaderValue};
 use async_trait::async_trait;
+use unicode_normalization::char::decompose_compatible;
+use unicode_normalization::UnicodeNormalization;
+use zeroize::Zeroizing;
 
 use crate::authentication::error::error_type::AuthenticationError;
 use crate::authentication::scheme::authentication::Authentication;
+use crate::authentication::scheme::basic::secret::Secret;
 use crate::authentication::scheme::basic::BasicAuthentication;
 use crate::authentication::scheme::header_extractor::{
     extract_auth_header, AuthorizationHeaderExtractor,
 };
 
+/// The normalisation applied to the username of basic authentication credentials,
+/// so that the same username entered on different keyboards or systems maps to the same account.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UsernameNormalization {
+    /// The username is kept as it is.
+    None,
+    /// The username is normalised to the Unicode Normalization Form C (NFC).
+    Nfc,
+    /// Fullwidth and halfwidth characters are mapped to their decomposition, the username is converted to lowercase
+    /// and normalised to NFC. Empty usernames and usernames containing whitespace or control characters are rejected.
+    ///
+    /// The mapping follows the mapping rules of the PRECIS `UsernameCaseMapped` profile (RFC 8265),
+    /// but does not enforce the `IdentifierClass` rules and is therefore not a conforming implementation of the profile.
+    Lowercase,
+}
+
+impl UsernameNormalization {
+    fn apply(&self, username: String) -> Result<String, AuthenticationError> {
+        match self {
+            UsernameNormalization::None => Ok(username),
+            UsernameNormalization::Nfc => Ok(username.nfc().collect()),
+            UsernameNormalization::Lowercase => {
+                let username: String = username
+                    .chars()
+                    .flat_map(map_width)
+                    .collect::<String>()
+                    .to_lowercase()
+                    .nfc()
+                    .collect();
+                if username.is_empty()
+                    || username
+                        .chars()
+                        .any(|c| c.is_whitespace() || c.is_control())
+                {
+                    return Err(AuthenticationError::InvalidAuthorizationHeader);
+                }
+                Ok(username)
+            }
+        }
+    }
+}
+
+/// Maps fullwidth and halfwidth characters to their compatibility decomposition (RFC 8265, section 3.3.1).
+fn map_width(c: char) -> Vec<char> {
+    if ('\u{FF00}'..='\u{FFEF}').contains(&c) {
+        let mut decomposed = Vec::new();
+        decompose_compatible(c, |d| decomposed.push(d));
+        decomposed
+    } else {
+        vec![c]
+    }
+}
+
 /// The definition of a `BasicAuthenticationExtractor`. The authentication extractor
 /// extracts the authentication information from the authorization header and decodes
 /// the user credentials to be used in the user authentication.
+///
+/// The credentials are parsed according to RFC 7617: the scheme is matched case-insensitively,
+/// surrounding whitespace is ignored and the credentials are decoded as UTF-8.
+/// Credentials of legacy clients encoded with ISO-8859-1 can be accepted with `with_iso_8859_1_fallback`
+/// and the username can be normalised with `with_username_normalization`. The password is never normalised.
 #[derive(Clone)]
-pub struct BasicAuthenticationExtractor {}
+pub struct BasicAuthenticationExtractor {
+    iso_8859_1_fallback: bool,
+    username_normalization: UsernameNormalization,
+}
 
 impl BasicAuthenticationExtractor {
     /// Constructs a new instance of `BasicAuthenticationExtractor`.
     pub fn new() -> BasicAuthenticationExtractor {
-        BasicAuthenticationExtractor {}
+        BasicAuthenticationExtractor {
+            iso_8859_1_fallback: false,
+            username_normalization: UsernameNormalization::None,
+        }
+    }
+
+    /// Decodes credentials that are not valid UTF-8 as ISO-8859-1 instead of rejecting them.
+    pub fn with_iso_8859_1_fallback(mut self) -> BasicAuthenticationExtractor {
+        self.iso_8859_1_fallback = true;
+        self
+    }
+
+    /// Sets the normalisation of the username. Defaults to `UsernameNormalization::None`.
+    pub fn with_username_normalization(
+        mut self,
+        username_normalization: UsernameNormalization,
+    ) -> BasicAuthenticationExtractor {
+        self.username_normalization = username_normalization;
+        self
     }
 
     fn extract_basic(
@@ -29,26 +112,31 @@ impl BasicAuthenticationExtractor {
         let token = extract_auth_header(header, "Basic", 7)?;
 
         // Decode the token
-        if let Ok(decoded) = base64::decode(token) {
-            let decoded_str = std::str::from_utf8(&decoded);
-            if let Ok(credentials_str) = decoded_str {
-                let mut credentials = credentials_str.splitn(2, ':');
-
-                let username = credentials
-                    .next()
-                    .ok_or(AuthenticationError::InvalidAuthorizationHeader)?
-                    .to_string();
-
-                let password = credentials
-                    .next()
-                    .ok_or(AuthenticationError::InvalidAuthorizationHeader)?
-                    .to_string();
-
-                return Ok(BasicAuthentication { username, password });
-            }
-        }
+        let decoded = Zeroizing::new(
+            base64::decode(token.trim_end())
+                .map_err(|_| AuthenticationError::InvalidAuthorizationHeader)?,
+        );
+        let credentials_str = Zeroizing::new(match std::str::from_utf8(&decoded) {
+            Ok(credentials_str) => credentials_str.to_string(),
+            Err(_) if self.iso_8859_1_fallback => decoded.iter().map(|&b| b as char).collect(),
+            Err(_) => return Err(AuthenticationError::InvalidAuthorizationHeader),
+        });
+        let mut credentials = credentials_str.splitn(2, ':');
+
+        let username = credentials
+            .next()
+            .ok_or(AuthenticationError::InvalidAuthorizationHeader)?
+            .to_string();
+
+        let password = credentials
+            .next()
+            .ok_or(AuthenticationError::InvalidAuthorizationHeader)?
+            .to_string();
 
-        Err(AuthenticationError::InvalidAuthorizationHeader)
+        Ok(BasicAuthentication {
+            username: self.username_normalization.apply(username)?,
+            password: Secret::new(password),
+        })
     }
 }
 
@@ -0,0 +1,153 @@
+//! A `BasicUserDetailsService` and `BasicUserStore` implementation for Apache htpasswd files.
+
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock, Weak};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use async_trait::async_trait;
+
+use crate::authentication::error::error_type::AuthenticationError;
+use crate::authentication::scheme::basic::basic_user::BasicUser;
+use crate::authentication::scheme::basic::password_encoder::htpasswd_encoder::HtpasswdPasswordEncoder;
+use crate::authentication::scheme::basic::password_encoder::DummyPasswordHash;
+use crate::authentication::scheme::basic::secret::Secret;
+use crate::authentication::scheme::basic::user_details_service::BasicUserDetailsService;
+use crate::authentication::scheme::basic::user_store::{BasicUserStore, UserCredentials};
+use crate::authorization::authority::Authorities;
+use crate::user_details::UserDetails;
+
+/// A user details service reading the users from an Apache htpasswd file (`username:hash` per line).
+/// The passwords are verified with the `HtpasswdPasswordEncoder` (bcrypt, SHA-1 and Apache MD5).
+/// htpasswd files do not contain roles, therefore the authenticated `BasicUser`s have no authorities.
+///
+/// The file is read once on construction. With `with_file_watch` the file is checked for modifications
+/// periodically and reloaded on change. If a reload fails the previously loaded users are kept.
+#[derive(Clone)]
+pub struct HtpasswdUserDetailsService {
+    path: PathBuf,
+    users: Arc<RwLock<HashMap<String, String>>>,
+    password_encoder: HtpasswdPasswordEncoder,
+    dummy_password_hash: DummyPasswordHash,
+}
+
+impl HtpasswdUserDetailsService {
+    /// Constructs an instance for the htpasswd file at the given path.
+    /// Returns an error if the file cannot be read.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> io::Result<HtpasswdUserDetailsService> {
+        let path = path.as_ref().to_path_buf();
+        let users = read_htpasswd_file(&path)?;
+        Ok(HtpasswdUserDetailsService {
+            path,
+            users: Arc::new(RwLock::new(users)),
+            password_encoder: HtpasswdPasswordEncoder::new(),
+            dummy_password_hash: DummyPasswordHash::new(),
+        })
+    }
+
+    /// Starts a background thread checking the modification time of the file in the given interval and
+    /// reloading the users on change. The thread stops once all clones of the service have been dropped.
+    pub fn with_file_watch(self, interval: Duration) -> HtpasswdUserDetailsService {
+        let path = self.path.clone();
+        let users = Arc::downgrade(&self.users);
+        thread::spawn(move || watch_file(path, users, interval));
+        self
+    }
+
+    /// Reloads the users from the file. The previously loaded users are kept if the file cannot be read.
+    pub fn reload(&self) -> io::Result<()> {
+        reload_users(&self.path, &self.users)
+    }
+
+    fn find_password_hash(&self, username: &str) -> Option<String> {
+        self.users
+            .read()
+            .expect("Failed to acquire read lock")
+            .get(username)
+            .cloned()
+    }
+}
+
+#[async_trait]
+impl BasicUserDetailsService for HtpasswdUserDetailsService {
+    async fn find_user(&self, username: &str, password: &str) -> Option<Box<dyn UserDetails>> {
+        let password_hash = self.find_password_hash(username);
+
+        let matches = self
+            .dummy_password_hash
+            .verify_blocking(
+                Box::new(self.password_encoder.clone()),
+                Secret::new(password.to_string()),
+                password_hash,
+                |_, _, _| (),
+            )
+            .await;
+
+        matches.map(|_| {
+            Box::new(BasicUser {
+                username: username.to_string(),
+                authorities: Authorities::default(),
+            }) as Box<dyn UserDetails>
+        })
+    }
+}
+
+#[async_trait]
+impl BasicUserStore for HtpasswdUserDetailsService {
+    async fn find_user_by_username(
+        &self,
+        username: &str,
+    ) -> Result<Option<UserCredentials>, AuthenticationError> {
+        Ok(self
+            .find_password_hash(username)
+            .map(|password_hash| UserCredentials {
+                user_details: Box::new(BasicUser {
+                    username: username.to_string(),
+                    authorities: Authorities::default(),
+                }),
+                password_hash,
+            }))
+    }
+}
+
+fn watch_file(path: PathBuf, users: Weak<RwLock<HashMap<String, String>>>, interval: Duration) {
+    let mut last_modified = modification_time(&path);
+    loop {
+        thread::sleep(interval);
+        let users = match users.upgrade() {
+            Some(users) => users,
+            None => return,
+        };
+        let modified = modification_time(&path);
+        if modified != last_modified && reload_users(&path, &users).is_ok() {
+            last_modified = modified;
+        }
+    }
+}
+
+fn modification_time(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+fn reload_users(path: &Path, users: &RwLock<HashMap<String, String>>) -> io::Result<()> {
+    let loaded_users = read_htpasswd_file(path)?;
+    *users.write().expect("Failed to acquire write lock") = loaded_users;
+    Ok(())
+}
+
+/// Reads the `username:hash` lines of a htpasswd file. Empty lines and comments (`#`) are skipped.
+fn read_htpasswd_file(path: &Path) -> io::Result<HashMap<String, String>> {
+    let content = fs::read_to_string(path)?;
+    Ok(content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| line.split_once(':'))
+        .map(|(username, hash)| (username.to_string(), hash.to_string()))
+        .collect())
+}
@@ -0,0 +1,101 @@
+//! A `BasicUserDetailsService` and `BasicUserStore` implementation for a static set of users kept in memory.
+
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+
+use crate::authentication::error::error_type::AuthenticationError;
+use crate::authentication::scheme::basic::basic_user::BasicUser;
+use crate::authentication::scheme::basic::password_encoder::{DummyPasswordHash, PasswordEncoder};
+use crate::authentication::scheme::basic::secret::Secret;
+use crate::authentication::scheme::basic::user_details_service::BasicUserDetailsService;
+use crate::authentication::scheme::basic::user_store::{BasicUserStore, UserCredentials};
+use crate::authorization::authority::Authorities;
+use crate::user_details::UserDetails;
+
+/// A user details service for a handful of static users (e.g. operator accounts).
+/// The passwords are stored as hashes and verified with the configured `PasswordEncoder`.
+/// The service can either be used with the `BasicAuthenticationProvider` or, as `BasicUserStore`,
+/// with the `PasswordAuthenticationProvider`.
+#[derive(Clone)]
+pub struct InMemoryUserDetailsService {
+    users: HashMap<String, UserEntry>,
+    password_encoder: Box<dyn PasswordEncoder>,
+    dummy_password_hash: DummyPasswordHash,
+}
+
+#[derive(Clone)]
+struct UserEntry {
+    password_hash: String,
+    authorities: Authorities,
+}
+
+impl InMemoryUserDetailsService {
+    /// Constructs an instance without users for a boxed `PasswordEncoder` to verify the passwords with.
+    pub fn new(password_encoder: Box<dyn PasswordEncoder>) -> InMemoryUserDetailsService {
+        InMemoryUserDetailsService {
+            users: HashMap::new(),
+            password_encoder,
+            dummy_password_hash: DummyPasswordHash::new(),
+        }
+    }
+
+    /// Adds a user with the encoded password (as produced by the `PasswordEncoder`) and the granted authorities.
+    /// An existing user with the same username is replaced.
+    pub fn with_user(
+        mut self,
+        username: &str,
+        password_hash: &str,
+        authorities: Vec<&str>,
+    ) -> InMemoryUserDetailsService {
+        self.users.insert(
+            username.to_string(),
+            UserEntry {
+                password_hash: password_hash.to_string(),
+                authorities: authorities.into_iter().map(String::from).collect(),
+            },
+        );
+        self
+    }
+}
+
+#[async_trait]
+impl BasicUserDetailsService for InMemoryUserDetailsService {
+    async fn find_user(&self, username: &str, password: &str) -> Option<Box<dyn UserDetails>> {
+        let user = self.users.get(username);
+
+        let matches = self
+            .dummy_password_hash
+            .verify_blocking(
+                self.password_encoder.clone(),
+                Secret::new(password.to_string()),
+                user.map(|user| user.password_hash.clone()),
+                |_, _, _| (),
+            )
+            .await;
+
+        let user = user?;
+        matches.map(|_| {
+            Box::new(BasicUser {
+                username: username.to_string(),
+                authorities: user.authorities.clone(),
+            }) as Box<dyn UserDetails>
+        })
+    }
+}
+
+#[async_trait]
+impl BasicUserStore for InMemoryUserDetailsService {
+    async fn find_user_by_username(
+        &self,
+        username: &str,
+    ) -> Result<Option<UserCredentials>, AuthenticationError> {
+        Ok(self.users.get(username).map(|user| UserCredentials {
+            user_details: Box::new(BasicUser {
+                username: username.to_string(),
+                authorities: user.authorities.clone(),
+            }),
+            password_hash: user.password_hash.clone(),
+        }))
+    }
+}
@@ -0,0 +1,302 @@
+//! An implementation of an `AuthenticationProvider` for basic authentication against an LDAP directory.
+
+use std::collections::HashSet;
+
+use actix_web::error::BlockingError;
+use actix_web::web;
+use async_trait::async_trait;
+
+use crate::authentication::error::error_type::{AuthenticationError, LdapError};
+use crate::authentication::scheme::authentication::Authentication;
+use crate::authentication::scheme::authentication_provider::AuthenticationProvider;
+use crate::authentication::scheme::basic::ldap::ldap_user::LdapUser;
+use crate::authentication::scheme::basic::ldap::{
+    escape_dn_value, escape_filter_value, LdapConnection, LdapConnector, LdapSearchScope,
+};
+use crate::authentication::scheme::basic::BasicAuthentication;
+use crate::authorization::authority::{Authorities, CaseNormalization};
+use crate::user_details::UserDetails;
+
+/// The attribute to request if no attributes are required (RFC 4511).
+const NO_ATTRIBUTES: &str = "1.1";
+
+#[derive(Clone)]
+enum BindStrategy {
+    DirectBind {
+        user_dn_pattern: String,
+    },
+    SearchAndBind {
+        search_base: String,
+        search_filter: String,
+    },
+}
+
+#[derive(Clone)]
+struct GroupSearch {
+    search_base: String,
+    search_filter: String,
+    name_attribute: String,
+}
+
+/// The definition of a `LdapAuthenticationProvider`. The provider verifies the credentials of a `BasicAuthentication`
+/// with a bind of the user's DN and maps the groups of the user to `Authorities`. The user's DN is either built from a
+/// pattern (direct bind) or searched in the directory (search and bind).
+///
+/// Patterns and filters can contain the placeholders `{username}` (the escaped username) and, for group searches,
+/// `{dn}` (the escaped DN of the user). Group searches are executed with the bind of the user.
+/// Empty passwords are always rejected, as LDAP servers treat them as anonymous bind.
+/// Failed connections and directory operations are reported as `AuthenticationError::ServiceUnavailable`.
+#[derive(Clone)]
+pub struct LdapAuthenticationProvider {
+    connector: Box<dyn LdapConnector>,
+    bind_strategy: BindStrategy,
+    service_account: Option<(String, String)>,
+    group_search: Option<GroupSearch>,
+    member_of_attribute: Option<String>,
+    authority_prefix: String,
+    case_normalization: CaseNormalization,
+}
+
+impl LdapAuthenticationProvider {
+    /// Constructs an instance binding with the DN built from the pattern (e.g. `uid={username},ou=people,dc=example,dc=com`).
+    pub fn direct_bind(
+        connector: Box<dyn LdapConnector>,
+        user_dn_pattern: &str,
+    ) -> LdapAuthenticationProvider {
+        LdapAuthenticationProvider::new(
+            connector,
+            BindStrategy::DirectBind {
+                user_dn_pattern: user_dn_pattern.to_string(),
+            },
+        )
+    }
+
+    /// Constructs an instance searching the DN of the user in the subtree of the search base with the filter
+    /// (e.g. `(uid={username})`) before the bind. The search must return exactly one entry.
+    /// The search is executed anonymously unless a service account is configured.
+    pub fn search_and_bind(
+        connector: Box<dyn LdapConnector>,
+        search_base: &str,
+        search_filter: &str,
+    ) -> LdapAuthenticationProvider {
+        LdapAuthenticationProvider::new(
+            connector,
+            BindStrategy::SearchAndBind {
+                search_base: search_base.to_string(),
+                search_filter: search_filter.to_string(),
+            },
+        )
+    }
+
+    fn new(
+        connector: Box<dyn LdapConnector>,
+        bind_strategy: BindStrategy,
+    ) -> LdapAuthenticationProvider {
+        LdapAuthenticationProvider {
+            connector,
+            bind_strategy,
+            service_account: None,
+            group_search: None,
+            member_of_attribute: None,
+            authority_prefix: "".to_string(),
+            case_normalization: CaseNormalization::None,
+        }
+    }
+
+    /// Sets the DN and password of the service account to bind with before searching the user.
+    pub fn with_service_account(mut self, dn: &str, password: &str) -> LdapAuthenticationProvider {
+        self.service_account = Some((dn.to_string(), password.to_string()));
+        self
+    }
+
+    /// Maps the groups found in the subtree of the search base with the filter (e.g. `(member={dn})`) to authorities.
+    /// The authority is the value of the name attribute of the group (e.g. `cn`).
+    pub fn with_group_search(
+        mut self,
+        search_base: &str,
+        search_filter: &str,
+        name_attribute: &str,
+    ) -> LdapAuthenticationProvider {
+        self.group_search = Some(GroupSearch {
+            search_base: search_base.to_string(),
+            search_filter: search_filter.to_string(),
+            name_attribute: name_attribute.to_string(),
+        });
+        self
+    }
+
+    /// Maps the group DNs of the attribute of the user entry (e.g. `memberOf`) to authorities.
+    /// The authority is the value of the first RDN of the group DN (e.g. `admins` for `cn=admins,ou=groups,dc=example,dc=com`).
+    pub fn with_member_of_attribute(mut self, attribute: &str) -> LdapAuthenticationProvider {
+        self.member_of_attribute = Some(attribute.to_string());
+        self
+    }
+
+    /// Sets a prefix that is prepended to all authorities (e.g. `ROLE_`).
+    pub fn with_authority_prefix(mut self, prefix: &str) -> LdapAuthenticationProvider {
+        self.authority_prefix = prefix.to_string();
+        self
+    }
+
+    /// Sets the case normalisation of the group names (the prefix is not normalised).
+    pub fn with_case_normalization(
+        mut self,
+        case_normalization: CaseNormalization,
+    ) -> LdapAuthenticationProvider {
+        self.case_normalization = case_normalization;
+        self
+    }
+
+    fn authenticate_user(&self, username: &str, password: &str) -> Result<LdapUser, LdapError> {
+        if password.is_empty() {
+            return Err(LdapError::InvalidCredentials);
+        }
+
+        let mut connection = self.connector.connect()?;
+        let dn = self.find_user_dn(connection.as_mut(), username)?;
+        connection.simple_bind(&dn, password)?;
+        let authorities = self.find_authorities(connection.as_mut(), username, &dn)?;
+
+        Ok(LdapUser {
+            username: username.to_string(),
+            dn,
+            authorities,
+        })
+    }
+
+    fn find_user_dn(
+        &self,
+        connection: &mut dyn LdapConnection,
+        username: &str,
+    ) -> Result<String, LdapError> {
+        match &self.bind_strategy {
+            BindStrategy::DirectBind { user_dn_pattern } => {
+                Ok(user_dn_pattern.replace("{username}", &escape_dn_value(username)))
+            }
+            BindStrategy::SearchAndBind {
+                search_base,
+                search_filter,
+            } => {
+                if let Some((dn, password)) = &self.service_account {
+                    // Invalid service account credentials are a configuration error
+                    connection
+                        .simple_bind(dn, password)
+                        .map_err(|_| LdapError::OperationFailed)?;
+                }
+                let filter = search_filter.replace("{username}", &escape_filter_value(username));
+                let mut entries = connection.search(
+                    search_base,
+                    LdapSearchScope::Subtree,
+                    &filter,
+                    &[NO_ATTRIBUTES],
+                )?;
+                match entries.len() {
+                    1 => Ok(entries.remove(0).dn),
+                    _ => Err(LdapError::InvalidCredentials),
+                }
+            }
+        }
+    }
+
+    fn find_authorities(
+        &self,
+        connection: &mut dyn LdapConnection,
+        username: &str,
+        dn: &str,
+    ) -> Result<Authorities, LdapError> {
+        let mut groups = HashSet::new();
+
+        if let Some(attribute) = &self.member_of_attribute {
+            let entries =
+                connection.search(dn, LdapSearchScope::Base, "(objectClass=*)", &[attribute])?;
+            groups.extend(
+                entries
+                    .iter()
+                    .filter_map(|entry| entry.attributes.get(attribute))
+                    .flatten()
+                    .filter_map(|group_dn| first_rdn_value(group_dn)),
+            );
+        }
+
+        if let Some(group_search) = &self.group_search {
+            let filter = group_search
+                .search_filter
+                .replace("{dn}", &escape_filter_value(dn))
+                .replace("{username}", &escape_filter_value(username));
+            let entries = connection.search(
+                &group_search.search_base,
+                LdapSearchScope::Subtree,
+                &filter,
+                &[&group_search.name_attribute],
+            )?;
+            groups.extend(
+                entries
+                    .iter()
+                    .filter_map(|entry| entry.attributes.get(&group_search.name_attribute))
+                    .flatten()
+                    .cloned(),
+            );
+        }
+
+        Ok(groups
+            .iter()
+            .map(|group| {
+                format!(
+                    "{}{}",
+                    self.authority_prefix,
+                    self.case_normalization.apply(group)
+                )
+            })
+            .collect())
+    }
+}
+
+#[async_trait]
+impl AuthenticationProvider for LdapAuthenticationProvider {
+    fn supports(&self, authentication: &dyn Authentication) -> bool {
+        authentication.is::<BasicAuthentication>()
+    }
+
+    #[allow(clippy::borrowed_box)]
+    async fn authenticate(
+        &self,
+        authentication: &Box<dyn Authentication>,
+    ) -> Result<Box<dyn UserDetails>, AuthenticationError> {
+        let basic_auth = authentication
+            .downcast_ref::<BasicAuthentication>()
+            .ok_or(AuthenticationError::InvalidAuthentication)?;
+
+        // The directory operations are blocking and are therefore executed on a thread pool
+        // to not block the event loop.
+        let provider = self.clone();
+        let username = basic_auth.username.clone();
+        let password = basic_auth.password.clone();
+        let result =
+            web::block(move || provider.authenticate_user(&username, password.expose_secret()))
+                .await;
+
+        match result {
+            Ok(user) => Ok(Box::new(user)),
+            Err(BlockingError::Error(LdapError::InvalidCredentials)) => {
+                Err(AuthenticationError::UsernameNotFound)
+            }
+            // Connection and operation failures are outages of the directory, not failed logins
+            Err(_) => Err(AuthenticationError::ServiceUnavailable),
+        }
+    }
+}
+
+/// Returns the (unescaped) value of the first RDN of a DN (e.g. `admins` for `cn=admins,ou=groups`).
+fn first_rdn_value(dn: &str) -> Option<String> {
+    let (_, value) = dn.split_once('=')?;
+    let mut unescaped = String::new();
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => unescaped.extend(chars.next()),
+            ',' | '+' => break,
+            _ => unescaped.push(c),
+        }
+    }
+    Some(unescaped.trim().to_string())
+}
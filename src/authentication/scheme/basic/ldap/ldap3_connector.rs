@@ -0,0 +1,107 @@
+//! An `LdapConnector` implementation based on the `ldap3` crate.
+
+use std::time::Duration;
+
+use ldap3::{LdapConn, LdapConnSettings, Scope, SearchEntry};
+
+use crate::authentication::error::error_type::LdapError;
+use crate::authentication::scheme::basic::ldap::{
+    LdapConnection, LdapConnector, LdapEntry, LdapSearchScope,
+};
+
+/// The LDAP result code of a bind with invalid credentials.
+const INVALID_CREDENTIALS: u32 = 49;
+
+/// An `LdapConnector` connecting to the server of an `ldap://`, `ldaps://` or `ldapi://` URL.
+#[derive(Clone)]
+pub struct Ldap3Connector {
+    url: String,
+    starttls: bool,
+    timeout: Option<Duration>,
+}
+
+impl Ldap3Connector {
+    /// Constructs an instance for the URL of the LDAP server (e.g. `ldaps://ldap.example.com`).
+    pub fn new(url: &str) -> Ldap3Connector {
+        Ldap3Connector {
+            url: url.to_string(),
+            starttls: false,
+            timeout: None,
+        }
+    }
+
+    /// Upgrades `ldap://` connections to TLS with the StartTLS extended operation.
+    pub fn with_starttls(mut self, starttls: bool) -> Ldap3Connector {
+        self.starttls = starttls;
+        self
+    }
+
+    /// Sets the timeout of establishing the connection and of each operation.
+    pub fn with_timeout(mut self, timeout: Duration) -> Ldap3Connector {
+        self.timeout = Some(timeout);
+        self
+    }
+}
+
+impl LdapConnector for Ldap3Connector {
+    fn connect(&self) -> Result<Box<dyn LdapConnection>, LdapError> {
+        let mut settings = LdapConnSettings::new().set_starttls(self.starttls);
+        if let Some(timeout) = self.timeout {
+            settings = settings.set_conn_timeout(timeout);
+        }
+        let mut connection = LdapConn::with_settings(settings, &self.url)
+            .map_err(|_| LdapError::ConnectionFailed)?;
+        if let Some(timeout) = self.timeout {
+            connection.with_timeout(timeout);
+        }
+        Ok(Box::new(Ldap3Connection(connection)))
+    }
+}
+
+struct Ldap3Connection(LdapConn);
+
+impl LdapConnection for Ldap3Connection {
+    fn simple_bind(&mut self, dn: &str, password: &str) -> Result<(), LdapError> {
+        let result = self
+            .0
+            .simple_bind(dn, password)
+            .map_err(|_| LdapError::OperationFailed)?;
+        match result.rc {
+            0 => Ok(()),
+            INVALID_CREDENTIALS => Err(LdapError::InvalidCredentials),
+            _ => Err(LdapError::OperationFailed),
+        }
+    }
+
+    fn search(
+        &mut self,
+        base: &str,
+        scope: LdapSearchScope,
+        filter: &str,
+        attributes: &[&str],
+    ) -> Result<Vec<LdapEntry>, LdapError> {
+        let scope = match scope {
+            LdapSearchScope::Base => Scope::Base,
+            LdapSearchScope::Subtree => Scope::Subtree,
+        };
+        let (entries, _) = self
+            .0
+            .search(base, scope, filter, attributes.to_vec())
+            .and_then(|result| result.success())
+            .map_err(|_| LdapError::OperationFailed)?;
+        Ok(entries
+            .into_iter()
+            .map(SearchEntry::construct)
+            .map(|entry| LdapEntry {
+                dn: entry.dn,
+                attributes: entry.attrs,
+            })
+            .collect())
+    }
+}
+
+impl Drop for Ldap3Connection {
+    fn drop(&mut self) {
+        let _ = self.0.unbind();
+    }
+}
@@ -0,0 +1,43 @@
+//! A `UserDetails` implementation for users authenticated against an LDAP directory.
+
+use std::future::Future;
+use std::pin::Pin;
+
+use actix_web::dev::{Payload, PayloadStream};
+use actix_web::{Error, FromRequest, HttpRequest};
+use serde::Serialize;
+
+use crate::authentication::error::error_type::AuthenticationError;
+use crate::authorization::authority::Authorities;
+use crate::user_details::UserDetails;
+
+/// A user authenticated by the `LdapAuthenticationProvider`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct LdapUser {
+    /// The username
+    pub username: String,
+    /// The distinguished name of the user's directory entry
+    pub dn: String,
+    /// The authorities mapped from the groups of the user
+    pub authorities: Authorities,
+}
+
+impl UserDetails for LdapUser {}
+
+impl FromRequest for LdapUser {
+    type Error = Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self, Error>>>>;
+    type Config = ();
+
+    fn from_request(req: &HttpRequest, _payload: &mut Payload<PayloadStream>) -> Self::Future {
+        let req = req.clone();
+
+        Box::pin(async move {
+            req.extensions()
+                .get::<Box<dyn UserDetails>>()
+                .and_then(|user_details| user_details.downcast_ref::<LdapUser>())
+                .cloned()
+                .ok_or_else(|| AuthenticationError::UsernameNotFound.into())
+        })
+    }
+}
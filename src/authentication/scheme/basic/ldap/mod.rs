@@ -0,0 +1,107 @@
+//! The ldap module offers an `AuthenticationProvider` that verifies basic authentication credentials with a bind
+//! against an LDAP directory. The directory is accessed through the `LdapConnector` trait, e.g. with the implementation
+//! based on the `ldap3` crate. The module is available with the `ldap` feature.
+
+use std::collections::HashMap;
+
+use crate::authentication::error::error_type::LdapError;
+
+pub mod authentication_provider;
+pub mod ldap3_connector;
+pub mod ldap_user;
+
+/// The scope of an LDAP search.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LdapSearchScope {
+    /// Only the base entry itself.
+    Base,
+    /// The base entry and all entries below it.
+    Subtree,
+}
+
+/// An entry returned by an LDAP search.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LdapEntry {
+    /// The distinguished name of the entry
+    pub dn: String,
+    /// The (string-valued) attributes of the entry
+    pub attributes: HashMap<String, Vec<String>>,
+}
+
+/// Trait definition of a connection to an LDAP directory.
+/// The operations are blocking and are executed on a thread pool by the `LdapAuthenticationProvider`.
+pub trait LdapConnection {
+    /// Binds the connection with the given DN and password.
+    /// Returns `LdapError::InvalidCredentials` if the directory rejects the credentials.
+    fn simple_bind(&mut self, dn: &str, password: &str) -> Result<(), LdapError>;
+
+    /// Searches the directory for the entries matching the filter and returns the requested attributes.
+    fn search(
+        &mut self,
+        base: &str,
+        scope: LdapSearchScope,
+        filter: &str,
+        attributes: &[&str],
+    ) -> Result<Vec<LdapEntry>, LdapError>;
+}
+
+/// Trait definition of a factory for `LdapConnection`s. A new connection is opened for every authentication.
+pub trait LdapConnector: LdapConnectorClone {
+    /// Opens a new (unbound) connection to the directory.
+    fn connect(&self) -> Result<Box<dyn LdapConnection>, LdapError>;
+}
+
+/// An ldap connector must be cloneable, `send` and `sync`.
+/// Therefore it has to implement the `LdapConnectorClone` trait to be cloneable as a boxed object.
+pub trait LdapConnectorClone: Send + Sync {
+    fn clone_box(&self) -> Box<dyn LdapConnector>;
+}
+
+impl<U> LdapConnectorClone for U
+where
+    U: 'static + LdapConnector + Clone,
+{
+    fn clone_box(&self) -> Box<dyn LdapConnector> {
+        Box::new(self.clone())
+    }
+}
+
+impl Clone for Box<dyn LdapConnector> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
+/// Escapes a value to be used in a search filter (RFC 4515).
+pub fn escape_filter_value(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '*' | '(' | ')' | '\\' | '\0' => escaped.push_str(&format!("\\{:02x}", c as u8)),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Escapes a value to be used as attribute value of a distinguished name (RFC 4514).
+pub fn escape_dn_value(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    let last = value.chars().count().saturating_sub(1);
+    for (index, c) in value.chars().enumerate() {
+        match c {
+            ',' | '+' | '"' | '\\' | '<' | '>' | ';' | '=' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '#' | ' ' if index == 0 => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            ' ' if index == last => escaped.push_str("\\ "),
+            '\0' => escaped.push_str("\\00"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -2,15 +2,31 @@
 //! user detail service.
 
 use crate::authentication::scheme::authentication::Authentication;
+use crate::authentication::scheme::basic::secret::Secret;
 
 pub mod authentication_provider;
+pub mod basic_user;
+pub mod caching_user_details_service;
 pub mod header_extractor;
+#[cfg(feature = "htpasswd")]
+pub mod htpasswd_user_details_service;
+pub mod in_memory_user_details_service;
+#[cfg(feature = "ldap")]
+pub mod ldap;
+pub mod password_authentication_provider;
+pub mod password_encoder;
+pub mod secret;
+#[cfg(feature = "sqlx-user-store")]
+pub mod sqlx_user_store;
 pub mod user_details_service;
+pub mod user_store;
 
 /// A basic authentication struct representing the username and password extracted from the authorization header.
+/// The password is kept as `Secret`, which is wiped from memory on drop and redacted in `Debug` output.
+#[derive(Debug)]
 pub struct BasicAuthentication {
     pub username: String,
-    pub password: String,
+    pub password: Secret,
 }
 
 impl Authentication for BasicAuthentication {}
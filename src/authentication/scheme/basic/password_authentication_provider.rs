@@ -0,0 +1,105 @@
+//! An implementation of an `AuthenticationProvider` for basic authentication with hashed passwords.
+
+use async_trait::async_trait;
+
+use crate::authentication::error::error_type::AuthenticationError;
+use crate::authentication::scheme::authentication::Authentication;
+use crate::authentication::scheme::authentication_provider::AuthenticationProvider;
+use crate::authentication::scheme::basic::password_encoder::{DummyPasswordHash, PasswordEncoder};
+use crate::authentication::scheme::basic::user_store::BasicUserStore;
+use crate::authentication::scheme::basic::BasicAuthentication;
+use crate::user_details::account_status::{AccountStatusChecker, DefaultAccountStatusChecker};
+use crate::user_details::UserDetails;
+
+/// The definition of a `PasswordAuthenticationProvider`. The provider loads the user by username
+/// from a `BasicUserStore` and verifies the password against the stored hash with a `PasswordEncoder`.
+/// Hashes that do not match the encoding policy of the `PasswordEncoder` are upgraded after a successful
+/// verification with `BasicUserStore::update_password_hash`.
+/// The account status of the user is verified with the `AccountStatusChecker` after the password verification,
+/// so that the status of an account is not revealed without valid credentials.
+#[derive(Clone)]
+pub struct PasswordAuthenticationProvider {
+    user_store: Box<dyn BasicUserStore>,
+    password_encoder: Box<dyn PasswordEncoder>,
+    account_status_checker: Box<dyn AccountStatusChecker>,
+    dummy_password_hash: DummyPasswordHash,
+}
+
+impl PasswordAuthenticationProvider {
+    /// Constructs an instance of a `PasswordAuthenticationProvider` for a boxed instance of a `BasicUserStore`
+    /// which does the actual data lookup and a boxed `PasswordEncoder` to verify the passwords.
+    pub fn new(
+        user_store: Box<dyn BasicUserStore>,
+        password_encoder: Box<dyn PasswordEncoder>,
+    ) -> PasswordAuthenticationProvider {
+        PasswordAuthenticationProvider {
+            user_store,
+            password_encoder,
+            account_status_checker: Box::new(DefaultAccountStatusChecker::new()),
+            dummy_password_hash: DummyPasswordHash::new(),
+        }
+    }
+
+    /// Replaces the `DefaultAccountStatusChecker`.
+    pub fn with_account_status_checker(
+        mut self,
+        account_status_checker: Box<dyn AccountStatusChecker>,
+    ) -> PasswordAuthenticationProvider {
+        self.account_status_checker = account_status_checker;
+        self
+    }
+}
+
+#[async_trait]
+impl AuthenticationProvider for PasswordAuthenticationProvider {
+    fn supports(&self, authentication: &dyn Authentication) -> bool {
+        authentication.is::<BasicAuthentication>()
+    }
+
+    #[allow(clippy::borrowed_box)]
+    async fn authenticate(
+        &self,
+        authentication: &Box<dyn Authentication>,
+    ) -> Result<Box<dyn UserDetails>, AuthenticationError> {
+        let basic_auth = authentication
+            .downcast_ref::<BasicAuthentication>()
+            .ok_or(AuthenticationError::InvalidAuthentication)?;
+        let credentials = self
+            .user_store
+            .find_user_by_username(&basic_auth.username)
+            .await?;
+        let verification = self
+            .dummy_password_hash
+            .verify_blocking(
+                self.password_encoder.clone(),
+                basic_auth.password.clone(),
+                credentials
+                    .as_ref()
+                    .map(|credentials| credentials.password_hash.clone()),
+                |password_encoder, raw_password, password_hash| {
+                    if password_encoder.upgrade_encoding(password_hash) {
+                        password_encoder.encode(raw_password).ok()
+                    } else {
+                        None
+                    }
+                },
+            )
+            .await;
+
+        match (credentials, verification) {
+            (Some(credentials), Some(upgraded_password_hash)) => {
+                self.account_status_checker
+                    .check_pre_authentication(credentials.user_details.as_ref())?;
+                if let Some(password_hash) = upgraded_password_hash {
+                    self.user_store
+                        .update_password_hash(&basic_auth.username, &password_hash)
+                        .await;
+                }
+                self.account_status_checker
+                    .check_post_authentication(credentials.user_details.as_ref())?;
+                Ok(credentials.user_details)
+            }
+            _ => Err(AuthenticationError::UsernameNotFound),
+        }
+    }
+}
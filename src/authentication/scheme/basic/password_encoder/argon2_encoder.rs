@@ -0,0 +1,81 @@
+//! A `PasswordEncoder` implementation for Argon2id.
+
+use std::convert::TryFrom;
+
+use argon2::password_hash::rand_core::OsRng;
+use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use argon2::{Algorithm, Argon2, Params, Version};
+
+use crate::authentication::error::error_type::PasswordEncoderError;
+use crate::authentication::scheme::basic::password_encoder::PasswordEncoder;
+
+/// A `PasswordEncoder` hashing passwords with Argon2id (PHC string format, e.g. `$argon2id$v=19$m=19456,t=2,p=1$...`).
+/// Hashes of the other Argon2 variants and with other parameters are verified as well.
+#[derive(Clone)]
+pub struct Argon2PasswordEncoder {
+    params: Params,
+}
+
+impl Argon2PasswordEncoder {
+    /// Constructs a new instance with the default parameters (19 MiB memory, 2 iterations, 1 degree of parallelism).
+    pub fn new() -> Argon2PasswordEncoder {
+        Argon2PasswordEncoder {
+            params: Params::default(),
+        }
+    }
+
+    /// Constructs a new instance with the given memory cost (in KiB), number of iterations and degree of parallelism.
+    pub fn with_params(
+        memory_cost: u32,
+        iterations: u32,
+        parallelism: u32,
+    ) -> Result<Argon2PasswordEncoder, PasswordEncoderError> {
+        let params = Params::new(memory_cost, iterations, parallelism, None)
+            .map_err(|_| PasswordEncoderError::InvalidParameters)?;
+        Ok(Argon2PasswordEncoder { params })
+    }
+}
+
+impl Default for Argon2PasswordEncoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PasswordEncoder for Argon2PasswordEncoder {
+    fn encode(&self, raw_password: &str) -> Result<String, PasswordEncoderError> {
+        let salt = SaltString::generate(&mut OsRng);
+        Argon2::new(Algorithm::Argon2id, Version::V0x13, self.params.clone())
+            .hash_password(raw_password.as_bytes(), &salt)
+            .map(|hash| hash.to_string())
+            .map_err(|_| PasswordEncoderError::EncodingFailed)
+    }
+
+    fn matches(&self, raw_password: &str, encoded_password: &str) -> bool {
+        match PasswordHash::new(encoded_password) {
+            Ok(hash) => Argon2::default()
+                .verify_password(raw_password.as_bytes(), &hash)
+                .is_ok(),
+            Err(_) => false,
+        }
+    }
+
+    /// Returns **true** if the hash is not an Argon2id hash of the current version with the configured parameters.
+    fn upgrade_encoding(&self, encoded_password: &str) -> bool {
+        let hash = match PasswordHash::new(encoded_password) {
+            Ok(hash) => hash,
+            Err(_) => return true,
+        };
+        let is_current_algorithm = hash.algorithm == Algorithm::Argon2id.ident()
+            && hash.version == Some(Version::V0x13.into());
+        match Params::try_from(&hash) {
+            Ok(params) => {
+                !is_current_algorithm
+                    || params.m_cost() != self.params.m_cost()
+                    || params.t_cost() != self.params.t_cost()
+                    || params.p_cost() != self.params.p_cost()
+            }
+            Err(_) => true,
+        }
+    }
+}
@@ -0,0 +1,55 @@
+//! A `PasswordEncoder` implementation for bcrypt.
+
+use crate::authentication::error::error_type::PasswordEncoderError;
+use crate::authentication::scheme::basic::password_encoder::PasswordEncoder;
+
+/// A `PasswordEncoder` hashing passwords with bcrypt (e.g. `$2b$12$...`).
+/// Note: bcrypt only considers the first 72 bytes of a password.
+#[derive(Clone)]
+pub struct BcryptPasswordEncoder {
+    cost: u32,
+}
+
+impl BcryptPasswordEncoder {
+    /// Constructs a new instance with the default cost of 12.
+    pub fn new() -> BcryptPasswordEncoder {
+        BcryptPasswordEncoder {
+            cost: bcrypt::DEFAULT_COST,
+        }
+    }
+
+    /// Constructs a new instance with the given cost (4 <= cost <= 31).
+    pub fn with_cost(cost: u32) -> Result<BcryptPasswordEncoder, PasswordEncoderError> {
+        if (4..=31).contains(&cost) {
+            Ok(BcryptPasswordEncoder { cost })
+        } else {
+            Err(PasswordEncoderError::InvalidParameters)
+        }
+    }
+}
+
+impl Default for BcryptPasswordEncoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PasswordEncoder for BcryptPasswordEncoder {
+    fn encode(&self, raw_password: &str) -> Result<String, PasswordEncoderError> {
+        bcrypt::hash(raw_password, self.cost).map_err(|_| PasswordEncoderError::EncodingFailed)
+    }
+
+    fn matches(&self, raw_password: &str, encoded_password: &str) -> bool {
+        bcrypt::verify(raw_password, encoded_password).unwrap_or(false)
+    }
+
+    /// Returns **true** if the cost of the hash differs from the configured cost.
+    fn upgrade_encoding(&self, encoded_password: &str) -> bool {
+        // The hash has the format $<version>$<cost>$<salt and hash>
+        encoded_password
+            .split('$')
+            .nth(2)
+            .and_then(|cost| cost.parse::<u32>().ok())
+            != Some(self.cost)
+    }
+}
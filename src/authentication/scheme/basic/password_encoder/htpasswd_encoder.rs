@@ -0,0 +1,150 @@
+//! A `PasswordEncoder` implementation for the password formats of Apache htpasswd files.
+
+use md5::{Digest, Md5};
+use sha1::Sha1;
+
+use crate::authentication::error::error_type::PasswordEncoderError;
+use crate::authentication::scheme::basic::password_encoder::PasswordEncoder;
+use crate::authentication::scheme::basic::secret::constant_time_eq;
+
+const APR1_PREFIX: &str = "$apr1$";
+const SHA_PREFIX: &str = "{SHA}";
+const CRYPT_ALPHABET: &[u8] = b"./0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+/// A `PasswordEncoder` verifying the password formats written by `htpasswd`:
+/// bcrypt (`$2y$`, `$2a$`, `$2b$`), SHA-1 (`{SHA}`) and Apache MD5 (`$apr1$`).
+/// New passwords are encoded with bcrypt (`htpasswd -B`). SHA-1 and Apache MD5 hashes are considered
+/// insecure and are therefore always upgraded. Crypt and plain text passwords are not supported and never match.
+#[derive(Clone)]
+pub struct HtpasswdPasswordEncoder {
+    cost: u32,
+}
+
+impl HtpasswdPasswordEncoder {
+    /// Constructs a new instance encoding passwords with bcrypt with the default cost of 12.
+    pub fn new() -> HtpasswdPasswordEncoder {
+        HtpasswdPasswordEncoder {
+            cost: bcrypt::DEFAULT_COST,
+        }
+    }
+}
+
+impl Default for HtpasswdPasswordEncoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PasswordEncoder for HtpasswdPasswordEncoder {
+    fn encode(&self, raw_password: &str) -> Result<String, PasswordEncoderError> {
+        bcrypt::hash_with_result(raw_password, self.cost)
+            .map(|hash| hash.format_for_version(bcrypt::Version::TwoY))
+            .map_err(|_| PasswordEncoderError::EncodingFailed)
+    }
+
+    fn matches(&self, raw_password: &str, encoded_password: &str) -> bool {
+        if let Some(hash) = encoded_password.strip_prefix(SHA_PREFIX) {
+            constant_time_eq(
+                base64::encode(Sha1::digest(raw_password.as_bytes())).as_bytes(),
+                hash.as_bytes(),
+            )
+        } else if let Some(salt_and_hash) = encoded_password.strip_prefix(APR1_PREFIX) {
+            match salt_and_hash.split_once('$') {
+                Some((salt, _)) => constant_time_eq(
+                    apr1(raw_password.as_bytes(), salt.as_bytes()).as_bytes(),
+                    encoded_password.as_bytes(),
+                ),
+                None => false,
+            }
+        } else if encoded_password.starts_with("$2") {
+            bcrypt::verify(raw_password, encoded_password).unwrap_or(false)
+        } else {
+            false
+        }
+    }
+
+    /// Returns **true** for all hashes other than bcrypt hashes with the configured cost.
+    fn upgrade_encoding(&self, encoded_password: &str) -> bool {
+        !encoded_password.starts_with("$2")
+            || encoded_password
+                .split('$')
+                .nth(2)
+                .and_then(|cost| cost.parse::<u32>().ok())
+                != Some(self.cost)
+    }
+}
+
+/// Computes the Apache MD5 (`$apr1$`) hash of a password, a variant of the MD5 based crypt algorithm.
+fn apr1(password: &[u8], salt: &[u8]) -> String {
+    let salt = &salt[..salt.len().min(8)];
+
+    let mut alternate = Md5::new();
+    alternate.update(password);
+    alternate.update(salt);
+    alternate.update(password);
+    let alternate = alternate.finalize();
+
+    let mut context = Md5::new();
+    context.update(password);
+    context.update(APR1_PREFIX.as_bytes());
+    context.update(salt);
+    for chunk in password.chunks(16) {
+        context.update(&alternate[..chunk.len()]);
+    }
+    let mut length = password.len();
+    while length > 0 {
+        if length & 1 == 1 {
+            context.update([0u8]);
+        } else {
+            context.update(&password[..1]);
+        }
+        length >>= 1;
+    }
+    let mut digest = context.finalize();
+
+    // The iterations are intended to slow down brute force attacks
+    for round in 0..1000 {
+        let mut context = Md5::new();
+        if round & 1 == 1 {
+            context.update(password);
+        } else {
+            context.update(digest);
+        }
+        if round % 3 != 0 {
+            context.update(salt);
+        }
+        if round % 7 != 0 {
+            context.update(password);
+        }
+        if round & 1 == 1 {
+            context.update(digest);
+        } else {
+            context.update(password);
+        }
+        digest = context.finalize();
+    }
+
+    let mut encoded = String::with_capacity(22);
+    for (first, second, third) in [(0, 6, 12), (1, 7, 13), (2, 8, 14), (3, 9, 15), (4, 10, 5)] {
+        let value = (u32::from(digest[first]) << 16)
+            | (u32::from(digest[second]) << 8)
+            | u32::from(digest[third]);
+        encode_crypt_base64(value, 4, &mut encoded);
+    }
+    encode_crypt_base64(u32::from(digest[11]), 2, &mut encoded);
+
+    format!(
+        "{}{}${}",
+        APR1_PREFIX,
+        String::from_utf8_lossy(salt),
+        encoded
+    )
+}
+
+/// Appends the given number of characters of the crypt base64 encoding (least significant bits first).
+fn encode_crypt_base64(mut value: u32, characters: usize, output: &mut String) {
+    for _ in 0..characters {
+        output.push(CRYPT_ALPHABET[(value & 0x3f) as usize] as char);
+        value >>= 6;
+    }
+}
@@ -0,0 +1,217 @@
+//! The password encoder module provides a trait definition of `PasswordEncoder` to hash and verify passwords
+//! and a `DelegatingPasswordEncoder` that selects the encoder by the `{id}` prefix of a stored hash.  
+//! Argon2id, bcrypt, scrypt and PBKDF2 implementations are available with the `password-hashing` feature,
+//! an implementation for the formats of Apache htpasswd files with the `htpasswd` feature.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use actix_web::web;
+use once_cell::sync::OnceCell;
+
+use crate::authentication::error::error_type::PasswordEncoderError;
+use crate::authentication::scheme::basic::secret::Secret;
+
+#[cfg(feature = "password-hashing")]
+pub mod argon2_encoder;
+#[cfg(feature = "password-hashing")]
+pub mod bcrypt_encoder;
+#[cfg(feature = "htpasswd")]
+pub mod htpasswd_encoder;
+#[cfg(feature = "password-hashing")]
+pub mod pbkdf2_encoder;
+#[cfg(feature = "password-hashing")]
+pub mod scrypt_encoder;
+
+/// Trait definition of a password encoder. A password encoder hashes raw passwords (with a random salt)
+/// and verifies raw passwords against stored hashes.
+pub trait PasswordEncoder: PasswordEncoderClone {
+    /// Hashes the raw password.
+    fn encode(&self, raw_password: &str) -> Result<String, PasswordEncoderError>;
+
+    /// Verifies the raw password against the encoded password.
+    /// Returns **true** if the password matches, **false** otherwise (also if the encoded password is invalid).
+    fn matches(&self, raw_password: &str, encoded_password: &str) -> bool;
+
+    /// Checks whether the encoded password does not match the current encoding policy (algorithm and parameters)
+    /// and should therefore be encoded again. Returns **false** by default.
+    fn upgrade_encoding(&self, _encoded_password: &str) -> bool {
+        false
+    }
+}
+
+/// A password encoder must be cloneable, `send` and `sync`.
+/// Therefore it has to implement the `PasswordEncoderClone` trait to be cloneable as a boxed object.
+pub trait PasswordEncoderClone: Send + Sync {
+    fn clone_box(&self) -> Box<dyn PasswordEncoder>;
+}
+
+impl<U> PasswordEncoderClone for U
+where
+    U: 'static + PasswordEncoder + Clone,
+{
+    fn clone_box(&self) -> Box<dyn PasswordEncoder> {
+        Box::new(self.clone())
+    }
+}
+
+impl Clone for Box<dyn PasswordEncoder> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
+/// A lazily encoded hash of a dummy password. It is verified instead of a stored hash if a user does not exist,
+/// so that the response time does not reveal whether a username is known.
+#[derive(Clone, Default)]
+pub(crate) struct DummyPasswordHash(Arc<OnceCell<Option<String>>>);
+
+impl DummyPasswordHash {
+    pub(crate) fn new() -> DummyPasswordHash {
+        DummyPasswordHash::default()
+    }
+
+    /// Verifies the raw password against the dummy hash (encoded with the given encoder on first use).
+    /// The result is always **false**.
+    fn verify(&self, password_encoder: &dyn PasswordEncoder, raw_password: &str) -> bool {
+        if let Some(dummy_hash) = self
+            .0
+            .get_or_init(|| password_encoder.encode("dummy-password").ok())
+        {
+            password_encoder.matches(raw_password, dummy_hash);
+        }
+        false
+    }
+
+    /// Verifies the raw password against the password hash of a user or, if the user is unknown (no hash),
+    /// against the dummy hash. Password hashing is expensive by design and is therefore executed on a thread
+    /// pool to not block the event loop.
+    /// Returns the result of `on_match` (executed on the thread pool as well with the encoder, the raw password
+    /// and the password hash) if the password matches, **None** otherwise.
+    pub(crate) async fn verify_blocking<R, F>(
+        &self,
+        password_encoder: Box<dyn PasswordEncoder>,
+        raw_password: Secret,
+        password_hash: Option<String>,
+        on_match: F,
+    ) -> Option<R>
+    where
+        R: Send + 'static,
+        F: FnOnce(&dyn PasswordEncoder, &str, &str) -> R + Send + 'static,
+    {
+        let dummy_password_hash = self.clone();
+        web::block(move || {
+            let raw_password = raw_password.expose_secret();
+            match password_hash {
+                Some(password_hash) if password_encoder.matches(raw_password, &password_hash) => {
+                    Ok(on_match(
+                        password_encoder.as_ref(),
+                        raw_password,
+                        &password_hash,
+                    ))
+                }
+                Some(_) => Err(()),
+                None => {
+                    dummy_password_hash.verify(password_encoder.as_ref(), raw_password);
+                    Err(())
+                }
+            }
+        })
+        .await
+        .ok()
+    }
+}
+
+/// A `PasswordEncoder` that delegates to one of several encoders by the id prefix of the
+/// encoded password (e.g. `{argon2}$argon2id$v=19$...` or `{bcrypt}$2b$12$...`).
+/// New passwords are encoded with the encoder of the configured encoding id.
+/// Encoded passwords without a prefix or with an unknown id never match.
+#[derive(Clone)]
+pub struct DelegatingPasswordEncoder {
+    encoding_id: String,
+    encoders: HashMap<String, Box<dyn PasswordEncoder>>,
+}
+
+impl DelegatingPasswordEncoder {
+    /// Constructs a new instance for the id of the encoder to encode new passwords with and
+    /// a map of ids to boxed password encoders. Returns `PasswordEncoderError::InvalidParameters`
+    /// if the map doesn't contain the encoding id.
+    pub fn new(
+        encoding_id: &str,
+        encoders: HashMap<String, Box<dyn PasswordEncoder>>,
+    ) -> Result<DelegatingPasswordEncoder, PasswordEncoderError> {
+        if !encoders.contains_key(encoding_id) {
+            return Err(PasswordEncoderError::InvalidParameters);
+        }
+        Ok(DelegatingPasswordEncoder {
+            encoding_id: encoding_id.to_string(),
+            encoders,
+        })
+    }
+}
+
+#[cfg(feature = "password-hashing")]
+impl Default for DelegatingPasswordEncoder {
+    /// Constructs a new instance encoding passwords with Argon2id (`argon2`) that also verifies
+    /// bcrypt (`bcrypt`), scrypt (`scrypt`) and PBKDF2 (`pbkdf2`) hashes.
+    fn default() -> Self {
+        let mut encoders: HashMap<String, Box<dyn PasswordEncoder>> = HashMap::new();
+        encoders.insert(
+            "argon2".to_string(),
+            Box::new(argon2_encoder::Argon2PasswordEncoder::new()),
+        );
+        encoders.insert(
+            "bcrypt".to_string(),
+            Box::new(bcrypt_encoder::BcryptPasswordEncoder::new()),
+        );
+        encoders.insert(
+            "pbkdf2".to_string(),
+            Box::new(pbkdf2_encoder::Pbkdf2PasswordEncoder::new()),
+        );
+        encoders.insert(
+            "scrypt".to_string(),
+            Box::new(scrypt_encoder::ScryptPasswordEncoder::new()),
+        );
+        DelegatingPasswordEncoder::new("argon2", encoders)
+            .expect("The argon2 encoder is registered")
+    }
+}
+
+impl PasswordEncoder for DelegatingPasswordEncoder {
+    fn encode(&self, raw_password: &str) -> Result<String, PasswordEncoderError> {
+        let encoder = &self.encoders[&self.encoding_id];
+        Ok(format!(
+            "{{{}}}{}",
+            self.encoding_id,
+            encoder.encode(raw_password)?
+        ))
+    }
+
+    fn matches(&self, raw_password: &str, encoded_password: &str) -> bool {
+        match split_encoder_id(encoded_password) {
+            Some((id, encoded_password)) => self
+                .encoders
+                .get(id)
+                .is_some_and(|encoder| encoder.matches(raw_password, encoded_password)),
+            None => false,
+        }
+    }
+
+    /// Returns **true** if the encoded password was not encoded with the encoder of the encoding id
+    /// or if that encoder requires an upgrade.
+    fn upgrade_encoding(&self, encoded_password: &str) -> bool {
+        match split_encoder_id(encoded_password) {
+            Some((id, encoded_password)) if id == self.encoding_id => {
+                self.encoders[id].upgrade_encoding(encoded_password)
+            }
+            _ => true,
+        }
+    }
+}
+
+/// Splits an encoded password into the encoder id and the hash (`{id}hash`).
+fn split_encoder_id(encoded_password: &str) -> Option<(&str, &str)> {
+    let encoded_password = encoded_password.strip_prefix('{')?;
+    let end = encoded_password.find('}')?;
+    Some((&encoded_password[..end], &encoded_password[end + 1..]))
+}
@@ -0,0 +1,81 @@
+//! A `PasswordEncoder` implementation for PBKDF2.
+
+use std::convert::TryFrom;
+
+use pbkdf2::password_hash::rand_core::OsRng;
+use pbkdf2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use pbkdf2::{Algorithm, Params, Pbkdf2};
+
+use crate::authentication::error::error_type::PasswordEncoderError;
+use crate::authentication::scheme::basic::password_encoder::PasswordEncoder;
+
+/// A `PasswordEncoder` hashing passwords with PBKDF2-HMAC-SHA256 (PHC string format, e.g. `$pbkdf2-sha256$i=600000,l=32$...`).
+/// Hashes with PBKDF2-HMAC-SHA512 are verified as well.
+#[derive(Clone)]
+pub struct Pbkdf2PasswordEncoder {
+    rounds: u32,
+}
+
+impl Pbkdf2PasswordEncoder {
+    /// Constructs a new instance with the default number of 600.000 rounds.
+    pub fn new() -> Pbkdf2PasswordEncoder {
+        Pbkdf2PasswordEncoder {
+            rounds: Params::default().rounds,
+        }
+    }
+
+    /// Constructs a new instance with the given number of rounds (at least 1.000).
+    pub fn with_rounds(rounds: u32) -> Result<Pbkdf2PasswordEncoder, PasswordEncoderError> {
+        if rounds >= 1000 {
+            Ok(Pbkdf2PasswordEncoder { rounds })
+        } else {
+            Err(PasswordEncoderError::InvalidParameters)
+        }
+    }
+}
+
+impl Default for Pbkdf2PasswordEncoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PasswordEncoder for Pbkdf2PasswordEncoder {
+    fn encode(&self, raw_password: &str) -> Result<String, PasswordEncoderError> {
+        let salt = SaltString::generate(&mut OsRng);
+        let params = Params {
+            rounds: self.rounds,
+            ..Params::default()
+        };
+        Pbkdf2
+            .hash_password_customized(
+                raw_password.as_bytes(),
+                Some(Algorithm::Pbkdf2Sha256.ident()),
+                None,
+                params,
+                &salt,
+            )
+            .map(|hash| hash.to_string())
+            .map_err(|_| PasswordEncoderError::EncodingFailed)
+    }
+
+    fn matches(&self, raw_password: &str, encoded_password: &str) -> bool {
+        match PasswordHash::new(encoded_password) {
+            Ok(hash) => Pbkdf2
+                .verify_password(raw_password.as_bytes(), &hash)
+                .is_ok(),
+            Err(_) => false,
+        }
+    }
+
+    /// Returns **true** if the hash is not a PBKDF2-HMAC-SHA256 hash with the configured number of rounds.
+    fn upgrade_encoding(&self, encoded_password: &str) -> bool {
+        match PasswordHash::new(encoded_password) {
+            Ok(hash) => {
+                hash.algorithm != Algorithm::Pbkdf2Sha256.ident()
+                    || Params::try_from(&hash).map_or(true, |params| params.rounds != self.rounds)
+            }
+            Err(_) => true,
+        }
+    }
+}
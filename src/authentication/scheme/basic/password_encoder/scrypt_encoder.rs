@@ -0,0 +1,76 @@
+//! A `PasswordEncoder` implementation for scrypt.
+
+use std::convert::TryFrom;
+
+use scrypt::password_hash::rand_core::OsRng;
+use scrypt::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use scrypt::{Params, Scrypt};
+
+use crate::authentication::error::error_type::PasswordEncoderError;
+use crate::authentication::scheme::basic::password_encoder::PasswordEncoder;
+
+/// A `PasswordEncoder` hashing passwords with scrypt (PHC string format, e.g. `$scrypt$ln=17,r=8,p=1$...`).
+#[derive(Clone)]
+pub struct ScryptPasswordEncoder {
+    params: Params,
+}
+
+impl ScryptPasswordEncoder {
+    /// Constructs a new instance with the recommended parameters (log2 of the cost 17, block size 8, parallelism 1).
+    pub fn new() -> ScryptPasswordEncoder {
+        ScryptPasswordEncoder {
+            params: Params::recommended(),
+        }
+    }
+
+    /// Constructs a new instance with the given log2 of the cost, block size and degree of parallelism.
+    pub fn with_params(
+        log_n: u8,
+        block_size: u32,
+        parallelism: u32,
+    ) -> Result<ScryptPasswordEncoder, PasswordEncoderError> {
+        let params = Params::new(log_n, block_size, parallelism, Params::RECOMMENDED_LEN)
+            .map_err(|_| PasswordEncoderError::InvalidParameters)?;
+        Ok(ScryptPasswordEncoder { params })
+    }
+}
+
+impl Default for ScryptPasswordEncoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PasswordEncoder for ScryptPasswordEncoder {
+    fn encode(&self, raw_password: &str) -> Result<String, PasswordEncoderError> {
+        let salt = SaltString::generate(&mut OsRng);
+        Scrypt
+            .hash_password_customized(raw_password.as_bytes(), None, None, self.params, &salt)
+            .map(|hash| hash.to_string())
+            .map_err(|_| PasswordEncoderError::EncodingFailed)
+    }
+
+    fn matches(&self, raw_password: &str, encoded_password: &str) -> bool {
+        match PasswordHash::new(encoded_password) {
+            Ok(hash) => Scrypt
+                .verify_password(raw_password.as_bytes(), &hash)
+                .is_ok(),
+            Err(_) => false,
+        }
+    }
+
+    /// Returns **true** if the parameters of the hash differ from the configured parameters.
+    fn upgrade_encoding(&self, encoded_password: &str) -> bool {
+        match PasswordHash::new(encoded_password)
+            .ok()
+            .and_then(|hash| Params::try_from(&hash).ok())
+        {
+            Some(params) => {
+                params.log_n() != self.params.log_n()
+                    || params.r() != self.params.r()
+                    || params.p() != self.params.p()
+            }
+            None => true,
+        }
+    }
+}
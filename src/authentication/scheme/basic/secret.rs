@@ -0,0 +1,54 @@
+//! A wrapper for secrets (e.g. passwords) that is wiped from memory when dropped and is not printed in `Debug` output.
+
+use std::fmt;
+
+use subtle::ConstantTimeEq;
+use zeroize::Zeroize;
+
+/// A secret string. The content is overwritten with zeros when the secret is dropped, is redacted in
+/// `Debug` output and is compared in constant time.
+#[derive(Clone, Default)]
+pub struct Secret(String);
+
+impl Secret {
+    /// Constructs a new instance taking ownership of the secret.
+    pub fn new(secret: String) -> Secret {
+        Secret(secret)
+    }
+
+    /// Returns the secret. The returned value should not be copied.
+    pub fn expose_secret(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<String> for Secret {
+    fn from(secret: String) -> Self {
+        Secret::new(secret)
+    }
+}
+
+impl fmt::Debug for Secret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Secret(***)")
+    }
+}
+
+impl PartialEq for Secret {
+    fn eq(&self, other: &Self) -> bool {
+        constant_time_eq(self.0.as_bytes(), other.0.as_bytes())
+    }
+}
+
+impl Eq for Secret {}
+
+impl Drop for Secret {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+/// Compares two byte slices in constant time (only the length of the slices is not hidden).
+pub fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.ct_eq(b).into()
+}
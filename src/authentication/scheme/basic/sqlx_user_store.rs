@@ -0,0 +1,180 @@
+//! A `BasicUserStore` implementation loading the users from a SQL database with sqlx.
+
+use std::future::Future;
+use std::pin::Pin;
+
+use actix_web::dev::{Payload, PayloadStream};
+use actix_web::{Error, FromRequest, HttpRequest};
+use async_trait::async_trait;
+use serde::Serialize;
+use sqlx::any::AnyRow;
+use sqlx::{AnyPool, Row};
+
+use crate::authentication::error::error_type::AuthenticationError;
+use crate::authentication::scheme::basic::user_store::{BasicUserStore, UserCredentials};
+use crate::authorization::authority::Authorities;
+use crate::user_details::UserDetails;
+
+/// The default query to load the password hash and the enabled flag of a user.
+pub const DEFAULT_USER_QUERY: &str = "SELECT password_hash, enabled FROM users WHERE username = $1";
+/// The default query to load the authorities of a user.
+pub const DEFAULT_AUTHORITIES_QUERY: &str = "SELECT authority FROM authorities WHERE username = $1";
+/// The default query to update the password hash of a user.
+pub const DEFAULT_UPDATE_PASSWORD_HASH_QUERY: &str =
+    "UPDATE users SET password_hash = $1 WHERE username = $2";
+
+/// A user loaded by the `SqlxUserStore`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct SqlxUser {
+    /// The username
+    pub username: String,
+    /// The authorities (e.g. roles) granted to the user
+    pub authorities: Authorities,
+    /// The value of the `enabled` column
+    pub enabled: bool,
+}
+
+impl UserDetails for SqlxUser {
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+}
+
+impl FromRequest for SqlxUser {
+    type Error = Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self, Error>>>>;
+    type Config = ();
+
+    fn from_request(req: &HttpRequest, _payload: &mut Payload<PayloadStream>) -> Self::Future {
+        let req = req.clone();
+
+        Box::pin(async move {
+            req.extensions()
+                .get::<Box<dyn UserDetails>>()
+                .and_then(|user_details| user_details.downcast_ref::<SqlxUser>())
+                .cloned()
+                .ok_or_else(|| AuthenticationError::UsernameNotFound.into())
+        })
+    }
+}
+
+/// A user store querying the users from a SQL database (e.g. PostgreSQL, MySQL or SQLite) with an `AnyPool`.
+/// The database drivers have to be enabled in the sqlx dependency of the application and installed with
+/// `sqlx::any::install_default_drivers` before the pool is created.
+///
+/// The queries are configurable, the placeholder syntax depends on the database (`$1` works with PostgreSQL
+/// and SQLite, MySQL requires `?`):
+/// * The user query is bound to the username and must return the columns `password_hash` (text) and
+///   `enabled` (boolean or integer). Disabled users are rejected with `AuthenticationError::AccountDisabled` by the
+///   `AccountStatusChecker`. Note: The sqlx `Any` driver does not support SQLite `BOOLEAN` columns, use `INTEGER`
+///   instead.
+/// * The authorities query is bound to the username and must return one authority per row in the column `authority`.
+/// * The update password hash query is bound to the new password hash and the username (in this order).
+///
+/// Database errors (e.g. an unavailable database or queries not returning the expected columns) are reported as
+/// `AuthenticationError::ServiceUnavailable`.
+#[derive(Clone)]
+pub struct SqlxUserStore {
+    pool: AnyPool,
+    user_query: String,
+    authorities_query: Option<String>,
+    update_password_hash_query: Option<String>,
+}
+
+impl SqlxUserStore {
+    /// Constructs an instance for the given connection pool with the default queries.
+    pub fn new(pool: AnyPool) -> SqlxUserStore {
+        SqlxUserStore {
+            pool,
+            user_query: DEFAULT_USER_QUERY.to_string(),
+            authorities_query: Some(DEFAULT_AUTHORITIES_QUERY.to_string()),
+            update_password_hash_query: Some(DEFAULT_UPDATE_PASSWORD_HASH_QUERY.to_string()),
+        }
+    }
+
+    /// Sets the query to load the password hash and the enabled flag of a user.
+    pub fn with_user_query(mut self, query: &str) -> SqlxUserStore {
+        self.user_query = query.to_string();
+        self
+    }
+
+    /// Sets the query to load the authorities of a user.
+    /// Users have no authorities if the query is `None`.
+    pub fn with_authorities_query(mut self, query: Option<&str>) -> SqlxUserStore {
+        self.authorities_query = query.map(String::from);
+        self
+    }
+
+    /// Sets the query to update the password hash of a user.
+    /// Password hashes are not upgraded if the query is `None`.
+    pub fn with_update_password_hash_query(mut self, query: Option<&str>) -> SqlxUserStore {
+        self.update_password_hash_query = query.map(String::from);
+        self
+    }
+
+    async fn find_authorities(&self, username: &str) -> Result<Authorities, sqlx::Error> {
+        let query = match &self.authorities_query {
+            Some(query) => query,
+            None => return Ok(Authorities::default()),
+        };
+        let rows = sqlx::query(query)
+            .bind(username)
+            .fetch_all(&self.pool)
+            .await?;
+        rows.iter()
+            .map(|row| row.try_get::<String, _>("authority"))
+            .collect()
+    }
+}
+
+#[async_trait]
+impl BasicUserStore for SqlxUserStore {
+    async fn find_user_by_username(
+        &self,
+        username: &str,
+    ) -> Result<Option<UserCredentials>, AuthenticationError> {
+        let row = match sqlx::query(&self.user_query)
+            .bind(username)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|_| AuthenticationError::ServiceUnavailable)?
+        {
+            Some(row) => row,
+            None => return Ok(None),
+        };
+        let enabled = is_enabled(&row).map_err(|_| AuthenticationError::ServiceUnavailable)?;
+        let password_hash = row
+            .try_get::<String, _>("password_hash")
+            .map_err(|_| AuthenticationError::ServiceUnavailable)?;
+        let authorities = self
+            .find_authorities(username)
+            .await
+            .map_err(|_| AuthenticationError::ServiceUnavailable)?;
+
+        Ok(Some(UserCredentials {
+            user_details: Box::new(SqlxUser {
+                username: username.to_string(),
+                authorities,
+                enabled,
+            }),
+            password_hash,
+        }))
+    }
+
+    async fn update_password_hash(&self, username: &str, password_hash: &str) {
+        if let Some(query) = &self.update_password_hash_query {
+            // A failed upgrade is retried with the next successful authentication
+            let _ = sqlx::query(query)
+                .bind(password_hash)
+                .bind(username)
+                .execute(&self.pool)
+                .await;
+        }
+    }
+}
+
+/// Reads the enabled flag, which is an integer in databases without a boolean type (e.g. SQLite).
+fn is_enabled(row: &AnyRow) -> Result<bool, sqlx::Error> {
+    row.try_get::<bool, _>("enabled")
+        .or_else(|_| row.try_get::<i64, _>("enabled").map(|enabled| enabled != 0))
+}
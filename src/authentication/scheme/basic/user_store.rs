@@ -0,0 +1,57 @@
+//! The trait definition of a user store and its clone capabilities for basic authentication with password hashing.
+
+use async_trait::async_trait;
+use downcast_rs::impl_downcast;
+use downcast_rs::Downcast;
+
+use crate::authentication::error::error_type::AuthenticationError;
+use crate::user_details::UserDetails;
+
+/// The credentials of a user as stored in a datastore.
+pub struct UserCredentials {
+    /// The `UserDetails` to attach to the request context if the password matches.
+    pub user_details: Box<dyn UserDetails + Send + Sync>,
+    /// The encoded password (hash) of the user.
+    pub password_hash: String,
+}
+
+/// The trait definition of a user store for basic authentication.
+/// A user store is used to load the `UserCredentials` for a given username from a datastore.
+/// In contrast to the `BasicUserDetailsService` the password is verified by the
+/// `PasswordAuthenticationProvider` with a `PasswordEncoder`.
+#[async_trait]
+pub trait BasicUserStore: Downcast + BasicUserStoreClone {
+    /// Loads the credentials of the user with the given username. Returns `Ok(None)` for unknown users
+    /// and an error (e.g. `AuthenticationError::ServiceUnavailable`) if the datastore cannot be queried.
+    async fn find_user_by_username(
+        &self,
+        username: &str,
+    ) -> Result<Option<UserCredentials>, AuthenticationError>;
+
+    /// Updates the encoded password of a user. Called by the `PasswordAuthenticationProvider` after a successful
+    /// authentication if the stored hash does not match the encoding policy of the `PasswordEncoder` anymore
+    /// (e.g. after increasing the cost parameters or switching the algorithm). Does nothing by default.
+    async fn update_password_hash(&self, _username: &str, _password_hash: &str) {}
+}
+impl_downcast!(BasicUserStore);
+
+/// A user store must be cloneable, `send` and `sync`.
+/// Therefore it has to implement the `BasicUserStoreClone` trait to be cloneable as a boxed object.
+pub trait BasicUserStoreClone: Sync + Send {
+    fn clone_box(&self) -> Box<dyn BasicUserStore>;
+}
+
+impl<U> BasicUserStoreClone for U
+where
+    U: 'static + BasicUserStore + Clone,
+{
+    fn clone_box(&self) -> Box<dyn BasicUserStore> {
+        Box::new(self.clone())
+    }
+}
+
+impl Clone for Box<dyn BasicUserStore> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
@@ -0,0 +1,93 @@
+//! An implementation of a authentication extractor for resource servers accepting JWTs and opaque tokens.
+
+use actix_web::http::{header, HeaderMap};
+use async_trait::async_trait;
+use serde::Deserialize;
+
+use crate::authentication::error::error_type::AuthenticationError;
+use crate::authentication::scheme::authentication::Authentication;
+use crate::authentication::scheme::bearer::introspection::TokenIntrospector;
+use crate::authentication::scheme::bearer::jwt::token::decoder::TokenDecoder;
+use crate::authentication::scheme::bearer::jwt::token::{is_jwe, is_jws, Claims};
+use crate::authentication::scheme::bearer::jwt::JwtBearerAuthentication;
+use crate::authentication::scheme::header_extractor::{
+    extract_auth_header, AuthorizationHeaderExtractor,
+};
+use crate::authorization::authority::AuthorityMapper;
+use crate::instrumentation;
+
+/// The definition of a `HybridBearerAuthenticationExtractor`. The authentication extractor
+/// extracts the bearer token from the authorization header and detects whether it is a JWT (JWS or JWE)
+/// or an opaque token. A JWT is decoded with the token decoders, an opaque token is validated with the
+/// token introspector. In both cases a `JwtBearerAuthentication` with the resulting `Claims` is
+/// returned, so that the `JwtAuthenticationProvider` can be used for both kinds of tokens.
+#[derive(Clone)]
+pub struct HybridBearerAuthenticationExtractor<T: for<'b> Deserialize<'b> + Claims> {
+    pub token_decoders: Vec<Box<dyn TokenDecoder<T>>>,
+    pub token_introspector: Box<dyn TokenIntrospector<T>>,
+    pub authority_mapper: Option<Box<dyn AuthorityMapper>>,
+}
+
+impl<T: for<'b> Deserialize<'b> + Claims> HybridBearerAuthenticationExtractor<T> {
+    /// Constructs a new instance for a given vector of boxed `TokenDecoder` instances (used for JWTs)
+    /// and a boxed `TokenIntrospector` (used for opaque tokens).
+    pub fn new(
+        token_decoders: Vec<Box<dyn TokenDecoder<T>>>,
+        token_introspector: Box<dyn TokenIntrospector<T>>,
+    ) -> HybridBearerAuthenticationExtractor<T> {
+        HybridBearerAuthenticationExtractor {
+            token_decoders,
+            token_introspector,
+            authority_mapper: None,
+        }
+    }
+
+    /// Sets an `AuthorityMapper` to map the claims of the decoded / introspected tokens to `Authorities`.
+    pub fn with_authority_mapper(
+        mut self,
+        authority_mapper: Box<dyn AuthorityMapper>,
+    ) -> HybridBearerAuthenticationExtractor<T> {
+        self.authority_mapper = Some(authority_mapper);
+        self
+    }
+
+    fn decode_jwt(&self, token: &str) -> Result<Box<T>, AuthenticationError> {
+        for (index, decoder) in self.token_decoders.iter().enumerate() {
+            if let Ok(decoded_token) =
+                instrumentation::decode_token(index, || decoder.decode_token(token))
+            {
+                return Ok(decoded_token);
+            }
+        }
+        Err(AuthenticationError::InvalidAuthentication)
+    }
+}
+
+#[async_trait]
+impl<T: for<'b> Deserialize<'b> + Claims> AuthorizationHeaderExtractor
+    for HybridBearerAuthenticationExtractor<T>
+{
+    async fn extract_token(
+        &self,
+        headers: &HeaderMap,
+    ) -> Result<Box<dyn Authentication>, AuthenticationError> {
+        let authorization_header = headers.get(header::AUTHORIZATION);
+        let header_value =
+            authorization_header.ok_or(AuthenticationError::AuthorizationHeaderNotSet)?;
+        let extracted_token = extract_auth_header(header_value, "Bearer", 8)?.to_string();
+
+        // JWTs are never sent to the introspection endpoint
+        let decoded_token = if is_jws(&extracted_token) || is_jwe(&extracted_token) {
+            self.decode_jwt(&extracted_token)?
+        } else {
+            instrumentation::introspect_token(self.token_introspector.introspect(&extracted_token))
+                .await?
+        };
+        Ok(Box::new(JwtBearerAuthentication::new(
+            decoded_token,
+            extracted_token,
+            None,
+            self.authority_mapper.as_deref(),
+        )))
+    }
+}
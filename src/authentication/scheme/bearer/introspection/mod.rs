@@ -0,0 +1,44 @@
+//! The introspection module provides a trait definition of `TokenIntrospector` to validate opaque
+//! (non-JWT) access tokens at the authorization server and a header extractor that accepts both
+//! JWTs and opaque tokens.
+
+use async_trait::async_trait;
+use serde::Deserialize;
+
+use crate::authentication::error::error_type::AuthenticationError;
+use crate::authentication::scheme::bearer::jwt::token::Claims;
+
+pub mod header_extractor;
+
+#[cfg(feature = "token-introspection")]
+pub mod remote_introspector;
+
+/// Token introspector trait definition. Validates an opaque token (e.g. with an OAuth2 token introspection
+/// request as described in RFC 7662) and returns either a boxed instance of `Claims` or an error.
+#[async_trait]
+pub trait TokenIntrospector<T: for<'b> Deserialize<'b> + Claims>:
+    TokenIntrospectorClone<T>
+{
+    async fn introspect(&self, token: &str) -> Result<Box<T>, AuthenticationError>;
+}
+
+/// A token introspector must be cloneable, `send` and `sync`.
+/// Therefore it has to implement the `TokenIntrospectorClone` trait to be cloneable as a boxed object.
+pub trait TokenIntrospectorClone<T: for<'b> Deserialize<'b> + Claims>: Send + Sync {
+    fn clone_box(&self) -> Box<dyn TokenIntrospector<T>>;
+}
+
+impl<T: for<'b> Deserialize<'b> + Claims, U> TokenIntrospectorClone<T> for U
+where
+    U: 'static + TokenIntrospector<T> + Clone,
+{
+    fn clone_box(&self) -> Box<dyn TokenIntrospector<T>> {
+        Box::new(self.clone())
+    }
+}
+
+impl<T: for<'b> Deserialize<'b> + Claims> Clone for Box<dyn TokenIntrospector<T>> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
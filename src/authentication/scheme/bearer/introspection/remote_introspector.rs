@@ -0,0 +1,81 @@
+//! A token introspector implementation that validates opaque tokens at the introspection
+//! endpoint of an OAuth2 authorization server as described in RFC 7662.
+
+use std::sync::Arc;
+
+use actix_web::web;
+use async_trait::async_trait;
+use once_cell::sync::OnceCell;
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::authentication::error::error_type::AuthenticationError;
+use crate::authentication::scheme::bearer::introspection::TokenIntrospector;
+use crate::authentication::scheme::bearer::jwt::token::Claims;
+
+/// Remote token introspector definition. The resource server authenticates itself with the client id and
+/// secret (basic authentication) at the introspection endpoint.
+/// Inactive tokens are rejected with `AuthenticationError::InvalidToken`. Failed requests (e.g. an unreachable
+/// authorization server or an error status) result in `AuthenticationError::ServiceUnavailable`.
+#[derive(Clone)]
+pub struct RemoteTokenIntrospector {
+    introspection_url: String,
+    client_id: String,
+    client_secret: String,
+    // The http client (and its connection pool) is shared by all clones. It is created lazily on the
+    // thread pool, as the blocking client must not be created on the event loop.
+    client: Arc<OnceCell<reqwest::blocking::Client>>,
+}
+
+impl RemoteTokenIntrospector {
+    /// Constructs a new instance of `RemoteTokenIntrospector` for the given introspection endpoint URL and
+    /// client credentials.
+    pub fn new(
+        introspection_url: String,
+        client_id: String,
+        client_secret: String,
+    ) -> RemoteTokenIntrospector {
+        RemoteTokenIntrospector {
+            introspection_url,
+            client_id,
+            client_secret,
+            client: Arc::new(OnceCell::new()),
+        }
+    }
+}
+
+#[async_trait]
+impl<T: for<'b> Deserialize<'b> + Claims> TokenIntrospector<T> for RemoteTokenIntrospector {
+    async fn introspect(&self, token: &str) -> Result<Box<T>, AuthenticationError> {
+        let introspector = self.clone();
+        let token = token.to_string();
+
+        // The request is executed on a thread pool as the blocking client must not block the event loop.
+        let response = web::block(move || {
+            introspector
+                .client
+                .get_or_init(reqwest::blocking::Client::new)
+                .post(&introspector.introspection_url)
+                .basic_auth(&introspector.client_id, Some(&introspector.client_secret))
+                .form(&[
+                    ("token", token.as_str()),
+                    ("token_type_hint", "access_token"),
+                ])
+                .send()
+                .and_then(|response| response.error_for_status())
+                .and_then(|response| response.json::<Value>())
+        })
+        .await
+        // The token could not be verified, which is not the fault of the client
+        .map_err(|_| AuthenticationError::ServiceUnavailable)?;
+
+        // Inactive tokens must be rejected. No further information is evaluated in this case.
+        match response.get("active") {
+            Some(Value::Bool(true)) => match serde_json::from_value::<T>(response) {
+                Ok(claims) => Ok(Box::new(claims)),
+                Err(_) => Err(AuthenticationError::InvalidToken),
+            },
+            _ => Err(AuthenticationError::InvalidToken),
+        }
+    }
+}
@@ -0,0 +1,92 @@
+//! An implementation of an `AuthenticationProvider` for JWTs of multiple trusted issuers.
+
+use async_trait::async_trait;
+use serde::Deserialize;
+
+use crate::authentication::error::error_type::AuthenticationError;
+use crate::authentication::scheme::authentication::Authentication;
+use crate::authentication::scheme::authentication_provider::AuthenticationProvider;
+use crate::authentication::scheme::bearer::issuer::IssuerRegistry;
+use crate::authentication::scheme::bearer::jwt::token::Claims;
+use crate::authentication::scheme::bearer::jwt::user_details_service::JwtUserDetailsService;
+use crate::authentication::scheme::bearer::jwt::JwtBearerAuthentication;
+use crate::user_details::account_status::{AccountStatusChecker, DefaultAccountStatusChecker};
+use crate::user_details::UserDetails;
+
+/// The definition of a `MultiIssuerJwtAuthenticationProvider`. The user lookup is delegated to the
+/// user details service of the issuer the token was verified for, or to the default user details service
+/// if the issuer has none configured. The account status of the found user is verified with the `AccountStatusChecker`.
+#[derive(Clone)]
+pub struct MultiIssuerJwtAuthenticationProvider<T: for<'b> Deserialize<'b> + Claims> {
+    issuer_registry: IssuerRegistry<T>,
+    user_details_service: Box<dyn JwtUserDetailsService>,
+    account_status_checker: Box<dyn AccountStatusChecker>,
+}
+
+impl<T: for<'b> Deserialize<'b> + Claims> MultiIssuerJwtAuthenticationProvider<T> {
+    /// Constructs an instance of a `MultiIssuerJwtAuthenticationProvider` for an `IssuerRegistry` and a
+    /// boxed instance of a default `JwtUserDetailsService`.
+    pub fn new(
+        issuer_registry: IssuerRegistry<T>,
+        user_details_service: Box<dyn JwtUserDetailsService>,
+    ) -> MultiIssuerJwtAuthenticationProvider<T> {
+        MultiIssuerJwtAuthenticationProvider {
+            issuer_registry,
+            user_details_service,
+            account_status_checker: Box::new(DefaultAccountStatusChecker::new()),
+        }
+    }
+
+    /// Replaces the `DefaultAccountStatusChecker`.
+    pub fn with_account_status_checker(
+        mut self,
+        account_status_checker: Box<dyn AccountStatusChecker>,
+    ) -> MultiIssuerJwtAuthenticationProvider<T> {
+        self.account_status_checker = account_status_checker;
+        self
+    }
+}
+
+#[async_trait]
+impl<T: for<'b> Deserialize<'b> + Claims + Clone> AuthenticationProvider
+    for MultiIssuerJwtAuthenticationProvider<T>
+{
+    fn supports(&self, authentication: &dyn Authentication) -> bool {
+        authentication.is::<JwtBearerAuthentication>()
+    }
+
+    #[allow(clippy::borrowed_box)]
+    async fn authenticate(
+        &self,
+        authentication: &Box<dyn Authentication>,
+    ) -> Result<Box<dyn UserDetails>, AuthenticationError> {
+        if authentication.is::<JwtBearerAuthentication>() {
+            let jwt_auth = authentication
+                .downcast_ref::<JwtBearerAuthentication>()
+                .unwrap();
+
+            let issuer_configuration = match &jwt_auth.issuer {
+                Some(issuer) => Some(
+                    self.issuer_registry
+                        .get_issuer(issuer)
+                        .ok_or(AuthenticationError::InvalidToken)?,
+                ),
+                None => None,
+            };
+            let user_details_service = issuer_configuration
+                .as_ref()
+                .and_then(|configuration| configuration.user_details_service.as_ref())
+                .unwrap_or(&self.user_details_service);
+
+            match user_details_service.find_user(&jwt_auth.token).await {
+                Some(user) => {
+                    self.account_status_checker.check(user.as_ref())?;
+                    Ok(user)
+                }
+                None => Err(AuthenticationError::UsernameNotFound),
+            }
+        } else {
+            Err(AuthenticationError::InvalidAuthentication)
+        }
+    }
+}
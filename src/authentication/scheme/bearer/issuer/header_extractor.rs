@@ -0,0 +1,69 @@
+//! An implementation of a authentication extractor for bearer tokens of multiple trusted issuers.
+
+use actix_web::http::{header, HeaderMap};
+use async_trait::async_trait;
+use serde::Deserialize;
+
+use crate::authentication::error::error_type::AuthenticationError;
+use crate::authentication::scheme::authentication::Authentication;
+use crate::authentication::scheme::bearer::issuer::IssuerRegistry;
+use crate::authentication::scheme::bearer::jwt::token::{decode_unverified_payload, Claims};
+use crate::authentication::scheme::bearer::jwt::JwtBearerAuthentication;
+use crate::authentication::scheme::header_extractor::{
+    extract_auth_header, AuthorizationHeaderExtractor,
+};
+use crate::instrumentation;
+
+/// The definition of a `MultiIssuerBearerAuthenticationExtractor`. The authentication extractor
+/// extracts the bearer token from the authorization header, reads the `iss` claim from the (not yet verified)
+/// token and decodes the token with the token decoders of that issuer only. Tokens of unknown issuers are rejected.
+#[derive(Clone)]
+pub struct MultiIssuerBearerAuthenticationExtractor<T: for<'b> Deserialize<'b> + Claims> {
+    pub issuer_registry: IssuerRegistry<T>,
+}
+
+impl<T: for<'b> Deserialize<'b> + Claims> MultiIssuerBearerAuthenticationExtractor<T> {
+    /// Constructs a new instance for a given `IssuerRegistry`.
+    pub fn new(issuer_registry: IssuerRegistry<T>) -> MultiIssuerBearerAuthenticationExtractor<T> {
+        MultiIssuerBearerAuthenticationExtractor { issuer_registry }
+    }
+}
+
+#[async_trait]
+impl<T: for<'b> Deserialize<'b> + Claims> AuthorizationHeaderExtractor
+    for MultiIssuerBearerAuthenticationExtractor<T>
+{
+    async fn extract_token(
+        &self,
+        headers: &HeaderMap,
+    ) -> Result<Box<dyn Authentication>, AuthenticationError> {
+        let authorization_header = headers.get(header::AUTHORIZATION);
+        let header_value =
+            authorization_header.ok_or(AuthenticationError::AuthorizationHeaderNotSet)?;
+        let extracted_token = extract_auth_header(header_value, "Bearer", 8)?.to_string();
+
+        // The issuer is read from the unverified payload to select the issuer's decoders.
+        // As the payload is signed, the issuer is trustworthy once the token has been verified.
+        let issuer = decode_unverified_payload(&extracted_token)
+            .and_then(|payload| payload.get("iss")?.as_str().map(String::from))
+            .ok_or(AuthenticationError::InvalidToken)?;
+        let issuer_configuration = self
+            .issuer_registry
+            .get_issuer(&issuer)
+            .ok_or(AuthenticationError::InvalidToken)?;
+
+        for (index, decoder) in issuer_configuration.token_decoders.iter().enumerate() {
+            if let Ok(decoded_token) =
+                instrumentation::decode_token(index, || decoder.decode_token(&extracted_token))
+            {
+                return Ok(Box::new(JwtBearerAuthentication::new(
+                    decoded_token,
+                    extracted_token,
+                    Some(issuer),
+                    issuer_configuration.authority_mapper.as_deref(),
+                )));
+            }
+        }
+        Err(AuthenticationError::InvalidAuthentication)
+    }
+}
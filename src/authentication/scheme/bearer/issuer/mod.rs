@@ -0,0 +1,113 @@
+//! The issuer module provides a registry of trusted token issuers for multi-tenant resource servers.
+//! Each issuer has its own set of `TokenDecoder` instances (keys and validation rules) and optionally
+//! its own `JwtUserDetailsService`. Tokens of issuers that are not registered are rejected.
+
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+use serde::Deserialize;
+
+use crate::authentication::scheme::bearer::jwt::token::decoder::TokenDecoder;
+use crate::authentication::scheme::bearer::jwt::token::Claims;
+use crate::authentication::scheme::bearer::jwt::user_details_service::JwtUserDetailsService;
+use crate::authorization::authority::AuthorityMapper;
+
+pub mod authentication_provider;
+pub mod header_extractor;
+
+/// The configuration of a trusted issuer.
+#[derive(Clone)]
+pub struct IssuerConfiguration<T: for<'b> Deserialize<'b> + Claims> {
+    /// The token decoders used to verify tokens of the issuer.
+    pub token_decoders: Vec<Box<dyn TokenDecoder<T>>>,
+    /// The user details service used to load the `UserDetails` for tokens of the issuer.
+    /// The default user details service of the `MultiIssuerJwtAuthenticationProvider` is used if not set.
+    pub user_details_service: Option<Box<dyn JwtUserDetailsService>>,
+    /// The authority mapper used to map the claims of tokens of the issuer to `Authorities`.
+    pub authority_mapper: Option<Box<dyn AuthorityMapper>>,
+}
+
+impl<T: for<'b> Deserialize<'b> + Claims> IssuerConfiguration<T> {
+    /// Constructs a new instance for a given vector of boxed `TokenDecoder` instances.
+    pub fn new(token_decoders: Vec<Box<dyn TokenDecoder<T>>>) -> IssuerConfiguration<T> {
+        IssuerConfiguration {
+            token_decoders,
+            user_details_service: None,
+            authority_mapper: None,
+        }
+    }
+
+    /// Sets an issuer specific user details service.
+    pub fn with_user_details_service(
+        mut self,
+        user_details_service: Box<dyn JwtUserDetailsService>,
+    ) -> IssuerConfiguration<T> {
+        self.user_details_service = Some(user_details_service);
+        self
+    }
+
+    /// Sets an issuer specific authority mapper, as the token layout usually differs between identity providers.
+    pub fn with_authority_mapper(
+        mut self,
+        authority_mapper: Box<dyn AuthorityMapper>,
+    ) -> IssuerConfiguration<T> {
+        self.authority_mapper = Some(authority_mapper);
+        self
+    }
+}
+
+/// A registry of trusted issuers. The registry is cloneable and clones share the registered issuers,
+/// so that issuers can be added or removed at runtime (e.g. when a new tenant is onboarded).
+#[derive(Clone)]
+pub struct IssuerRegistry<T: for<'b> Deserialize<'b> + Claims> {
+    issuers: Arc<RwLock<HashMap<String, Arc<IssuerConfiguration<T>>>>>,
+}
+
+impl<T: for<'b> Deserialize<'b> + Claims> IssuerRegistry<T> {
+    /// Constructs a new instance without any trusted issuers.
+    pub fn new() -> IssuerRegistry<T> {
+        IssuerRegistry {
+            issuers: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Adds a trusted issuer. The issuer must match the `iss` claim of the tokens exactly.
+    /// An existing configuration of the issuer is replaced.
+    pub fn add_issuer(&self, issuer: String, configuration: IssuerConfiguration<T>) {
+        self.issuers
+            .write()
+            .expect("Issuer registry lock poisoned")
+            .insert(issuer, Arc::new(configuration));
+    }
+
+    /// Removes a trusted issuer. Returns **true** if the issuer was registered, **false** otherwise.
+    pub fn remove_issuer(&self, issuer: &str) -> bool {
+        self.issuers
+            .write()
+            .expect("Issuer registry lock poisoned")
+            .remove(issuer)
+            .is_some()
+    }
+
+    /// Checks whether the given issuer is registered.
+    pub fn contains_issuer(&self, issuer: &str) -> bool {
+        self.issuers
+            .read()
+            .expect("Issuer registry lock poisoned")
+            .contains_key(issuer)
+    }
+
+    pub(crate) fn get_issuer(&self, issuer: &str) -> Option<Arc<IssuerConfiguration<T>>> {
+        self.issuers
+            .read()
+            .expect("Issuer registry lock poisoned")
+            .get(issuer)
+            .cloned()
+    }
+}
+
+impl<T: for<'b> Deserialize<'b> + Claims> Default for IssuerRegistry<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
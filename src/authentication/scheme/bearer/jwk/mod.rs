@@ -5,6 +5,8 @@ use std::fs;
 use serde::Deserialize;
 
 use crate::authentication::error::error_type::JwkLoaderError;
+#[cfg(feature = "jwk-loader")]
+use crate::instrumentation;
 
 pub mod default_jwk;
 
@@ -31,12 +33,14 @@ impl<T: for<'a> Deserialize<'a>> JwkLoader<T> {
     /// Download a JWK file from a remote location with http.
     #[cfg(feature = "jwk-loader")]
     pub fn from_url(url: String) -> Result<JwkLoader<T>, JwkLoaderError> {
-        match reqwest::blocking::get(&url) {
+        let result = match reqwest::blocking::get(&url) {
             Ok(response) => match response.json::<T>() {
                 Ok(jwks) => Ok(JwkLoader { jwks }),
                 Err(_) => Err(JwkLoaderError::InvalidJsonResponse),
             },
             Err(_) => Err(JwkLoaderError::JwksCouldNotBeDownloaded),
-        }
+        };
+        instrumentation::jwks_loaded(&url, &result);
+        result
     }
 }
@@ -0,0 +1,76 @@
+//! The JWT profile for OAuth 2.0 access tokens (RFC 9068) defines a set of mandatory claims.
+//! This module provides an implementation of `Claims` with the claims of the profile.
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::authentication::scheme::bearer::jwt::token::audience::Audience;
+use crate::authentication::scheme::bearer::jwt::token::scopes::Scopes;
+use crate::authentication::scheme::bearer::jwt::token::Claims;
+
+/// Claims of a JWT access token as defined in RFC 9068. Tokens without one of the mandatory claims
+/// cannot be deserialized and are therefore rejected.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AccessTokenJwt {
+    /// The URL of the authorization server
+    pub iss: String,
+    /// The expiration date of the token
+    pub exp: usize,
+    /// The recipients the token is for
+    pub aud: Audience,
+    /// The principal identifier (resource owner or client)
+    pub sub: String,
+    /// The identifier of the client the token was issued to
+    pub client_id: String,
+    /// The time the token was issued
+    pub iat: usize,
+    /// Unique identifier of the token
+    pub jti: String,
+    /// The scopes granted to the token
+    #[serde(default, skip_serializing_if = "Scopes::is_empty")]
+    pub scope: Scopes,
+    /// The time the resource owner was authenticated
+    pub auth_time: Option<usize>,
+    /// The authentication context class reference
+    pub acr: Option<String>,
+    /// The authentication methods references
+    pub amr: Option<Vec<String>>,
+    /// All other claims of the token (e.g. `roles`, `groups` or `entitlements`)
+    #[serde(flatten)]
+    pub additional_claims: HashMap<String, Value>,
+}
+
+impl Claims for AccessTokenJwt {
+    fn issuer(&self) -> Option<&str> {
+        Some(&self.iss)
+    }
+
+    fn subject(&self) -> Option<&str> {
+        Some(&self.sub)
+    }
+
+    fn audience(&self) -> Option<Audience> {
+        Some(self.aud.clone())
+    }
+
+    fn expiration_time(&self) -> Option<usize> {
+        Some(self.exp)
+    }
+
+    fn issued_at(&self) -> Option<usize> {
+        Some(self.iat)
+    }
+
+    fn jwt_id(&self) -> Option<&str> {
+        Some(&self.jti)
+    }
+
+    fn scopes(&self) -> Scopes {
+        self.scope.clone()
+    }
+
+    fn to_json(&self) -> Option<Value> {
+        serde_json::to_value(self).ok()
+    }
+}
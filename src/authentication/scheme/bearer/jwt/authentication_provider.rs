@@ -7,12 +7,15 @@ use crate::authentication::scheme::authentication::Authentication;
 use crate::authentication::scheme::authentication_provider::AuthenticationProvider;
 use crate::authentication::scheme::bearer::jwt::user_details_service::JwtUserDetailsService;
 use crate::authentication::scheme::bearer::jwt::JwtBearerAuthentication;
+use crate::user_details::account_status::{AccountStatusChecker, DefaultAccountStatusChecker};
 use crate::user_details::UserDetails;
 
 /// The definition of a `JwtAuthenticationProvider`.
+/// The account status of the found user is verified with the `AccountStatusChecker`.
 #[derive(Clone)]
 pub struct JwtAuthenticationProvider {
     user_details_service: Box<dyn JwtUserDetailsService>,
+    account_status_checker: Box<dyn AccountStatusChecker>,
 }
 
 impl JwtAuthenticationProvider {
@@ -21,12 +24,26 @@ impl JwtAuthenticationProvider {
     pub fn new(user_details_service: Box<dyn JwtUserDetailsService>) -> JwtAuthenticationProvider {
         JwtAuthenticationProvider {
             user_details_service,
+            account_status_checker: Box::new(DefaultAccountStatusChecker::new()),
         }
     }
+
+    /// Replaces the `DefaultAccountStatusChecker`.
+    pub fn with_account_status_checker(
+        mut self,
+        account_status_checker: Box<dyn AccountStatusChecker>,
+    ) -> JwtAuthenticationProvider {
+        self.account_status_checker = account_status_checker;
+        self
+    }
 }
 
 #[async_trait]
 impl AuthenticationProvider for JwtAuthenticationProvider {
+    fn supports(&self, authentication: &dyn Authentication) -> bool {
+        authentication.is::<JwtBearerAuthentication>()
+    }
+
     #[allow(clippy::borrowed_box)]
     async fn authenticate(
         &self,
@@ -37,7 +54,10 @@ impl AuthenticationProvider for JwtAuthenticationProvider {
                 .downcast_ref::<JwtBearerAuthentication>()
                 .unwrap();
             match self.user_details_service.find_user(&jwt_auth.token).await {
-                Some(user) => Ok(user),
+                Some(user) => {
+                    self.account_status_checker.check(user.as_ref())?;
+                    Ok(user)
+                }
                 None => Err(AuthenticationError::UsernameNotFound),
             }
         } else {
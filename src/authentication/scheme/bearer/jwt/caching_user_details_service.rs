@@ -0,0 +1,115 @@
+//! A caching decorator for a `JwtUserDetailsService`.
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+
+use crate::authentication::scheme::bearer::jwt::token::Claims;
+use crate::authentication::scheme::bearer::jwt::user_details_service::JwtUserDetailsService;
+use crate::user_details::cache::UserDetailsCache;
+use crate::user_details::UserDetails;
+
+/// The key of a cached user: the issuer (if set), the subject and the additional key of the token.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+struct CacheKey {
+    issuer: Option<String>,
+    subject: String,
+    additional_key: Option<String>,
+}
+
+/// A `JwtUserDetailsService` caching the users loaded by the wrapped user details service by the issuer and
+/// subject (`sub`) of the token. Tokens without a subject are not cached.
+///
+/// As all tokens of a subject share the cached user, the wrapped service must not derive the user details from
+/// other claims (e.g. authorities from the `scope` claim). Otherwise these claims have to be added to the cache key
+/// with `with_additional_cache_key`.
+///
+/// Found users are cached for 5 minutes and at most 10.000 entries are kept by default. Users that were not found
+/// are only cached if a negative time to live is set with `with_negative_time_to_live`.
+/// The cache is shared between all clones of the service, so that users can be evicted with `evict`
+/// (e.g. after a change of the user's authorities).
+#[derive(Clone)]
+pub struct CachingJwtUserDetailsService {
+    user_details_service: Box<dyn JwtUserDetailsService>,
+    cache: UserDetailsCache<CacheKey>,
+    additional_cache_key: Option<fn(&dyn Claims) -> Option<String>>,
+}
+
+impl CachingJwtUserDetailsService {
+    /// Constructs a new instance for the boxed `JwtUserDetailsService` to load uncached users with.
+    pub fn new(
+        user_details_service: Box<dyn JwtUserDetailsService>,
+    ) -> CachingJwtUserDetailsService {
+        CachingJwtUserDetailsService {
+            user_details_service,
+            cache: UserDetailsCache::new(),
+            additional_cache_key: None,
+        }
+    }
+
+    /// Sets a function deriving an additional part of the cache key from the token, e.g. the granted scopes
+    /// (`|token| Some(token.scopes().to_delimited())`) if the wrapped service maps them to authorities.
+    pub fn with_additional_cache_key(
+        mut self,
+        additional_cache_key: fn(&dyn Claims) -> Option<String>,
+    ) -> CachingJwtUserDetailsService {
+        self.additional_cache_key = Some(additional_cache_key);
+        self
+    }
+
+    /// Sets the time to live of found users. Defaults to 5 minutes.
+    pub fn with_time_to_live(mut self, time_to_live: Duration) -> CachingJwtUserDetailsService {
+        self.cache.set_time_to_live(time_to_live);
+        self
+    }
+
+    /// Enables caching of users that were not found with the given time to live.
+    pub fn with_negative_time_to_live(
+        mut self,
+        negative_time_to_live: Duration,
+    ) -> CachingJwtUserDetailsService {
+        self.cache
+            .set_negative_time_to_live(Some(negative_time_to_live));
+        self
+    }
+
+    /// Sets the maximum number of cached entries. Defaults to 10.000.
+    pub fn with_max_size(mut self, max_size: usize) -> CachingJwtUserDetailsService {
+        self.cache.set_max_size(max_size);
+        self
+    }
+
+    /// Removes the cached entries of the subject (of all issuers).
+    pub fn evict(&self, subject: &str) {
+        self.cache.evict_matching(|key| key.subject == subject);
+    }
+
+    /// Removes all cached entries.
+    pub fn evict_all(&self) {
+        self.cache.evict_all();
+    }
+}
+
+#[async_trait]
+impl JwtUserDetailsService for CachingJwtUserDetailsService {
+    #[allow(clippy::borrowed_box)]
+    async fn find_user(&self, token: &Box<dyn Claims>) -> Option<Box<dyn UserDetails>> {
+        let key = match token.subject() {
+            Some(subject) => CacheKey {
+                issuer: token.issuer().map(String::from),
+                subject: subject.to_string(),
+                additional_key: self
+                    .additional_cache_key
+                    .and_then(|additional_cache_key| additional_cache_key(token.as_ref())),
+            },
+            None => return self.user_details_service.find_user(token).await,
+        };
+
+        if let Some(user_details) = self.cache.get(&key) {
+            return user_details;
+        }
+        let user_details = self.user_details_service.find_user(token).await;
+        self.cache.insert(key, user_details.clone());
+        user_details
+    }
+}
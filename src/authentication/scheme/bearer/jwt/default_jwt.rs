@@ -1,18 +1,23 @@
 //! A JWT is characterized through a set of `Claims`. There are mandatory claims defined for JWTs and optional ones.
-//! This module provides a default implementation with common claims (iss, sub, aud, exp, nbf, iat, jti).
+//! This module provides a default implementation with common claims (iss, sub, aud, exp, nbf, iat, jti, scope, scp).
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
+use crate::authentication::scheme::bearer::jwt::token::audience::Audience;
+use crate::authentication::scheme::bearer::jwt::token::scopes::Scopes;
 use crate::authentication::scheme::bearer::jwt::token::Claims;
 
 /// A default implementation that can be used for JWT based authentication with commonly used claims.
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct DefaultJwt {
     /// The URL of the identity provider
     pub iss: Option<String>,
     /// The principal identifier
     pub sub: Option<String>,
-    /// The recipients the claim is for
-    pub aud: Option<String>,
+    /// The recipients the claim is for (string or array)
+    pub aud: Option<Audience>,
     /// The expiration date of the token
     pub exp: Option<usize>,
     /// The time the token must not be used before
@@ -21,6 +26,69 @@ pub struct DefaultJwt {
     pub iat: Option<usize>,
     /// Unique identifier of the token
     pub jti: Option<String>,
+    /// The scopes granted to the token (space-delimited string or array)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scope: Option<Scopes>,
+    /// The scopes granted to the token as used by some identity providers (space-delimited string or array)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scp: Option<Scopes>,
+    /// All other (private) claims of the token
+    #[serde(flatten)]
+    pub additional_claims: HashMap<String, Value>,
 }
 
-impl Claims for DefaultJwt {}
+impl Claims for DefaultJwt {
+    fn issuer(&self) -> Option<&str> {
+        self.iss.as_deref()
+    }
+
+    fn subject(&self) -> Option<&str> {
+        self.sub.as_deref()
+    }
+
+    fn audience(&self) -> Option<Audience> {
+        self.aud.clone()
+    }
+
+    fn expiration_time(&self) -> Option<usize> {
+        self.exp
+    }
+
+    fn not_before(&self) -> Option<usize> {
+        self.nbf
+    }
+
+    fn issued_at(&self) -> Option<usize> {
+        self.iat
+    }
+
+    fn jwt_id(&self) -> Option<&str> {
+        self.jti.as_deref()
+    }
+
+    /// Returns the value of a private claim from `additional_claims` or of a registered claim.
+    fn get(&self, name: &str) -> Option<Value> {
+        match self.additional_claims.get(name) {
+            Some(value) => Some(value.clone()),
+            None => self
+                .to_json()?
+                .get(name)
+                .filter(|value| !value.is_null())
+                .cloned(),
+        }
+    }
+
+    /// Returns the union of the `scope` and `scp` claims.
+    fn scopes(&self) -> Scopes {
+        self.scope
+            .iter()
+            .chain(self.scp.iter())
+            .flat_map(Scopes::iter)
+            .cloned()
+            .collect()
+    }
+
+    fn to_json(&self) -> Option<Value> {
+        serde_json::to_value(self).ok()
+    }
+}
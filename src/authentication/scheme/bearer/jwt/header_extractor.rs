@@ -6,11 +6,15 @@ use serde::Deserialize;
 
 use crate::authentication::error::error_type::AuthenticationError;
 use crate::authentication::scheme::authentication::Authentication;
+use crate::authentication::scheme::bearer::jwt::access_token_jwt::AccessTokenJwt;
+use crate::authentication::scheme::bearer::jwt::token::decoder::access_token_profile_decoder::AccessTokenProfileDecoder;
 use crate::authentication::scheme::bearer::jwt::token::decoder::TokenDecoder;
 use crate::authentication::scheme::bearer::jwt::{Claims, JwtBearerAuthentication};
 use crate::authentication::scheme::header_extractor::{
     extract_auth_header, AuthorizationHeaderExtractor,
 };
+use crate::authorization::authority::AuthorityMapper;
+use crate::instrumentation;
 
 /// The definition of a `BearerAuthenticationExtractor`. The authentication extractor
 /// extracts the authentication information from the authorization header and decodes
@@ -18,16 +22,53 @@ use crate::authentication::scheme::header_extractor::{
 #[derive(Clone)]
 pub struct BearerAuthenticationExtractor<T: for<'b> Deserialize<'b> + Claims> {
     pub token_decoders: Vec<Box<dyn TokenDecoder<T>>>,
+    pub authority_mapper: Option<Box<dyn AuthorityMapper>>,
 }
 
 impl<T: for<'b> Deserialize<'b> + Claims> BearerAuthenticationExtractor<T> {
     /// Constructs a new instance for a given vector of boxed `TokenDecoder` instances.
     pub fn new(token_decoders: Vec<Box<dyn TokenDecoder<T>>>) -> BearerAuthenticationExtractor<T> {
-        BearerAuthenticationExtractor { token_decoders }
+        BearerAuthenticationExtractor {
+            token_decoders,
+            authority_mapper: None,
+        }
+    }
+
+    /// Sets an `AuthorityMapper` to map the claims of the decoded tokens to `Authorities`.
+    pub fn with_authority_mapper(
+        mut self,
+        authority_mapper: Box<dyn AuthorityMapper>,
+    ) -> BearerAuthenticationExtractor<T> {
+        self.authority_mapper = Some(authority_mapper);
+        self
     }
 
     fn extract_bearer(&self, header: &HeaderValue) -> Result<String, AuthenticationError> {
-        extract_auth_header(header, "Bearer", 8)
+        extract_auth_header(header, "Bearer", 8).map(|token| token.to_string())
+    }
+}
+
+impl BearerAuthenticationExtractor<AccessTokenJwt> {
+    /// Constructs a new instance that enforces the JWT profile for OAuth 2.0 access tokens (RFC 9068).
+    /// Each token decoder is wrapped into an `AccessTokenProfileDecoder` to verify the `typ` header and
+    /// the expected `iss` and `aud` claims. The claims are deserialized into `AccessTokenJwt` which requires
+    /// all mandatory claims of the profile.
+    pub fn access_token_profile(
+        token_decoders: Vec<Box<dyn TokenDecoder<AccessTokenJwt>>>,
+        issuer: String,
+        audience: String,
+    ) -> BearerAuthenticationExtractor<AccessTokenJwt> {
+        let token_decoders = token_decoders
+            .into_iter()
+            .map(|token_decoder| {
+                Box::new(AccessTokenProfileDecoder::new(
+                    token_decoder,
+                    issuer.clone(),
+                    audience.clone(),
+                )) as Box<dyn TokenDecoder<AccessTokenJwt>>
+            })
+            .collect();
+        BearerAuthenticationExtractor::new(token_decoders)
     }
 }
 
@@ -43,11 +84,16 @@ impl<T: for<'b> Deserialize<'b> + Claims> AuthorizationHeaderExtractor
         match authorization_header {
             Some(header_value) => match self.extract_bearer(header_value) {
                 Ok(extracted_token) => {
-                    for decoder in &self.token_decoders {
-                        if let Ok(decoded_token) = decoder.decode_token(&extracted_token) {
-                            return Ok(Box::new(JwtBearerAuthentication {
-                                token: decoded_token,
-                            }));
+                    for (index, decoder) in self.token_decoders.iter().enumerate() {
+                        if let Ok(decoded_token) = instrumentation::decode_token(index, || {
+                            decoder.decode_token(&extracted_token)
+                        }) {
+                            return Ok(Box::new(JwtBearerAuthentication::new(
+                                decoded_token,
+                                extracted_token,
+                                None,
+                                self.authority_mapper.as_deref(),
+                            )));
                         }
                     }
                     Err(AuthenticationError::InvalidAuthentication)
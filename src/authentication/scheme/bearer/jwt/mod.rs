@@ -1,18 +1,64 @@
 //! The jwt bearer scheme module offers an implementation of a JWT based authentication header extractor, authentication provider and
 //! user detail service.
 
+use std::sync::Arc;
+
+use actix_web::dev::Extensions;
+
 use crate::authentication::scheme::authentication::Authentication;
+use crate::authentication::scheme::bearer::jwt::request_extension::BearerToken;
 use crate::authentication::scheme::bearer::jwt::token::Claims;
+use crate::authorization::authority::{Authorities, AuthorityMapper};
 
+pub mod access_token_jwt;
 pub mod authentication_provider;
+pub mod caching_user_details_service;
 pub mod default_jwt;
 pub mod header_extractor;
+pub mod principal;
+pub mod principal_authentication_provider;
+pub mod request_extension;
 pub mod token;
 pub mod user_details_service;
 
 /// A JWT authentication struct representing the decoded JWT `Claims` extracted from the authorization header.
 pub struct JwtBearerAuthentication {
     pub token: Box<dyn Claims>,
+    /// The issuer the token has been verified for. Only set if the token was verified with an `IssuerRegistry`.
+    pub issuer: Option<String>,
+    /// The authorities mapped from the claims. Empty if no `AuthorityMapper` is configured.
+    pub authorities: Authorities,
+    /// The bearer token as extracted from the authorization header.
+    pub bearer_token: String,
 }
 
-impl Authentication for JwtBearerAuthentication {}
+impl JwtBearerAuthentication {
+    pub(crate) fn new(
+        token: Box<dyn Claims>,
+        bearer_token: String,
+        issuer: Option<String>,
+        authority_mapper: Option<&dyn AuthorityMapper>,
+    ) -> JwtBearerAuthentication {
+        let authorities = match (authority_mapper, token.to_json()) {
+            (Some(authority_mapper), Some(claims)) => authority_mapper.map_authorities(&claims),
+            _ => Authorities::default(),
+        };
+        JwtBearerAuthentication {
+            token,
+            issuer,
+            authorities,
+            bearer_token,
+        }
+    }
+}
+
+impl Authentication for JwtBearerAuthentication {
+    /// Attaches the `Scopes` granted to the token, the mapped `Authorities`, the decoded `Claims`
+    /// and the `BearerToken` to the request context.
+    fn attach_to_request(self: Box<Self>, extensions: &mut Extensions) {
+        extensions.insert(self.token.scopes());
+        extensions.insert(self.authorities);
+        extensions.insert(Arc::<dyn Claims>::from(self.token));
+        extensions.insert(BearerToken::new(self.bearer_token));
+    }
+}
@@ -0,0 +1,62 @@
+//! A `UserDetails` implementation that is created from the decoded claims of a token without any user lookup.
+
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+
+use actix_web::dev::{Payload, PayloadStream};
+use actix_web::{Error, FromRequest, HttpRequest};
+use serde_json::Value;
+
+use crate::authentication::error::error_type::AuthenticationError;
+use crate::authorization::authority::Authorities;
+use crate::user_details::UserDetails;
+
+/// The principal of a stateless JWT authentication. The token is the identity, therefore all
+/// information is taken from the verified token.
+#[derive(Clone)]
+pub struct JwtPrincipal {
+    /// The principal identifier (`sub` claim)
+    pub subject: String,
+    /// The issuer the token has been verified for, or the `iss` claim if not verified with an `IssuerRegistry`
+    pub issuer: Option<String>,
+    /// The authorities mapped from the claims
+    pub authorities: Authorities,
+    /// All claims of the token as JSON object (`Null` if the claims cannot be converted to JSON)
+    pub claims: Value,
+    /// The original bearer token
+    pub token: String,
+}
+
+impl UserDetails for JwtPrincipal {}
+
+/// The bearer token is redacted, so that it cannot be logged accidentally.
+impl fmt::Debug for JwtPrincipal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("JwtPrincipal")
+            .field("subject", &self.subject)
+            .field("issuer", &self.issuer)
+            .field("authorities", &self.authorities)
+            .field("claims", &self.claims)
+            .field("token", &"***")
+            .finish()
+    }
+}
+
+impl FromRequest for JwtPrincipal {
+    type Error = Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self, Error>>>>;
+    type Config = ();
+
+    fn from_request(req: &HttpRequest, _payload: &mut Payload<PayloadStream>) -> Self::Future {
+        let req = req.clone();
+
+        Box::pin(async move {
+            req.extensions()
+                .get::<Box<dyn UserDetails>>()
+                .and_then(|user_details| user_details.downcast_ref::<JwtPrincipal>())
+                .cloned()
+                .ok_or_else(|| AuthenticationError::UsernameNotFound.into())
+        })
+    }
+}
@@ -0,0 +1,65 @@
+//! An implementation of an `AuthenticationProvider` for stateless JWT based authentication without a `JwtUserDetailsService`.
+
+use async_trait::async_trait;
+use serde_json::Value;
+
+use crate::authentication::error::error_type::AuthenticationError;
+use crate::authentication::scheme::authentication::Authentication;
+use crate::authentication::scheme::authentication_provider::AuthenticationProvider;
+use crate::authentication::scheme::bearer::jwt::principal::JwtPrincipal;
+use crate::authentication::scheme::bearer::jwt::JwtBearerAuthentication;
+use crate::user_details::UserDetails;
+
+/// The definition of a `JwtPrincipalAuthenticationProvider`. The provider creates a `JwtPrincipal` directly
+/// from the decoded claims. The claims must provide a subject (see `Claims::subject`), otherwise the
+/// authentication fails.
+#[derive(Clone)]
+pub struct JwtPrincipalAuthenticationProvider {}
+
+impl JwtPrincipalAuthenticationProvider {
+    /// Constructs an instance of a `JwtPrincipalAuthenticationProvider`.
+    pub fn new() -> JwtPrincipalAuthenticationProvider {
+        JwtPrincipalAuthenticationProvider {}
+    }
+}
+
+impl Default for JwtPrincipalAuthenticationProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl AuthenticationProvider for JwtPrincipalAuthenticationProvider {
+    fn supports(&self, authentication: &dyn Authentication) -> bool {
+        authentication.is::<JwtBearerAuthentication>()
+    }
+
+    #[allow(clippy::borrowed_box)]
+    async fn authenticate(
+        &self,
+        authentication: &Box<dyn Authentication>,
+    ) -> Result<Box<dyn UserDetails>, AuthenticationError> {
+        let jwt_auth = authentication
+            .downcast_ref::<JwtBearerAuthentication>()
+            .ok_or(AuthenticationError::InvalidAuthentication)?;
+        let subject = jwt_auth
+            .token
+            .subject()
+            .map(String::from)
+            .ok_or(AuthenticationError::UsernameNotFound)?;
+        let issuer = jwt_auth
+            .issuer
+            .clone()
+            .or_else(|| jwt_auth.token.issuer().map(String::from));
+        let claims = jwt_auth.token.to_json().unwrap_or(Value::Null);
+
+        Ok(Box::new(JwtPrincipal {
+            subject,
+            issuer,
+            authorities: jwt_auth.authorities.clone(),
+            claims,
+            token: jwt_auth.bearer_token.clone(),
+        }))
+    }
+}
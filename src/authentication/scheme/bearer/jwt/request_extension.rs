@@ -0,0 +1,89 @@
+//! Extension functions to inject the decoded claims and the bearer token of a JWT authentication into an endpoint.
+
+use std::fmt;
+use std::future::Future;
+use std::ops::Deref;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use actix_web::dev::{Payload, PayloadStream};
+use actix_web::{Error, FromRequest, HttpRequest};
+
+use crate::authentication::error::error_type::AuthenticationError;
+use crate::authentication::scheme::bearer::jwt::token;
+
+/// The decoded claims of the token of the current request. The type parameter must match the type
+/// of the `Claims` decoded by the token decoders (e.g. `Claims<DefaultJwt>`).
+pub struct Claims<T: token::Claims>(Arc<T>);
+
+impl<T: token::Claims> Claims<T> {
+    /// Returns the shared decoded claims.
+    pub fn into_inner(self) -> Arc<T> {
+        self.0
+    }
+}
+
+impl<T: token::Claims> Deref for Claims<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T: token::Claims> FromRequest for Claims<T> {
+    type Error = Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self, Error>>>>;
+    type Config = ();
+
+    fn from_request(req: &HttpRequest, _payload: &mut Payload<PayloadStream>) -> Self::Future {
+        let req = req.clone();
+
+        Box::pin(async move {
+            req.extensions()
+                .get::<Arc<dyn token::Claims>>()
+                .and_then(|claims| claims.clone().downcast_arc::<T>().ok())
+                .map(Claims)
+                .ok_or_else(|| AuthenticationError::UsernameNotFound.into())
+        })
+    }
+}
+
+/// The bearer token of the current request as extracted from the authorization header
+/// (e.g. to forward it to downstream services). The token is not printed in debug output.
+#[derive(Clone, PartialEq, Eq)]
+pub struct BearerToken(String);
+
+impl BearerToken {
+    pub(crate) fn new(token: String) -> BearerToken {
+        BearerToken(token)
+    }
+
+    /// Returns the token.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Debug for BearerToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("BearerToken(***)")
+    }
+}
+
+impl FromRequest for BearerToken {
+    type Error = Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self, Error>>>>;
+    type Config = ();
+
+    fn from_request(req: &HttpRequest, _payload: &mut Payload<PayloadStream>) -> Self::Future {
+        let req = req.clone();
+
+        Box::pin(async move {
+            req.extensions()
+                .get::<BearerToken>()
+                .cloned()
+                .ok_or_else(|| AuthenticationError::UsernameNotFound.into())
+        })
+    }
+}
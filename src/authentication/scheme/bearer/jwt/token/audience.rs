@@ -0,0 +1,43 @@
+//! The audience (`aud`) claim of a token can either be a single string or an array of strings.
+
+use serde::{Deserialize, Serialize};
+
+/// The recipients a token is intended for.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Audience {
+    /// A single recipient (`"aud": "api"`).
+    Single(String),
+    /// Multiple recipients (`"aud": ["api", "admin-api"]`).
+    Multiple(Vec<String>),
+}
+
+impl Audience {
+    /// Checks whether the given recipient is part of the audience.
+    pub fn contains(&self, audience: &str) -> bool {
+        match self {
+            Audience::Single(value) => value == audience,
+            Audience::Multiple(values) => values.iter().any(|value| value == audience),
+        }
+    }
+
+    /// Returns the recipients as a vector.
+    pub fn to_vec(&self) -> Vec<String> {
+        match self {
+            Audience::Single(value) => vec![value.clone()],
+            Audience::Multiple(values) => values.clone(),
+        }
+    }
+}
+
+impl From<String> for Audience {
+    fn from(audience: String) -> Self {
+        Audience::Single(audience)
+    }
+}
+
+impl From<Vec<String>> for Audience {
+    fn from(audience: Vec<String>) -> Self {
+        Audience::Multiple(audience)
+    }
+}
@@ -0,0 +1,61 @@
+//! A token decoder implementation that enforces the JWT profile for OAuth 2.0 access tokens (RFC 9068).
+use serde::Deserialize;
+
+use crate::authentication::error::error_type::AuthenticationError;
+use crate::authentication::scheme::bearer::jwt::token::decoder::{DecodedToken, TokenDecoder};
+use crate::authentication::scheme::bearer::jwt::token::Claims;
+
+/// Access token profile decoder definition. Wraps a token decoder and rejects tokens that don't have the
+/// `typ` header `at+jwt` (or `application/at+jwt`) to prevent other JWTs (e.g. ID tokens) from being
+/// accepted as access tokens.  
+/// Tokens are rejected as well if the `iss` claim doesn't match the expected issuer or if the `aud` claim
+/// doesn't contain the expected audience (the identifier of the resource server).
+#[derive(Clone)]
+pub struct AccessTokenProfileDecoder<T: for<'b> Deserialize<'b> + Claims> {
+    token_decoder: Box<dyn TokenDecoder<T>>,
+    issuer: String,
+    audience: String,
+}
+
+impl<T: for<'b> Deserialize<'b> + Claims> AccessTokenProfileDecoder<T> {
+    /// Constructs a new instance of `AccessTokenProfileDecoder` wrapping the given token decoder.
+    /// Only tokens of the given issuer with the given audience are accepted.
+    pub fn new(
+        token_decoder: Box<dyn TokenDecoder<T>>,
+        issuer: String,
+        audience: String,
+    ) -> AccessTokenProfileDecoder<T> {
+        AccessTokenProfileDecoder {
+            token_decoder,
+            issuer,
+            audience,
+        }
+    }
+}
+
+impl<T: for<'b> Deserialize<'b> + Claims + Clone> TokenDecoder<T> for AccessTokenProfileDecoder<T> {
+    fn decode_token(&self, token: &str) -> Result<Box<T>, AuthenticationError> {
+        Ok(self.decode_token_with_header(token)?.claims)
+    }
+
+    fn decode_token_with_header(
+        &self,
+        token: &str,
+    ) -> Result<DecodedToken<T>, AuthenticationError> {
+        let decoded_token = self.token_decoder.decode_token_with_header(token)?;
+        match decoded_token.header.typ.as_deref() {
+            Some(typ)
+                if typ.eq_ignore_ascii_case("at+jwt")
+                    || typ.eq_ignore_ascii_case("application/at+jwt") => {}
+            _ => return Err(AuthenticationError::InvalidToken),
+        }
+        let claims = &decoded_token.claims;
+        if claims.issuer() != Some(self.issuer.as_str()) {
+            return Err(AuthenticationError::InvalidToken);
+        }
+        match claims.audience() {
+            Some(audience) if audience.contains(&self.audience) => Ok(decoded_token),
+            _ => Err(AuthenticationError::InvalidToken),
+        }
+    }
+}
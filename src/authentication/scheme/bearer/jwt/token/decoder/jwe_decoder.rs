@@ -0,0 +1,219 @@
+//! A JWE token decoder implementation. Encrypted tokens (JWE compact serialization) are decrypted with
+//! the configured private keys and the decrypted nested JWT is delegated to signature verifying token decoders.
+//!
+//! Supported key management algorithms are `RSA-OAEP`, `RSA-OAEP-256` and `ECDH-ES` (P-256).
+//! The only supported content encryption algorithm is `A256GCM`.
+use aes_gcm::aead::{Aead, KeyInit, Payload};
+use aes_gcm::{Aes256Gcm, Nonce};
+use p256::elliptic_curve::sec1::FromEncodedPoint;
+use p256::{EncodedPoint, PublicKey, SecretKey};
+use rsa::rand_core::OsRng;
+use rsa::{Oaep, RsaPrivateKey};
+use serde::Deserialize;
+
+use crate::authentication::error::error_type::AuthenticationError;
+use crate::authentication::scheme::bearer::jwt::token::decoder::{DecodedToken, TokenDecoder};
+use crate::authentication::scheme::bearer::jwt::token::Claims;
+
+const CONTENT_ENCRYPTION_ALGORITHM: &str = "A256GCM";
+const CONTENT_ENCRYPTION_KEY_LENGTH: usize = 32;
+
+/// A private key used to decrypt the content encryption key of JWEs.
+#[derive(Clone)]
+pub enum JwePrivateKey {
+    /// RSA private key for the `RSA-OAEP` and `RSA-OAEP-256` algorithms.
+    Rsa(Box<RsaPrivateKey>),
+    /// P-256 private key for the `ECDH-ES` algorithm.
+    EcP256(SecretKey),
+}
+
+/// A private key with an optional key id. If a JWE header contains a `kid`, only the keys
+/// with the matching key id are used to decrypt the token.
+#[derive(Clone)]
+pub struct JweDecryptionKey {
+    pub key_id: Option<String>,
+    pub key: JwePrivateKey,
+}
+
+impl JweDecryptionKey {
+    /// Constructs a new decryption key for a RSA private key.
+    pub fn rsa(key_id: Option<String>, key: RsaPrivateKey) -> JweDecryptionKey {
+        JweDecryptionKey {
+            key_id,
+            key: JwePrivateKey::Rsa(Box::new(key)),
+        }
+    }
+
+    /// Constructs a new decryption key for a P-256 private key.
+    pub fn ec_p256(key_id: Option<String>, key: SecretKey) -> JweDecryptionKey {
+        JweDecryptionKey {
+            key_id,
+            key: JwePrivateKey::EcP256(key),
+        }
+    }
+
+    fn unwrap_content_encryption_key(
+        &self,
+        header: &JweHeader,
+        encrypted_key: &[u8],
+    ) -> Option<Vec<u8>> {
+        match (&self.key, header.alg.as_str()) {
+            (JwePrivateKey::Rsa(key), "RSA-OAEP") => key
+                .decrypt_blinded(&mut OsRng, Oaep::new::<sha1::Sha1>(), encrypted_key)
+                .ok(),
+            (JwePrivateKey::Rsa(key), "RSA-OAEP-256") => key
+                .decrypt_blinded(&mut OsRng, Oaep::new::<sha2::Sha256>(), encrypted_key)
+                .ok(),
+            (JwePrivateKey::EcP256(key), "ECDH-ES") if encrypted_key.is_empty() => {
+                derive_ecdh_es_key(key, header)
+            }
+            _ => None,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct JweHeader {
+    alg: String,
+    enc: String,
+    kid: Option<String>,
+    zip: Option<String>,
+    epk: Option<EphemeralPublicKey>,
+    apu: Option<String>,
+    apv: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct EphemeralPublicKey {
+    kty: String,
+    crv: String,
+    x: String,
+    y: String,
+}
+
+/// JWE decoder definition. Wraps the token decoders that verify the signature of the nested JWT.
+/// Plaintext that is not a JWT which can be verified by one of the wrapped token decoders is rejected,
+/// as encryption alone does not authenticate the sender of a token.
+#[derive(Clone)]
+pub struct JweDecoder<T: for<'b> Deserialize<'b> + Claims> {
+    decryption_keys: Vec<JweDecryptionKey>,
+    token_decoders: Vec<Box<dyn TokenDecoder<T>>>,
+}
+
+impl<T: for<'b> Deserialize<'b> + Claims> JweDecoder<T> {
+    /// Constructs a new instance of `JweDecoder` for the given decryption keys and token decoders.
+    pub fn new(
+        decryption_keys: Vec<JweDecryptionKey>,
+        token_decoders: Vec<Box<dyn TokenDecoder<T>>>,
+    ) -> JweDecoder<T> {
+        JweDecoder {
+            decryption_keys,
+            token_decoders,
+        }
+    }
+
+    fn decrypt(&self, token: &str) -> Option<Vec<u8>> {
+        let parts: Vec<&str> = token.split('.').collect();
+        if parts.len() != 5 {
+            return None;
+        }
+        let header: JweHeader = serde_json::from_slice(&decode_base64url(parts[0])?).ok()?;
+        // Compressed plaintext is not supported
+        if header.zip.is_some() || header.enc != CONTENT_ENCRYPTION_ALGORITHM {
+            return None;
+        }
+        let encrypted_key = decode_base64url(parts[1])?;
+        let iv = decode_base64url(parts[2])?;
+        let mut ciphertext = decode_base64url(parts[3])?;
+        ciphertext.extend(decode_base64url(parts[4])?);
+        if iv.len() != 12 {
+            return None;
+        }
+
+        let candidate_keys = self
+            .decryption_keys
+            .iter()
+            .filter(|decryption_key| header.kid.is_none() || decryption_key.key_id == header.kid);
+        for decryption_key in candidate_keys {
+            let content_encryption_key =
+                match decryption_key.unwrap_content_encryption_key(&header, &encrypted_key) {
+                    Some(key) if key.len() == CONTENT_ENCRYPTION_KEY_LENGTH => key,
+                    _ => continue,
+                };
+            let cipher = Aes256Gcm::new_from_slice(&content_encryption_key).ok()?;
+            let payload = Payload {
+                msg: &ciphertext,
+                aad: parts[0].as_bytes(),
+            };
+            if let Ok(plaintext) = cipher.decrypt(Nonce::from_slice(&iv), payload) {
+                return Some(plaintext);
+            }
+        }
+        None
+    }
+}
+
+impl<T: for<'b> Deserialize<'b> + Claims + Clone> TokenDecoder<T> for JweDecoder<T> {
+    fn decode_token(&self, token: &str) -> Result<Box<T>, AuthenticationError> {
+        Ok(self.decode_token_with_header(token)?.claims)
+    }
+
+    /// Returns the JOSE header of the nested JWT.
+    fn decode_token_with_header(
+        &self,
+        token: &str,
+    ) -> Result<DecodedToken<T>, AuthenticationError> {
+        let plaintext = self
+            .decrypt(token)
+            .ok_or(AuthenticationError::InvalidToken)?;
+        let nested_token =
+            String::from_utf8(plaintext).map_err(|_| AuthenticationError::InvalidToken)?;
+        for decoder in &self.token_decoders {
+            if let Ok(decoded_token) = decoder.decode_token_with_header(&nested_token) {
+                return Ok(decoded_token);
+            }
+        }
+        Err(AuthenticationError::InvalidToken)
+    }
+}
+
+fn decode_base64url(value: &str) -> Option<Vec<u8>> {
+    base64::decode_config(value, base64::URL_SAFE_NO_PAD).ok()
+}
+
+/// Derives the content encryption key with the Concat KDF as described in RFC 7518, section 4.6.
+fn derive_ecdh_es_key(key: &SecretKey, header: &JweHeader) -> Option<Vec<u8>> {
+    let epk = header.epk.as_ref()?;
+    if epk.kty != "EC" || epk.crv != "P-256" {
+        return None;
+    }
+    let x = decode_base64url(&epk.x)?;
+    let y = decode_base64url(&epk.y)?;
+    if x.len() != 32 || y.len() != 32 {
+        return None;
+    }
+    let encoded_point =
+        EncodedPoint::from_affine_coordinates(x.as_slice().into(), y.as_slice().into(), false);
+    let ephemeral_public_key: PublicKey =
+        Option::from(PublicKey::from_encoded_point(&encoded_point))?;
+    let shared_secret =
+        p256::ecdh::diffie_hellman(key.to_nonzero_scalar(), ephemeral_public_key.as_affine());
+
+    let apu = decode_base64url(header.apu.as_deref().unwrap_or_default())?;
+    let apv = decode_base64url(header.apv.as_deref().unwrap_or_default())?;
+    let mut other_info = Vec::new();
+    for value in [CONTENT_ENCRYPTION_ALGORITHM.as_bytes(), &apu, &apv] {
+        other_info.extend((value.len() as u32).to_be_bytes());
+        other_info.extend(value);
+    }
+    other_info.extend(((CONTENT_ENCRYPTION_KEY_LENGTH * 8) as u32).to_be_bytes());
+
+    let mut derived_key = vec![0u8; CONTENT_ENCRYPTION_KEY_LENGTH];
+    concat_kdf::derive_key_into::<sha2::Sha256>(
+        shared_secret.raw_secret_bytes(),
+        &other_info,
+        &mut derived_key,
+    )
+    .ok()?;
+    Some(derived_key)
+}
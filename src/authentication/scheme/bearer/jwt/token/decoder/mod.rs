@@ -1,16 +1,42 @@
-//! The decoder module provides a trait definition of `TokenDecoder` and a RSA token decoder implementation.
+//! The decoder module provides a trait definition of `TokenDecoder` and a RSA token decoder implementation.  
+//! A JWE token decoder is available with the `jwe` feature, a PASETO token decoder with the `paseto` feature.
 
+use jsonwebtoken::Header;
 use serde::Deserialize;
 
 use crate::authentication::error::error_type::AuthenticationError;
 use crate::authentication::scheme::bearer::jwt::token::Claims;
 
+pub mod access_token_profile_decoder;
+#[cfg(feature = "jwe")]
+pub mod jwe_decoder;
+#[cfg(feature = "paseto")]
+pub mod paseto_decoder;
 pub mod rsa_decoder;
 
+/// A decoded token consisting of the JOSE header and the `Claims` of the token.
+pub struct DecodedToken<T: for<'b> Deserialize<'b> + Claims> {
+    pub header: Header,
+    pub claims: Box<T>,
+}
+
 /// Token decoder claim trait definition. Decodes a string token to either a boxed instance of `Claims`
 /// or returns an error.
 pub trait TokenDecoder<T: for<'b> Deserialize<'b> + Claims>: TokenDecoderClone<T> {
     fn decode_token(&self, token: &str) -> Result<Box<T>, AuthenticationError>;
+
+    /// Decodes a string token to a `DecodedToken` with the JOSE header and the `Claims` of the token.
+    /// The default implementation reads the header of the token after it has been verified with `decode_token`.
+    /// Decoders of tokens without a JOSE header return an `InvalidToken` error.
+    fn decode_token_with_header(
+        &self,
+        token: &str,
+    ) -> Result<DecodedToken<T>, AuthenticationError> {
+        let claims = self.decode_token(token)?;
+        let header =
+            jsonwebtoken::decode_header(token).map_err(|_| AuthenticationError::InvalidToken)?;
+        Ok(DecodedToken { header, claims })
+    }
 }
 
 /// A token decoder must be cloneable, `send` and `sync`.
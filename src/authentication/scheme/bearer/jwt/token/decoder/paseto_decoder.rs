@@ -0,0 +1,170 @@
+//! A PASETO token decoder implementation for `v4.public` tokens.
+//!
+//! The registered time claims of PASETO (`exp`, `nbf`, `iat`) are ISO 8601 / RFC 3339 strings.
+//! They are validated and converted into numeric timestamps (seconds since epoch) before the claims
+//! are deserialized, so that the same `Claims` types as for JWTs (e.g. `DefaultJwt`) can be used.
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+use serde::Deserialize;
+use serde_json::{Map, Value};
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+
+use crate::authentication::error::error_type::AuthenticationError;
+use crate::authentication::scheme::bearer::jwt::token::decoder::TokenDecoder;
+use crate::authentication::scheme::bearer::jwt::token::Claims;
+
+const HEADER: &str = "v4.public.";
+const SIGNATURE_LENGTH: usize = 64;
+const TIME_CLAIMS: [&str; 3] = ["exp", "nbf", "iat"];
+
+/// An Ed25519 public key with an optional key id. If the footer of a token contains a `kid`,
+/// only the keys with the matching key id are used to verify the token.
+#[derive(Clone)]
+pub struct PasetoPublicKey {
+    pub key_id: Option<String>,
+    pub key: VerifyingKey,
+}
+
+impl PasetoPublicKey {
+    /// Constructs a new instance of `PasetoPublicKey` for the given key id and public key.
+    pub fn new(key_id: Option<String>, key: VerifyingKey) -> PasetoPublicKey {
+        PasetoPublicKey { key_id, key }
+    }
+}
+
+/// PASETO `v4.public` decoder definition.
+#[derive(Clone)]
+pub struct PasetoDecoder {
+    public_keys: Vec<PasetoPublicKey>,
+    implicit_assertion: Vec<u8>,
+    leeway: u64,
+}
+
+impl PasetoDecoder {
+    /// Constructs a new instance of `PasetoDecoder` for the given public keys.
+    /// No implicit assertion and no leeway is used by default.
+    pub fn new(public_keys: Vec<PasetoPublicKey>) -> PasetoDecoder {
+        PasetoDecoder {
+            public_keys,
+            implicit_assertion: Vec::new(),
+            leeway: 0,
+        }
+    }
+
+    /// Sets the implicit assertion that must have been used to sign the tokens.
+    pub fn with_implicit_assertion(mut self, implicit_assertion: Vec<u8>) -> PasetoDecoder {
+        self.implicit_assertion = implicit_assertion;
+        self
+    }
+
+    /// Sets the leeway (in seconds) used to validate the `exp` and `nbf` claims.
+    pub fn with_leeway(mut self, leeway: u64) -> PasetoDecoder {
+        self.leeway = leeway;
+        self
+    }
+
+    /// Verifies the signature of the token and returns the signed message.
+    fn verify(&self, token: &str) -> Option<Vec<u8>> {
+        let body = token.strip_prefix(HEADER)?;
+        let mut parts = body.splitn(2, '.');
+        let signed_message = decode_base64url(parts.next()?)?;
+        let footer = match parts.next() {
+            Some(footer) => decode_base64url(footer)?,
+            None => Vec::new(),
+        };
+        if signed_message.len() < SIGNATURE_LENGTH {
+            return None;
+        }
+        let (message, signature) = signed_message.split_at(signed_message.len() - SIGNATURE_LENGTH);
+        let signature = Signature::from_slice(signature).ok()?;
+        let pre_authentication_encoding = pre_authentication_encoding(&[
+            HEADER.as_bytes(),
+            message,
+            &footer,
+            &self.implicit_assertion,
+        ]);
+
+        let key_id = footer_key_id(&footer);
+        self.public_keys
+            .iter()
+            .filter(|public_key| key_id.is_none() || public_key.key_id == key_id)
+            .find(|public_key| {
+                public_key
+                    .key
+                    .verify(&pre_authentication_encoding, &signature)
+                    .is_ok()
+            })
+            .map(|_| message.to_vec())
+    }
+
+    /// Validates the registered time claims and converts them into numeric timestamps.
+    fn validate_time_claims(&self, claims: &mut Map<String, Value>) -> Option<()> {
+        for claim in TIME_CLAIMS.iter() {
+            if let Some(value) = claims.get(*claim) {
+                let timestamp = OffsetDateTime::parse(value.as_str()?, &Rfc3339)
+                    .ok()?
+                    .unix_timestamp();
+                if timestamp < 0 {
+                    return None;
+                }
+                claims.insert(claim.to_string(), Value::from(timestamp as u64));
+            }
+        }
+
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).ok()?.as_secs();
+        let expiration = claims.get("exp")?.as_u64()?;
+        if expiration.saturating_add(self.leeway) <= now {
+            return None;
+        }
+        if let Some(not_before) = claims.get("nbf").and_then(Value::as_u64) {
+            if not_before > now.saturating_add(self.leeway) {
+                return None;
+            }
+        }
+        Some(())
+    }
+}
+
+impl<T: for<'b> Deserialize<'b> + Claims> TokenDecoder<T> for PasetoDecoder {
+    fn decode_token(&self, token: &str) -> Result<Box<T>, AuthenticationError> {
+        let message = self
+            .verify(token)
+            .ok_or(AuthenticationError::InvalidToken)?;
+        let mut claims = match serde_json::from_slice::<Value>(&message) {
+            Ok(Value::Object(claims)) => claims,
+            _ => return Err(AuthenticationError::InvalidToken),
+        };
+        self.validate_time_claims(&mut claims)
+            .ok_or(AuthenticationError::InvalidToken)?;
+        match serde_json::from_value::<T>(Value::Object(claims)) {
+            Ok(decoded_token) => Ok(Box::new(decoded_token)),
+            Err(_) => Err(AuthenticationError::InvalidToken),
+        }
+    }
+}
+
+/// Pre-Authentication Encoding (PAE) as defined in the PASETO specification.
+fn pre_authentication_encoding(pieces: &[&[u8]]) -> Vec<u8> {
+    let mut output = (pieces.len() as u64).to_le_bytes().to_vec();
+    for piece in pieces {
+        // The most significant bit must be cleared for interoperability
+        output.extend(((piece.len() as u64) & (u64::MAX >> 1)).to_le_bytes());
+        output.extend(*piece);
+    }
+    output
+}
+
+/// Reads the key id from a footer. Footers that are not a JSON object are ignored.
+fn footer_key_id(footer: &[u8]) -> Option<String> {
+    serde_json::from_slice::<Value>(footer)
+        .ok()?
+        .get("kid")?
+        .as_str()
+        .map(String::from)
+}
+
+fn decode_base64url(value: &str) -> Option<Vec<u8>> {
+    base64::decode_config(value, base64::URL_SAFE_NO_PAD).ok()
+}
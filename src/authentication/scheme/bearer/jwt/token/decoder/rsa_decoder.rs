@@ -3,7 +3,7 @@ use jsonwebtoken::{decode, Algorithm, DecodingKey, Validation};
 use serde::Deserialize;
 
 use crate::authentication::error::error_type::AuthenticationError;
-use crate::authentication::scheme::bearer::jwt::token::decoder::TokenDecoder;
+use crate::authentication::scheme::bearer::jwt::token::decoder::{DecodedToken, TokenDecoder};
 use crate::authentication::scheme::bearer::jwt::token::Claims;
 
 /// RSA key component trait definition. Stores the n- and e-component of the RSA key.
@@ -15,13 +15,23 @@ pub trait RsaKeyComponents {
 /// RSA JWT decoder trait definition.
 #[derive(Clone)]
 pub struct RsaJwtDecoder {
-    algorithm: Algorithm,
+    validation: Validation,
     decoding_keys: Vec<DecodingKey<'static>>,
 }
 
 impl RsaJwtDecoder {
     /// Constructs a new instance of `RsaJwtDecoder` for the given algorithm and keys.
     pub fn new(algorithm: Algorithm, rsa_keys: Vec<Box<dyn RsaKeyComponents>>) -> RsaJwtDecoder {
+        RsaJwtDecoder::with_validation(Validation::new(algorithm), rsa_keys)
+    }
+
+    /// Constructs a new instance of `RsaJwtDecoder` for the given validation rules and keys.
+    /// The validation rules (e.g. expected issuer, audience, leeway and algorithms) are applied
+    /// to all tokens decoded with this decoder.
+    pub fn with_validation(
+        validation: Validation,
+        rsa_keys: Vec<Box<dyn RsaKeyComponents>>,
+    ) -> RsaJwtDecoder {
         let mut decoding_keys: Vec<DecodingKey<'static>> = Vec::new();
 
         for rsa_key in &rsa_keys {
@@ -34,7 +44,7 @@ impl RsaJwtDecoder {
         }
 
         RsaJwtDecoder {
-            algorithm,
+            validation,
             decoding_keys,
         }
     }
@@ -42,10 +52,20 @@ impl RsaJwtDecoder {
 
 impl<T: for<'b> Deserialize<'b> + Claims> TokenDecoder<T> for RsaJwtDecoder {
     fn decode_token(&self, token: &str) -> Result<Box<T>, AuthenticationError> {
+        Ok(self.decode_token_with_header(token)?.claims)
+    }
+
+    fn decode_token_with_header(
+        &self,
+        token: &str,
+    ) -> Result<DecodedToken<T>, AuthenticationError> {
         for key in &self.decoding_keys {
-            let result = decode::<T>(token, key, &Validation::new(self.algorithm));
+            let result = decode::<T>(token, key, &self.validation);
             if let Ok(decoded_token) = result {
-                return Ok(Box::new(decoded_token.claims));
+                return Ok(DecodedToken {
+                    header: decoded_token.header,
+                    claims: Box::new(decoded_token.claims),
+                });
             }
         }
         Err(AuthenticationError::InvalidToken)
@@ -1,10 +1,120 @@
 //! The token module provides a trait definition of `Claims`, `TokenDecoder` and a RSA token decoder implementation.
 
 use downcast_rs::impl_downcast;
-use downcast_rs::Downcast;
+use downcast_rs::DowncastSync;
+use serde_json::Value;
 
+use crate::authentication::scheme::bearer::jwt::token::audience::Audience;
+use crate::authentication::scheme::bearer::jwt::token::scopes::Scopes;
+
+pub mod audience;
 pub mod decoder;
+pub mod scopes;
+
+/// Derive macro of the `Claims` trait (available with the `derive` feature).
+#[cfg(feature = "derive")]
+pub use actix_web_security_derive::Claims;
 
 /// Trait definition of claims to decode from a token.
-pub trait Claims: Downcast + Sync + Send {}
-impl_downcast!(Claims);
+/// The accessors of the registered claims (RFC 7519) return `None` by default.
+pub trait Claims: DowncastSync {
+    /// Returns the issuer (`iss` claim).
+    fn issuer(&self) -> Option<&str> {
+        None
+    }
+
+    /// Returns the subject (`sub` claim).
+    fn subject(&self) -> Option<&str> {
+        None
+    }
+
+    /// Returns the audience (`aud` claim).
+    fn audience(&self) -> Option<Audience> {
+        None
+    }
+
+    /// Returns the expiration time (`exp` claim).
+    fn expiration_time(&self) -> Option<usize> {
+        None
+    }
+
+    /// Returns the time before which the token must not be accepted (`nbf` claim).
+    fn not_before(&self) -> Option<usize> {
+        None
+    }
+
+    /// Returns the time at which the token was issued (`iat` claim).
+    fn issued_at(&self) -> Option<usize> {
+        None
+    }
+
+    /// Returns the unique identifier of the token (`jti` claim).
+    fn jwt_id(&self) -> Option<&str> {
+        None
+    }
+
+    /// Returns the value of a claim by name (e.g. of a private claim). Claims with a `null` value are not returned.
+    /// The default implementation reads the claim from `to_json`.
+    fn get(&self, name: &str) -> Option<Value> {
+        self.to_json()?
+            .get(name)
+            .filter(|value| !value.is_null())
+            .cloned()
+    }
+
+    /// Returns the scopes granted to the token. No scopes are granted by default.
+    fn scopes(&self) -> Scopes {
+        Scopes::default()
+    }
+
+    /// Returns the claims as JSON object (e.g. to map authorities from the claims).
+    /// Returns `None` by default.
+    fn to_json(&self) -> Option<Value> {
+        None
+    }
+}
+impl_downcast!(sync Claims);
+
+/// Returns **true** if all characters of the segment are base64url characters (without padding).
+fn is_base64url(segment: &str) -> bool {
+    segment
+        .bytes()
+        .all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_')
+}
+
+/// Checks whether the given token is a JWS in compact serialization. A JWS consists of three
+/// base64url encoded segments separated by dots, whereas the first segment must be a parsable JOSE header.
+/// Returns **true** if the token is a JWS, **false** otherwise (e.g. for opaque tokens).
+pub fn is_jws(token: &str) -> bool {
+    let segments: Vec<&str> = token.split('.').collect();
+    if segments.len() != 3 {
+        return false;
+    }
+    if segments[0].is_empty() || !segments.iter().all(|segment| is_base64url(segment)) {
+        return false;
+    }
+    jsonwebtoken::decode_header(token).is_ok()
+}
+
+/// Checks whether the given token is a JWE in compact serialization. A JWE consists of five
+/// base64url encoded segments separated by dots, whereas the first segment must be a JOSE header
+/// with the content encryption algorithm (`enc`).
+/// Returns **true** if the token is a JWE, **false** otherwise (e.g. for opaque tokens).
+pub fn is_jwe(token: &str) -> bool {
+    let segments: Vec<&str> = token.split('.').collect();
+    if segments.len() != 5 || !segments.iter().all(|segment| is_base64url(segment)) {
+        return false;
+    }
+    base64::decode_config(segments[0], base64::URL_SAFE_NO_PAD)
+        .ok()
+        .and_then(|header| serde_json::from_slice::<Value>(&header).ok())
+        .is_some_and(|header| header.get("enc").is_some())
+}
+
+/// Decodes the payload of a JWS in compact serialization **without** verifying the signature.
+/// Values read from the payload must not be trusted before the token has been verified by a `TokenDecoder`.
+pub(crate) fn decode_unverified_payload(token: &str) -> Option<Value> {
+    let payload = token.split('.').nth(1)?;
+    let decoded = base64::decode_config(payload, base64::URL_SAFE_NO_PAD).ok()?;
+    serde_json::from_slice(&decoded).ok()
+}
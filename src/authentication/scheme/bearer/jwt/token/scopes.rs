@@ -0,0 +1,79 @@
+//! Scopes granted to a token. Scopes are either encoded as space-delimited string
+//! (`"scope": "orders:read orders:write"`, RFC 8693 / RFC 9068) or as array of strings
+//! (`"scp": ["orders:read", "orders:write"]`).
+
+use std::collections::HashSet;
+use std::iter::FromIterator;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// A set of scopes.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Scopes(HashSet<String>);
+
+impl Scopes {
+    /// Constructs a new instance for the given scopes.
+    pub fn new(scopes: HashSet<String>) -> Scopes {
+        Scopes(scopes)
+    }
+
+    /// Parses a space-delimited string of scopes.
+    pub fn from_delimited(scopes: &str) -> Scopes {
+        Scopes(scopes.split_whitespace().map(String::from).collect())
+    }
+
+    /// Checks whether the given scope has been granted.
+    pub fn contains(&self, scope: &str) -> bool {
+        self.0.contains(scope)
+    }
+
+    /// Checks whether all of the given scopes have been granted.
+    pub fn contains_all<S: AsRef<str>>(&self, scopes: &[S]) -> bool {
+        scopes.iter().all(|scope| self.contains(scope.as_ref()))
+    }
+
+    /// Returns **true** if no scope has been granted.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns an iterator over the granted scopes.
+    pub fn iter(&self) -> impl Iterator<Item = &String> {
+        self.0.iter()
+    }
+
+    /// Returns the scopes as sorted, space-delimited string.
+    pub fn to_delimited(&self) -> String {
+        let mut scopes: Vec<&str> = self.0.iter().map(String::as_str).collect();
+        scopes.sort_unstable();
+        scopes.join(" ")
+    }
+}
+
+impl FromIterator<String> for Scopes {
+    fn from_iter<I: IntoIterator<Item = String>>(scopes: I) -> Self {
+        Scopes(scopes.into_iter().collect())
+    }
+}
+
+impl Serialize for Scopes {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_delimited())
+    }
+}
+
+impl<'de> Deserialize<'de> for Scopes {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum EncodedScopes {
+            Delimited(String),
+            List(Vec<String>),
+        }
+
+        Ok(match EncodedScopes::deserialize(deserializer)? {
+            EncodedScopes::Delimited(scopes) => Scopes::from_delimited(&scopes),
+            EncodedScopes::List(scopes) => scopes.into_iter().collect(),
+        })
+    }
+}
@@ -1,7 +1,12 @@
 //! The bearer scheme module offers an implementation of JWT based OAuth2 authentication header extractor,
 //! authentication provider and user detail service.  
 //! Additionally a JWK module is provided to load JWKs for token verification from the local file system
-//! or dynamically from a HTTP-Endpoint.
+//! or dynamically from a HTTP-Endpoint.  
+//! Opaque tokens can be validated with a token introspector. The `HybridBearerAuthenticationExtractor`
+//! accepts both, JWTs and opaque tokens.  
+//! Multi-tenant resource servers can trust multiple issuers with an `IssuerRegistry`.
 
+pub mod introspection;
+pub mod issuer;
 pub mod jwk;
 pub mod jwt;
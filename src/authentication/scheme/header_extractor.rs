@@ -2,6 +2,7 @@
 
 use actix_web::http::{HeaderMap, HeaderValue};
 use async_trait::async_trait;
+use zeroize::Zeroizing;
 
 use crate::authentication::error::error_type::AuthenticationError;
 use crate::authentication::scheme::authentication::Authentication;
@@ -17,29 +18,28 @@ pub trait AuthorizationHeaderExtractor: Send + Sync {
 }
 
 /// Utility function to extract the actual token from the header for a given authentication scheme (basic/bearer).
-/// Returns either a `String` with the extracted token (without the scheme prefix from the header) or an `AuthenticationError`.
+/// The scheme is matched case-insensitively (RFC 7235) and surrounding whitespace is ignored.
+/// Returns either the extracted token (without the scheme prefix from the header) or an `AuthenticationError`.
+/// The token is zeroized on drop as it may contain credentials (e.g. the base64 encoded basic credentials).
 pub fn extract_auth_header(
     header: &HeaderValue,
     auth_scheme: &str,
     header_length: usize,
-) -> Result<String, AuthenticationError> {
+) -> Result<Zeroizing<String>, AuthenticationError> {
     if header.len() < header_length {
         return Err(AuthenticationError::InvalidAuthorizationHeader);
     }
 
     // Split header into scheme (Basic/Bearer) and the actual token
-    let token: &str;
-    if let Ok(header_str) = header.to_str() {
-        let mut parts = header_str.splitn(2, ' ');
-        match parts.next() {
-            Some(scheme) if scheme == auth_scheme => (),
-            _ => return Err(AuthenticationError::InvalidAuthorizationHeader),
-        }
-        token = parts
-            .next()
-            .ok_or(AuthenticationError::InvalidAuthorizationHeader)?
-    } else {
+    let header_str = header
+        .to_str()
+        .map_err(|_| AuthenticationError::InvalidAuthorizationHeader)?
+        .trim();
+    let (scheme, token) = header_str
+        .split_once([' ', '\t'])
+        .ok_or(AuthenticationError::InvalidAuthorizationHeader)?;
+    if !scheme.eq_ignore_ascii_case(auth_scheme) {
         return Err(AuthenticationError::InvalidAuthorizationHeader);
     }
-    Ok(token.to_string())
+    Ok(Zeroizing::new(token.trim_start().to_string()))
 }
@@ -0,0 +1,237 @@
+//! Authorities granted to an authenticated principal. Authorities (e.g. roles or groups) are mapped from the
+//! claims of a token with an `AuthorityMapper`. The `ClaimAuthorityMapper` reads the authorities from
+//! configurable claims (JSON pointers) and offers presets for common identity provider token layouts.
+//! The `Authorities` are attached to the request context by the `HttpAuthenticationMiddleware` and can be
+//! injected into endpoints.
+
+use std::collections::HashSet;
+use std::future::Future;
+use std::iter::FromIterator;
+use std::pin::Pin;
+
+use actix_web::dev::{Payload, PayloadStream};
+use actix_web::{Error, FromRequest, HttpRequest};
+use serde::{Serialize, Serializer};
+use serde_json::Value;
+
+use crate::authentication::error::error_type::AuthenticationError;
+
+/// A set of authorities.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Authorities(HashSet<String>);
+
+impl Authorities {
+    /// Constructs a new instance for the given authorities.
+    pub fn new(authorities: HashSet<String>) -> Authorities {
+        Authorities(authorities)
+    }
+
+    /// Checks whether the given authority has been granted.
+    pub fn contains(&self, authority: &str) -> bool {
+        self.0.contains(authority)
+    }
+
+    /// Checks whether at least one of the given authorities has been granted.
+    pub fn contains_any<S: AsRef<str>>(&self, authorities: &[S]) -> bool {
+        authorities
+            .iter()
+            .any(|authority| self.contains(authority.as_ref()))
+    }
+
+    /// Checks whether all of the given authorities have been granted.
+    pub fn contains_all<S: AsRef<str>>(&self, authorities: &[S]) -> bool {
+        authorities
+            .iter()
+            .all(|authority| self.contains(authority.as_ref()))
+    }
+
+    /// Returns **true** if no authority has been granted.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns an iterator over the granted authorities.
+    pub fn iter(&self) -> impl Iterator<Item = &String> {
+        self.0.iter()
+    }
+}
+
+impl FromIterator<String> for Authorities {
+    fn from_iter<I: IntoIterator<Item = String>>(authorities: I) -> Self {
+        Authorities(authorities.into_iter().collect())
+    }
+}
+
+impl Serialize for Authorities {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut authorities: Vec<&String> = self.0.iter().collect();
+        authorities.sort_unstable();
+        authorities.serialize(serializer)
+    }
+}
+
+impl FromRequest for Authorities {
+    type Error = Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self, Error>>>>;
+    type Config = ();
+
+    fn from_request(req: &HttpRequest, _payload: &mut Payload<PayloadStream>) -> Self::Future {
+        let req = req.clone();
+
+        Box::pin(async move {
+            req.extensions()
+                .get::<Authorities>()
+                .cloned()
+                .ok_or_else(|| AuthenticationError::UsernameNotFound.into())
+        })
+    }
+}
+
+/// Trait definition of a mapper that maps the claims of a token (as JSON) to `Authorities`.
+pub trait AuthorityMapper: AuthorityMapperClone {
+    fn map_authorities(&self, claims: &Value) -> Authorities;
+}
+
+/// An authority mapper must be cloneable, `send` and `sync`.
+/// Therefore it has to implement the `AuthorityMapperClone` trait to be cloneable as a boxed object.
+pub trait AuthorityMapperClone: Send + Sync {
+    fn clone_box(&self) -> Box<dyn AuthorityMapper>;
+}
+
+impl<U> AuthorityMapperClone for U
+where
+    U: 'static + AuthorityMapper + Clone,
+{
+    fn clone_box(&self) -> Box<dyn AuthorityMapper> {
+        Box::new(self.clone())
+    }
+}
+
+impl Clone for Box<dyn AuthorityMapper> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
+/// Normalisation of the case of mapped authorities.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CaseNormalization {
+    /// The authorities are kept as they are.
+    None,
+    /// The authorities are converted to lowercase.
+    Lowercase,
+    /// The authorities are converted to uppercase.
+    Uppercase,
+}
+
+impl CaseNormalization {
+    pub(crate) fn apply(&self, authority: &str) -> String {
+        match self {
+            CaseNormalization::None => authority.to_string(),
+            CaseNormalization::Lowercase => authority.to_lowercase(),
+            CaseNormalization::Uppercase => authority.to_uppercase(),
+        }
+    }
+}
+
+#[derive(Clone)]
+struct ClaimPath {
+    pointer: String,
+    prefix: String,
+}
+
+/// An `AuthorityMapper` that reads authorities from one or more claims addressed by JSON pointers (RFC 6901).
+/// The claim values can either be an array of strings or a space-delimited string.
+/// A prefix (e.g. `ROLE_`) can be added per claim and the case of the authorities can be normalised
+/// (the prefix is not normalised).
+#[derive(Clone)]
+pub struct ClaimAuthorityMapper {
+    claim_paths: Vec<ClaimPath>,
+    case_normalization: CaseNormalization,
+}
+
+impl ClaimAuthorityMapper {
+    /// Constructs a new instance without any claims to read authorities from.
+    pub fn new() -> ClaimAuthorityMapper {
+        ClaimAuthorityMapper {
+            claim_paths: Vec::new(),
+            case_normalization: CaseNormalization::None,
+        }
+    }
+
+    /// Constructs a new instance reading the realm roles (`realm_access.roles`) of Keycloak tokens
+    /// and the client roles (`resource_access.<client_id>.roles`) of the given client.
+    pub fn keycloak(client_id: Option<&str>) -> ClaimAuthorityMapper {
+        let mapper = ClaimAuthorityMapper::new().with_pointer("/realm_access/roles", "");
+        match client_id {
+            Some(client_id) => mapper.with_pointer(
+                &format!("/resource_access/{}/roles", escape_pointer_token(client_id)),
+                "",
+            ),
+            None => mapper,
+        }
+    }
+
+    /// Constructs a new instance reading the `roles` and `groups` claims of Azure AD tokens.
+    pub fn azure_ad() -> ClaimAuthorityMapper {
+        ClaimAuthorityMapper::new()
+            .with_claim("roles", "")
+            .with_claim("groups", "")
+    }
+
+    /// Adds a claim addressed by a JSON pointer (e.g. `/realm_access/roles`) and the prefix to add to its values.
+    pub fn with_pointer(mut self, pointer: &str, prefix: &str) -> ClaimAuthorityMapper {
+        self.claim_paths.push(ClaimPath {
+            pointer: pointer.to_string(),
+            prefix: prefix.to_string(),
+        });
+        self
+    }
+
+    /// Adds a top-level claim and the prefix to add to its values. The claim name is used as it is,
+    /// so that namespaced claims (e.g. `https://example.com/roles` used by Auth0) can be added without escaping.
+    pub fn with_claim(self, claim_name: &str, prefix: &str) -> ClaimAuthorityMapper {
+        let pointer = format!("/{}", escape_pointer_token(claim_name));
+        self.with_pointer(&pointer, prefix)
+    }
+
+    /// Sets the case normalisation of the mapped authorities.
+    pub fn with_case_normalization(
+        mut self,
+        case_normalization: CaseNormalization,
+    ) -> ClaimAuthorityMapper {
+        self.case_normalization = case_normalization;
+        self
+    }
+
+    fn normalize(&self, authority: &str) -> String {
+        self.case_normalization.apply(authority)
+    }
+}
+
+impl Default for ClaimAuthorityMapper {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AuthorityMapper for ClaimAuthorityMapper {
+    fn map_authorities(&self, claims: &Value) -> Authorities {
+        let mut authorities = HashSet::new();
+        for claim_path in &self.claim_paths {
+            let values: Vec<&str> = match claims.pointer(&claim_path.pointer) {
+                Some(Value::String(value)) => value.split_whitespace().collect(),
+                Some(Value::Array(values)) => values.iter().filter_map(Value::as_str).collect(),
+                _ => Vec::new(),
+            };
+            for value in values {
+                authorities.insert(format!("{}{}", claim_path.prefix, self.normalize(value)));
+            }
+        }
+        Authorities(authorities)
+    }
+}
+
+fn escape_pointer_token(token: &str) -> String {
+    token.replace('~', "~0").replace('/', "~1")
+}
@@ -0,0 +1,5 @@
+//! The authorization module provides functionality to restrict access to endpoints for authenticated requests.
+//! It requires the `HttpAuthenticationMiddleware` to be registered to authenticate the requests.
+
+pub mod authority;
+pub mod scope;
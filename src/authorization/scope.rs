@@ -0,0 +1,153 @@
+//! Scope based authorization. The `Scopes` granted to a token are attached to the request context
+//! by the `HttpAuthenticationMiddleware` and can be injected into endpoints or checked with the
+//! `ScopeRequirement` middleware on a route / scope level.
+
+use std::future::{self, Future, Ready};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use actix_web::dev::{
+    HttpResponseBuilder, Payload, PayloadStream, Service, ServiceRequest, ServiceResponse,
+    Transform,
+};
+use actix_web::http::{header, StatusCode};
+use actix_web::{error, Error, FromRequest, HttpMessage, HttpRequest, HttpResponse};
+use derive_more::{Display, Error};
+
+use crate::authentication::error::error_handler::auth_error_content_type;
+use crate::authentication::error::error_type::AuthenticationError;
+use crate::authentication::scheme::bearer::jwt::token::scopes::Scopes;
+
+/// Error returned if the token has not been granted all required scopes.
+/// The error response has the status code `403` and a `WWW-Authenticate` challenge
+/// with the error `insufficient_scope` and the required scopes (RFC 6750).
+#[derive(Debug, Display, Error, PartialEq, Eq, Clone)]
+#[display(fmt = "Insufficient scope")]
+pub struct InsufficientScopeError {
+    #[error(not(source))]
+    required_scopes: Scopes,
+}
+
+impl InsufficientScopeError {
+    /// Constructs a new instance for the scopes required to access the resource.
+    pub fn new(required_scopes: Scopes) -> InsufficientScopeError {
+        InsufficientScopeError { required_scopes }
+    }
+
+    /// Returns the scopes required to access the resource.
+    pub fn required_scopes(&self) -> &Scopes {
+        &self.required_scopes
+    }
+}
+
+impl error::ResponseError for InsufficientScopeError {
+    fn status_code(&self) -> StatusCode {
+        StatusCode::FORBIDDEN
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        HttpResponseBuilder::new(self.status_code())
+            .set_header(header::CONTENT_TYPE, auth_error_content_type())
+            .set_header(
+                header::WWW_AUTHENTICATE,
+                format!(
+                    "Bearer error=\"insufficient_scope\", scope=\"{}\"",
+                    self.required_scopes.to_delimited()
+                ),
+            )
+            .body("insufficient scope")
+    }
+}
+
+impl FromRequest for Scopes {
+    type Error = Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self, Error>>>>;
+    type Config = ();
+
+    fn from_request(req: &HttpRequest, _payload: &mut Payload<PayloadStream>) -> Self::Future {
+        let req = req.clone();
+
+        Box::pin(async move {
+            req.extensions()
+                .get::<Scopes>()
+                .cloned()
+                .ok_or_else(|| AuthenticationError::UsernameNotFound.into())
+        })
+    }
+}
+
+/// An actix middleware that rejects requests with an `InsufficientScopeError` if the token
+/// has not been granted all of the required scopes.
+/// The middleware must be registered "inside" of the `HttpAuthenticationMiddleware`, e.g. with
+/// `web::resource("/orders").wrap(ScopeRequirement::new(vec!["orders:write"]))`.
+#[derive(Clone)]
+pub struct ScopeRequirement {
+    required_scopes: Scopes,
+}
+
+impl ScopeRequirement {
+    /// Constructs a new instance requiring all of the given scopes.
+    pub fn new(required_scopes: Vec<&str>) -> ScopeRequirement {
+        ScopeRequirement {
+            required_scopes: required_scopes.into_iter().map(String::from).collect(),
+        }
+    }
+}
+
+impl<S, B> Transform<S> for ScopeRequirement
+where
+    S: Service<Request = ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+{
+    type Request = ServiceRequest;
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Transform = ScopeRequirementService<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        future::ready(Ok(ScopeRequirementService {
+            service,
+            required_scopes: self.required_scopes.clone(),
+        }))
+    }
+}
+
+/// The `ScopeRequirementService` checks the granted scopes of the request.
+pub struct ScopeRequirementService<S> {
+    service: S,
+    required_scopes: Scopes,
+}
+
+impl<S, B> Service for ScopeRequirementService<S>
+where
+    S: Service<Request = ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+{
+    type Request = ServiceRequest;
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Error>>>>;
+
+    fn poll_ready(&mut self, cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+        self.service.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Self::Request) -> Self::Future {
+        let granted = match req.extensions().get::<Scopes>() {
+            Some(scopes) => self
+                .required_scopes
+                .iter()
+                .all(|scope| scopes.contains(scope)),
+            None => false,
+        };
+
+        if granted {
+            Box::pin(self.service.call(req))
+        } else {
+            let error = InsufficientScopeError::new(self.required_scopes.clone());
+            Box::pin(async move { Err(error.into()) })
+        }
+    }
+}
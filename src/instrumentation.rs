@@ -0,0 +1,239 @@
+//! Internal instrumentation points of the authentication pipeline. The `tracing` feature emits spans and events,
+//! the `metrics` feature records counters and histograms with the `metrics` facade.
+//! Tokens, passwords and other secrets are never recorded.
+
+use std::future::Future;
+#[cfg(feature = "metrics")]
+use std::time::Instant;
+
+use actix_web::dev::ServiceRequest;
+#[cfg(feature = "tracing")]
+use tracing::Instrument;
+
+use crate::authentication::error::error_type::AuthenticationError;
+#[cfg(feature = "jwk-loader")]
+use crate::authentication::error::error_type::JwkLoaderError;
+use crate::authentication::event::AuthenticationOutcome;
+
+/// Counter of the authentication decisions with the labels `scheme`, `outcome` and `error`.
+#[cfg(feature = "metrics")]
+const AUTHENTICATIONS_TOTAL: &str = "actix_web_security_authentications_total";
+
+/// Histogram of the token decoding durations (in seconds) with the labels `decoder` and `result`.
+/// Token introspections are recorded with the `decoder` label `introspector`.
+#[cfg(feature = "metrics")]
+const TOKEN_DECODE_DURATION_SECONDS: &str = "actix_web_security_token_decode_duration_seconds";
+
+/// Histogram of the authentication provider durations (in seconds) with the labels `provider` and `result`.
+#[cfg(feature = "metrics")]
+const PROVIDER_DURATION_SECONDS: &str = "actix_web_security_provider_duration_seconds";
+
+/// Counter of the JWKS downloads with the labels `result` and `error`.
+#[cfg(all(feature = "metrics", feature = "jwk-loader"))]
+const JWKS_REFRESHES_TOTAL: &str = "actix_web_security_jwks_refreshes_total";
+
+/// The `authentication` span of a request with the method, path and (once authenticated) the principal of the
+/// request.
+pub(crate) struct RequestSpan {
+    #[cfg(feature = "tracing")]
+    span: tracing::Span,
+}
+
+impl RequestSpan {
+    pub(crate) fn new(_req: &ServiceRequest) -> RequestSpan {
+        RequestSpan {
+            #[cfg(feature = "tracing")]
+            span: tracing::info_span!(
+                "authentication",
+                method = %_req.method(),
+                path = %_req.path(),
+                principal = tracing::field::Empty,
+            ),
+        }
+    }
+
+    /// Executes the authentication of the request within the span.
+    #[cfg(feature = "tracing")]
+    pub(crate) fn instrument<F: Future>(self, future: F) -> impl Future<Output = F::Output> {
+        future.instrument(self.span)
+    }
+
+    #[cfg(not(feature = "tracing"))]
+    pub(crate) fn instrument<F: Future>(self, future: F) -> impl Future<Output = F::Output> {
+        future
+    }
+}
+
+/// Records the principal (username or token subject) of a successful authentication on the current
+/// `authentication` span.
+pub(crate) fn record_principal(_principal: &str) {
+    #[cfg(feature = "tracing")]
+    tracing::Span::current().record("principal", _principal);
+}
+
+/// Records the result of the authorization header extraction.
+pub(crate) fn header_extracted<T>(_result: &Result<T, AuthenticationError>) {
+    #[cfg(feature = "tracing")]
+    match _result {
+        Ok(_) => tracing::debug!("authorization header extracted"),
+        Err(error) => tracing::debug!(error = ?error, "authorization header extraction failed"),
+    }
+}
+
+/// Executes a token decoder within a `token_decoder` span and records the result.
+pub(crate) fn decode_token<T, D>(_decoder: usize, decode: D) -> Result<T, AuthenticationError>
+where
+    D: FnOnce() -> Result<T, AuthenticationError>,
+{
+    #[cfg(feature = "tracing")]
+    let _span = tracing::debug_span!("token_decoder", decoder = _decoder).entered();
+    #[cfg(feature = "metrics")]
+    let started = Instant::now();
+    let result = decode();
+    #[cfg(feature = "metrics")]
+    metrics::histogram!(
+        TOKEN_DECODE_DURATION_SECONDS,
+        "decoder" => _decoder.to_string(),
+        "result" => if result.is_ok() { "success" } else { "failure" },
+    )
+    .record(started.elapsed().as_secs_f64());
+    #[cfg(feature = "tracing")]
+    match &result {
+        Ok(_) => tracing::debug!("token decoded"),
+        Err(error) => tracing::debug!(error = ?error, "token decoder rejected the token"),
+    }
+    result
+}
+
+/// Executes a token introspection within a `token_introspector` span and records the result.
+pub(crate) async fn introspect_token<T, F>(introspection: F) -> Result<T, AuthenticationError>
+where
+    F: Future<Output = Result<T, AuthenticationError>>,
+{
+    #[cfg(feature = "tracing")]
+    let introspection = introspection.instrument(tracing::debug_span!("token_introspector"));
+    #[cfg(feature = "metrics")]
+    let started = Instant::now();
+    let result = introspection.await;
+    #[cfg(feature = "metrics")]
+    metrics::histogram!(
+        TOKEN_DECODE_DURATION_SECONDS,
+        "decoder" => "introspector",
+        "result" => if result.is_ok() { "success" } else { "failure" },
+    )
+    .record(started.elapsed().as_secs_f64());
+    #[cfg(feature = "tracing")]
+    match &result {
+        Ok(_) => tracing::debug!("token introspected"),
+        Err(error) => tracing::debug!(error = ?error, "token introspector rejected the token"),
+    }
+    result
+}
+
+/// Executes an authentication provider within an `authentication_provider` span and records the result.
+pub(crate) async fn authenticate_with_provider<T, F>(
+    _provider: usize,
+    authentication: F,
+) -> Result<T, AuthenticationError>
+where
+    F: Future<Output = Result<T, AuthenticationError>>,
+{
+    #[cfg(feature = "tracing")]
+    let authentication = authentication.instrument(tracing::debug_span!(
+        "authentication_provider",
+        provider = _provider
+    ));
+    #[cfg(feature = "metrics")]
+    let started = Instant::now();
+    let result = authentication.await;
+    #[cfg(feature = "metrics")]
+    metrics::histogram!(
+        PROVIDER_DURATION_SECONDS,
+        "provider" => _provider.to_string(),
+        "result" => match &result {
+            Ok(_) => "success",
+            Err(AuthenticationError::InvalidAuthentication) => "abstained",
+            Err(_) => "failure",
+        },
+    )
+    .record(started.elapsed().as_secs_f64());
+    #[cfg(feature = "tracing")]
+    match &result {
+        Ok(_) => tracing::debug!(provider = _provider, "authentication provider succeeded"),
+        Err(AuthenticationError::InvalidAuthentication) => {
+            tracing::debug!(provider = _provider, "authentication provider abstained")
+        }
+        Err(error) => {
+            tracing::debug!(provider = _provider, error = ?error, "authentication provider failed")
+        }
+    }
+    result
+}
+
+/// Records an authentication provider that does not support the type of the authentication.
+pub(crate) fn provider_skipped(_provider: usize) {
+    #[cfg(feature = "tracing")]
+    tracing::trace!(
+        provider = _provider,
+        "authentication provider does not support the authentication"
+    );
+}
+
+/// Records the final authentication decision of a request with the given authentication scheme.
+pub(crate) fn authentication_decided(_scheme: Option<&str>, _outcome: &AuthenticationOutcome) {
+    #[cfg(feature = "metrics")]
+    {
+        let (outcome, error) = match _outcome {
+            AuthenticationOutcome::Success => ("success", "none".to_string()),
+            AuthenticationOutcome::Failure(error) => ("failure", format!("{:?}", error)),
+            AuthenticationOutcome::Blocked { .. } => ("blocked", "none".to_string()),
+        };
+        metrics::counter!(
+            AUTHENTICATIONS_TOTAL,
+            "scheme" => scheme_label(_scheme),
+            "outcome" => outcome,
+            "error" => error,
+        )
+        .increment(1);
+    }
+    #[cfg(feature = "tracing")]
+    match _outcome {
+        AuthenticationOutcome::Success => tracing::debug!("authentication succeeded"),
+        AuthenticationOutcome::Failure(error) => {
+            tracing::info!(error = ?error, "authentication failed")
+        }
+        AuthenticationOutcome::Blocked { retry_after } => tracing::warn!(
+            retry_after = retry_after.as_secs(),
+            "authentication blocked after too many failed attempts"
+        ),
+    }
+}
+
+/// Records the result of a JWKS download.
+#[cfg(feature = "jwk-loader")]
+pub(crate) fn jwks_loaded<T>(_url: &str, _result: &Result<T, JwkLoaderError>) {
+    #[cfg(feature = "metrics")]
+    {
+        let (result, error) = match _result {
+            Ok(_) => ("success", "none".to_string()),
+            Err(error) => ("failure", format!("{:?}", error)),
+        };
+        metrics::counter!(JWKS_REFRESHES_TOTAL, "result" => result, "error" => error).increment(1);
+    }
+    #[cfg(feature = "tracing")]
+    match _result {
+        Ok(_) => tracing::debug!(url = _url, "JWKS downloaded"),
+        Err(error) => tracing::warn!(url = _url, error = ?error, "JWKS download failed"),
+    }
+}
+
+/// Maps the authentication scheme of the `Authorization` header to a label with a bounded set of values.
+#[cfg(feature = "metrics")]
+fn scheme_label(scheme: Option<&str>) -> &'static str {
+    match scheme {
+        Some(scheme) if scheme.eq_ignore_ascii_case("basic") => "basic",
+        Some(scheme) if scheme.eq_ignore_ascii_case("bearer") => "bearer",
+        Some(_) => "other",
+        None => "none",
+    }
+}
@@ -9,6 +9,14 @@
 //! * OAuth2 Resource Server "Auto-Configuration"
 //! * JWK-Downloader to verify JWTs
 //! * JWT verification
+//! * JWE decryption and PASETO verification
+//! * Opaque token validation with token introspection
+//! * Scope based authorization
+//! * Mapping of token claims to authorities
+//! * Password hashing for basic authentication
+//! * In-memory, htpasswd file and SQL user stores for basic authentication
+//! * LDAP bind authentication
+//! * Brute-force protection for basic authentication
 //!
 //! ## Note: Neither audited nor penetration tested
 //! This library is provided "as is" without warranties of any kind and is not verified to be secure.
@@ -39,11 +47,80 @@
 //!   actix-web-security = { version="0.1.0", features = ["jwk-default-loader"] }
 //!   ```
 //!
-//! Both features require `openssl` to be installed on the system.
+//! * `token-introspection`  
+//!   This feature can be activated to validate opaque tokens at the introspection endpoint of an authorization server
+//!   ```toml
+//!   actix-web-security = { version="0.1.0", features = ["token-introspection"] }
+//!   ```
+//!
+//! * `jwe`  
+//!   This feature can be activated to decrypt encrypted tokens (JWE with `RSA-OAEP` / `ECDH-ES` and `A256GCM`)
+//!   ```toml
+//!   actix-web-security = { version="0.1.0", features = ["jwe"] }
+//!   ```
+//!
+//! * `paseto`  
+//!   This feature can be activated to verify PASETO `v4.public` tokens
+//!   ```toml
+//!   actix-web-security = { version="0.1.0", features = ["paseto"] }
+//!   ```
+//!
+//! * `password-hashing`  
+//!   This feature can be activated to verify Argon2id, bcrypt, scrypt and PBKDF2 password hashes for basic authentication
+//!   ```toml
+//!   actix-web-security = { version="0.1.0", features = ["password-hashing"] }
+//!   ```
+//!
+//! * `htpasswd`  
+//!   This feature can be activated to authenticate basic authentication users from Apache htpasswd files
+//!   ```toml
+//!   actix-web-security = { version="0.1.0", features = ["htpasswd"] }
+//!   ```
+//!
+//! * `sqlx-user-store`  
+//!   This feature can be activated to load basic authentication users from a SQL database with sqlx
+//!   ```toml
+//!   actix-web-security = { version="0.1.0", features = ["sqlx-user-store"] }
+//!   ```
+//!
+//! * `ldap`  
+//!   This feature can be activated to authenticate basic authentication users against an LDAP directory with `ldap3`
+//!   ```toml
+//!   actix-web-security = { version="0.1.0", features = ["ldap"] }
+//!   ```
+//!
+//! * `derive`  
+//!   This feature can be activated to derive the `Claims` trait for custom claims (`#[derive(Claims)]`)
+//!   ```toml
+//!   actix-web-security = { version="0.1.0", features = ["derive"] }
+//!   ```
+//!
+//! * `tracing`  
+//!   This feature can be activated to emit `tracing` spans and events for the authentication pipeline
+//!   ```toml
+//!   actix-web-security = { version="0.1.0", features = ["tracing"] }
+//!   ```
+//!
+//! * `metrics`  
+//!   This feature can be activated to record counters and histograms of the authentication outcomes, token decoding
+//!   and authentication provider latencies and JWKS downloads with the `metrics` facade
+//!   ```toml
+//!   actix-web-security = { version="0.1.0", features = ["metrics"] }
+//!   ```
+//!
+//! The `jwk-loader`, `jwk-default-loader`, `token-introspection` and `ldap` features require `openssl` to be installed on
+//! the system.
 //! The documentation about how to install it can be found [here](https://docs.rs/openssl/0.10.32/openssl/#automatic).
 //!
 //! ## Samples
 //! Sample applications can be found [here](https://github.com/cschaible/actix-web-security-samples).
 
 pub mod authentication;
+pub mod authorization;
+mod instrumentation;
 pub mod user_details;
+
+#[doc(hidden)]
+pub mod __private {
+    pub use serde_json;
+}
@@ -0,0 +1,96 @@
+//! The account status checker verifies the account status of a `UserDetails` object during the authentication.
+
+use crate::authentication::error::error_type::AuthenticationError;
+use crate::user_details::UserDetails;
+
+/// Trait definition of an account status checker. The authentication providers run both checks only after the
+/// credentials have been verified, so that the status of an account is not revealed without valid credentials:
+/// the pre authentication check before the authentication has any effects (e.g. a password hash upgrade of the
+/// `PasswordAuthenticationProvider`) and the post authentication check afterwards.
+/// Both checks pass by default.
+pub trait AccountStatusChecker: AccountStatusCheckerClone {
+    /// Checks the account status after the credentials have been verified, before the authentication has any effects.
+    fn check_pre_authentication(
+        &self,
+        _user_details: &dyn UserDetails,
+    ) -> Result<(), AuthenticationError> {
+        Ok(())
+    }
+
+    /// Checks the account status at the end of the authentication.
+    fn check_post_authentication(
+        &self,
+        _user_details: &dyn UserDetails,
+    ) -> Result<(), AuthenticationError> {
+        Ok(())
+    }
+
+    /// Runs the pre and post authentication checks. Used by providers that don't verify the credentials
+    /// themselves (e.g. if the `BasicUserDetailsService` verifies the password).
+    fn check(&self, user_details: &dyn UserDetails) -> Result<(), AuthenticationError> {
+        self.check_pre_authentication(user_details)?;
+        self.check_post_authentication(user_details)
+    }
+}
+
+/// An account status checker must be cloneable, `send` and `sync`.
+/// Therefore it has to implement the `AccountStatusCheckerClone` trait to be cloneable as a boxed object.
+pub trait AccountStatusCheckerClone: Send + Sync {
+    fn clone_box(&self) -> Box<dyn AccountStatusChecker>;
+}
+
+impl<U> AccountStatusCheckerClone for U
+where
+    U: 'static + AccountStatusChecker + Clone,
+{
+    fn clone_box(&self) -> Box<dyn AccountStatusChecker> {
+        Box::new(self.clone())
+    }
+}
+
+impl Clone for Box<dyn AccountStatusChecker> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
+/// The default `AccountStatusChecker`. Locked, disabled and expired accounts are rejected before the
+/// credentials are verified, expired credentials are rejected after the credentials have been verified
+/// (so that only the owner of the credentials learns that they have expired).
+#[derive(Clone, Default)]
+pub struct DefaultAccountStatusChecker {}
+
+impl DefaultAccountStatusChecker {
+    /// Constructs a new instance.
+    pub fn new() -> DefaultAccountStatusChecker {
+        DefaultAccountStatusChecker {}
+    }
+}
+
+impl AccountStatusChecker for DefaultAccountStatusChecker {
+    fn check_pre_authentication(
+        &self,
+        user_details: &dyn UserDetails,
+    ) -> Result<(), AuthenticationError> {
+        if user_details.is_locked() {
+            Err(AuthenticationError::AccountLocked)
+        } else if !user_details.is_enabled() {
+            Err(AuthenticationError::AccountDisabled)
+        } else if user_details.is_expired() {
+            Err(AuthenticationError::AccountExpired)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn check_post_authentication(
+        &self,
+        user_details: &dyn UserDetails,
+    ) -> Result<(), AuthenticationError> {
+        if user_details.is_credentials_expired() {
+            Err(AuthenticationError::CredentialsExpired)
+        } else {
+            Ok(())
+        }
+    }
+}
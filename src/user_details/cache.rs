@@ -0,0 +1,114 @@
+//! A size and time bound cache for `UserDetails` shared by the caching user details services.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::user_details::UserDetails;
+
+/// The default time to live of found users.
+pub(crate) const DEFAULT_TIME_TO_LIVE: Duration = Duration::from_secs(300);
+/// The default maximum number of cached entries.
+pub(crate) const DEFAULT_MAX_SIZE: usize = 10_000;
+
+/// A cache of user lookup results. Users that were not found are only cached if a negative time to live is set.
+/// The entries are shared between all clones (and therefore all workers).
+#[derive(Clone)]
+pub(crate) struct UserDetailsCache<K> {
+    entries: Arc<Mutex<HashMap<K, CacheEntry>>>,
+    time_to_live: Duration,
+    negative_time_to_live: Option<Duration>,
+    max_size: usize,
+}
+
+struct CacheEntry {
+    user_details: Option<Box<dyn UserDetails>>,
+    expires_at: Instant,
+}
+
+impl<K: Clone + Eq + Hash> UserDetailsCache<K> {
+    pub(crate) fn new() -> UserDetailsCache<K> {
+        UserDetailsCache {
+            entries: Arc::new(Mutex::new(HashMap::new())),
+            time_to_live: DEFAULT_TIME_TO_LIVE,
+            negative_time_to_live: None,
+            max_size: DEFAULT_MAX_SIZE,
+        }
+    }
+
+    pub(crate) fn set_time_to_live(&mut self, time_to_live: Duration) {
+        self.time_to_live = time_to_live;
+    }
+
+    pub(crate) fn set_negative_time_to_live(&mut self, negative_time_to_live: Option<Duration>) {
+        self.negative_time_to_live = negative_time_to_live;
+    }
+
+    pub(crate) fn set_max_size(&mut self, max_size: usize) {
+        self.max_size = max_size;
+    }
+
+    /// Returns `None` if there is no valid entry for the key, otherwise the cached lookup result.
+    pub(crate) fn get(&self, key: &K) -> Option<Option<Box<dyn UserDetails>>> {
+        let mut entries = self.entries.lock().expect("Failed to acquire lock");
+        match entries.get(key) {
+            Some(entry) if entry.expires_at > Instant::now() => Some(entry.user_details.clone()),
+            Some(_) => {
+                entries.remove(key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    /// Caches the lookup result. The result is not cached if the user was not found and negative caching is disabled.
+    pub(crate) fn insert(&self, key: K, user_details: Option<Box<dyn UserDetails>>) {
+        let time_to_live = match user_details {
+            Some(_) => self.time_to_live,
+            None => match self.negative_time_to_live {
+                Some(negative_time_to_live) => negative_time_to_live,
+                None => return,
+            },
+        };
+        if self.max_size == 0 {
+            return;
+        }
+
+        let now = Instant::now();
+        let mut entries = self.entries.lock().expect("Failed to acquire lock");
+        if entries.len() >= self.max_size && !entries.contains_key(&key) {
+            evict(&mut entries, now);
+        }
+        entries.insert(
+            key,
+            CacheEntry {
+                user_details,
+                expires_at: now + time_to_live,
+            },
+        );
+    }
+
+    /// Removes all entries matching the predicate.
+    pub(crate) fn evict_matching<F: Fn(&K) -> bool>(&self, predicate: F) {
+        self.entries
+            .lock()
+            .expect("Failed to acquire lock")
+            .retain(|key, _| !predicate(key));
+    }
+
+    /// Removes all entries.
+    pub(crate) fn evict_all(&self) {
+        self.entries.lock().expect("Failed to acquire lock").clear();
+    }
+}
+
+/// Removes the expired entries and the tenth of the entries expiring first.
+/// Evicting a batch of entries keeps the linear scan of the entries out of most insertions into a full cache.
+fn evict<K: Eq + Hash>(entries: &mut HashMap<K, CacheEntry>, now: Instant) {
+    let mut expiry_times: Vec<Instant> = entries.values().map(|entry| entry.expires_at).collect();
+    let index = expiry_times.len() / 10;
+    let (_, cutoff, _) = expiry_times.select_nth_unstable(index);
+    let cutoff = *cutoff;
+    entries.retain(|_, entry| entry.expires_at > cutoff && entry.expires_at > now);
+}
@@ -3,16 +3,40 @@
 use downcast_rs::impl_downcast;
 use downcast_rs::Downcast;
 
+pub mod account_status;
 pub mod attachment;
+pub(crate) mod cache;
 pub mod request_extension;
 
-/// Marker trait for a user object to put into the request context.
-pub trait UserDetails: Downcast + UserDetailsClone {}
+/// Trait for a user object to put into the request context.
+/// The account status methods are evaluated by the `AccountStatusChecker` of the authentication providers
+/// and return a valid status by default.
+pub trait UserDetails: Downcast + UserDetailsClone {
+    /// Returns **false** if the account has been disabled.
+    fn is_enabled(&self) -> bool {
+        true
+    }
+
+    /// Returns **true** if the account has been locked (e.g. after too many failed login attempts).
+    fn is_locked(&self) -> bool {
+        false
+    }
+
+    /// Returns **true** if the account has expired.
+    fn is_expired(&self) -> bool {
+        false
+    }
+
+    /// Returns **true** if the credentials (password) of the account have expired.
+    fn is_credentials_expired(&self) -> bool {
+        false
+    }
+}
 impl_downcast!(UserDetails);
 
-/// A user details object must be cloneable.
+/// A user details object must be cloneable, `send` and `sync`.
 /// Therefore it has to implement the `UserDetailsClone` trait to be cloneable as a boxed object.
-pub trait UserDetailsClone {
+pub trait UserDetailsClone: Send + Sync {
     fn clone_box(&self) -> Box<dyn UserDetails>;
 }
 
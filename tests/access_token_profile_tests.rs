@@ -0,0 +1,184 @@
+use actix_web::dev::Service;
+use actix_web::http::header;
+use actix_web::{get, test, App, HttpResponse, Responder};
+use async_trait::async_trait;
+use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
+use serde_json::json;
+
+use actix_web_security::authentication::endpoint_matcher::AllEndpointsMatcher;
+use actix_web_security::authentication::error::error_type::AuthenticationError;
+use actix_web_security::authentication::middleware::HttpAuthenticationMiddleware;
+use actix_web_security::authentication::scheme::bearer::jwt::access_token_jwt::AccessTokenJwt;
+use actix_web_security::authentication::scheme::bearer::jwt::authentication_provider::JwtAuthenticationProvider;
+use actix_web_security::authentication::scheme::bearer::jwt::header_extractor::BearerAuthenticationExtractor;
+use actix_web_security::authentication::scheme::bearer::jwt::token::decoder::TokenDecoder;
+use actix_web_security::authentication::scheme::bearer::jwt::token::Claims;
+use actix_web_security::authentication::scheme::bearer::jwt::user_details_service::JwtUserDetailsService;
+use actix_web_security::authentication::ProviderManager;
+use actix_web_security::user_details::UserDetails;
+
+use common::deserialize_json;
+use common::User;
+
+mod common;
+
+#[derive(Clone)]
+struct JwtUserDetailsServiceImpl {}
+
+#[async_trait]
+impl JwtUserDetailsService for JwtUserDetailsServiceImpl {
+    async fn find_user(&self, token: &Box<dyn Claims>) -> Option<Box<dyn UserDetails>> {
+        token
+            .downcast_ref::<AccessTokenJwt>()
+            .filter(|claims| claims.aud.contains("orders-api"))
+            .filter(|claims| claims.scope.contains_all(&["orders:read", "orders:write"]))
+            .map(|claims| {
+                Box::new(User {
+                    username: format!("{}@{}", claims.sub, claims.client_id),
+                }) as Box<dyn UserDetails>
+            })
+    }
+}
+
+#[derive(Clone)]
+struct SimpleTokenDecoder {}
+
+impl TokenDecoder<AccessTokenJwt> for SimpleTokenDecoder {
+    fn decode_token(&self, token: &str) -> Result<Box<AccessTokenJwt>, AuthenticationError> {
+        let key = &DecodingKey::from_secret("secret".as_ref());
+        match decode::<AccessTokenJwt>(token, key, &Validation::default()) {
+            Ok(token_data) => Ok(Box::new(token_data.claims)),
+            Err(_) => Err(AuthenticationError::InvalidToken),
+        }
+    }
+}
+
+#[get("/test")]
+async fn test_endpoint(user: User) -> impl Responder {
+    HttpResponse::Ok().json(user)
+}
+
+fn init_middleware(
+) -> HttpAuthenticationMiddleware<BearerAuthenticationExtractor<AccessTokenJwt>, AllEndpointsMatcher>
+{
+    let authentication_provider =
+        JwtAuthenticationProvider::new(Box::new(JwtUserDetailsServiceImpl {}));
+
+    let provider_manager = ProviderManager::new(vec![Box::new(authentication_provider)]);
+
+    let authentication_extractor = BearerAuthenticationExtractor::access_token_profile(
+        vec![Box::new(SimpleTokenDecoder {})],
+        "https://authorization-server".to_string(),
+        "orders-api".to_string(),
+    );
+
+    HttpAuthenticationMiddleware::new(
+        provider_manager,
+        Box::new(authentication_extractor),
+        Box::new(AllEndpointsMatcher::new()),
+    )
+}
+
+fn create_token(typ: &str, claims: serde_json::Value) -> String {
+    let header = Header {
+        typ: Some(typ.to_string()),
+        ..Header::default()
+    };
+    let key = &EncodingKey::from_secret("secret".as_ref());
+    encode(&header, &claims, key).expect("Token couldn't be encoded")
+}
+
+fn access_token_claims() -> serde_json::Value {
+    json!({
+        "iss": "https://authorization-server",
+        "exp": 10000000000u64,
+        "aud": ["orders-api", "billing-api"],
+        "sub": "test",
+        "client_id": "client",
+        "iat": 1600000000,
+        "jti": "a8f5f167",
+        "scope": "orders:read orders:write"
+    })
+}
+
+async fn call_endpoint(token: String) -> Result<User, AuthenticationError> {
+    let mut service =
+        test::init_service(App::new().wrap(init_middleware()).service(test_endpoint)).await;
+
+    let req = test::TestRequest::get()
+        .uri("/test")
+        .header(header::AUTHORIZATION, format!("Bearer {}", token))
+        .to_request();
+
+    match service.call(req).await {
+        Ok(mut service_response) => {
+            assert!(&service_response.status().is_success());
+            Ok(deserialize_json(&mut service_response.take_body()).await)
+        }
+        Err(e) => Err(e
+            .as_error::<AuthenticationError>()
+            .expect("error expected")
+            .clone()),
+    }
+}
+
+#[actix_rt::test]
+async fn validate_access_token_profile_succeeds() {
+    let token = create_token("at+jwt", access_token_claims());
+
+    assert_eq!(
+        Ok(User {
+            username: "test@client".to_string()
+        }),
+        call_endpoint(token).await
+    );
+}
+
+#[actix_rt::test]
+async fn validate_access_token_profile_with_invalid_type() {
+    let token = create_token("JWT", access_token_claims());
+
+    assert_eq!(
+        Err(AuthenticationError::InvalidAuthentication),
+        call_endpoint(token).await
+    );
+}
+
+#[actix_rt::test]
+async fn validate_access_token_profile_with_missing_claim() {
+    let mut claims = access_token_claims();
+    claims
+        .as_object_mut()
+        .expect("claims expected")
+        .remove("client_id");
+    let token = create_token("application/at+jwt", claims);
+
+    assert_eq!(
+        Err(AuthenticationError::InvalidAuthentication),
+        call_endpoint(token).await
+    );
+}
+
+#[actix_rt::test]
+async fn validate_access_token_profile_with_unexpected_issuer() {
+    let mut claims = access_token_claims();
+    claims["iss"] = json!("https://other-authorization-server");
+    let token = create_token("at+jwt", claims);
+
+    assert_eq!(
+        Err(AuthenticationError::InvalidAuthentication),
+        call_endpoint(token).await
+    );
+}
+
+#[actix_rt::test]
+async fn validate_access_token_profile_with_unexpected_audience() {
+    let mut claims = access_token_claims();
+    claims["aud"] = json!("billing-api");
+    let token = create_token("at+jwt", claims);
+
+    assert_eq!(
+        Err(AuthenticationError::InvalidAuthentication),
+        call_endpoint(token).await
+    );
+}
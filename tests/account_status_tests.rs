@@ -0,0 +1,259 @@
+use actix_web::dev::Service;
+use actix_web::http::{header, StatusCode};
+use actix_web::{get, test, App, HttpResponse, Responder, ResponseError};
+use async_trait::async_trait;
+
+use actix_web_security::authentication::endpoint_matcher::AllEndpointsMatcher;
+use actix_web_security::authentication::error::error_type::AuthenticationError;
+use actix_web_security::authentication::middleware::HttpAuthenticationMiddleware;
+use actix_web_security::authentication::scheme::authentication_provider::AuthenticationProvider;
+use actix_web_security::authentication::scheme::basic::authentication_provider::BasicAuthenticationProvider;
+use actix_web_security::authentication::scheme::basic::header_extractor::BasicAuthenticationExtractor;
+use actix_web_security::authentication::scheme::basic::password_authentication_provider::PasswordAuthenticationProvider;
+use actix_web_security::authentication::scheme::basic::user_details_service::BasicUserDetailsService;
+use actix_web_security::authentication::scheme::basic::user_store::{
+    BasicUserStore, UserCredentials,
+};
+use actix_web_security::authentication::ProviderManager;
+use actix_web_security::user_details::account_status::AccountStatusChecker;
+use actix_web_security::user_details::UserDetails;
+
+use common::ReversingPasswordEncoder;
+
+mod common;
+
+#[derive(Clone)]
+struct StatusUser {
+    enabled: bool,
+    locked: bool,
+    expired: bool,
+    credentials_expired: bool,
+}
+
+impl UserDetails for StatusUser {
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    fn is_locked(&self) -> bool {
+        self.locked
+    }
+
+    fn is_expired(&self) -> bool {
+        self.expired
+    }
+
+    fn is_credentials_expired(&self) -> bool {
+        self.credentials_expired
+    }
+}
+
+fn find_user(username: &str) -> Option<StatusUser> {
+    let user = StatusUser {
+        enabled: true,
+        locked: false,
+        expired: false,
+        credentials_expired: false,
+    };
+    match username {
+        "active" => Some(user),
+        "disabled" => Some(StatusUser {
+            enabled: false,
+            ..user
+        }),
+        "locked" => Some(StatusUser {
+            locked: true,
+            enabled: false,
+            ..user
+        }),
+        "expired" => Some(StatusUser {
+            expired: true,
+            ..user
+        }),
+        "credentials-expired" => Some(StatusUser {
+            credentials_expired: true,
+            ..user
+        }),
+        _ => None,
+    }
+}
+
+#[derive(Clone)]
+struct BasicUserDetailsServiceImpl {}
+
+#[async_trait]
+impl BasicUserDetailsService for BasicUserDetailsServiceImpl {
+    async fn find_user(&self, username: &str, password: &str) -> Option<Box<dyn UserDetails>> {
+        find_user(username)
+            .filter(|_| password == "pw")
+            .map(|user| Box::new(user) as Box<dyn UserDetails>)
+    }
+}
+
+#[derive(Clone)]
+struct BasicUserStoreImpl {}
+
+#[async_trait]
+impl BasicUserStore for BasicUserStoreImpl {
+    async fn find_user_by_username(
+        &self,
+        username: &str,
+    ) -> Result<Option<UserCredentials>, AuthenticationError> {
+        Ok(find_user(username).map(|user| UserCredentials {
+            user_details: Box::new(user),
+            password_hash: "rev:wp".to_string(),
+        }))
+    }
+}
+
+/// Checker that accepts disabled accounts
+#[derive(Clone)]
+struct LockedAccountStatusChecker {}
+
+impl AccountStatusChecker for LockedAccountStatusChecker {
+    fn check_pre_authentication(
+        &self,
+        user_details: &dyn UserDetails,
+    ) -> Result<(), AuthenticationError> {
+        if user_details.is_locked() {
+            Err(AuthenticationError::AccountLocked)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[get("/test")]
+async fn test_endpoint() -> impl Responder {
+    HttpResponse::Ok().finish()
+}
+
+async fn call_endpoint(
+    authentication_provider: Box<dyn AuthenticationProvider>,
+    credentials: &str,
+) -> Result<(), AuthenticationError> {
+    let middleware = HttpAuthenticationMiddleware::new(
+        ProviderManager::new(vec![authentication_provider]),
+        Box::new(BasicAuthenticationExtractor::new()),
+        Box::new(AllEndpointsMatcher::new()),
+    );
+    let mut service = test::init_service(App::new().wrap(middleware).service(test_endpoint)).await;
+
+    let req = test::TestRequest::get()
+        .uri("/test")
+        .header(
+            header::AUTHORIZATION,
+            format!("Basic {}", base64::encode(credentials)),
+        )
+        .to_request();
+
+    match service.call(req).await {
+        Ok(service_response) => {
+            assert!(&service_response.status().is_success());
+            Ok(())
+        }
+        Err(e) => Err(e
+            .as_error::<AuthenticationError>()
+            .expect("error expected")
+            .clone()),
+    }
+}
+
+fn basic_authentication_provider() -> BasicAuthenticationProvider {
+    BasicAuthenticationProvider::new(Box::new(BasicUserDetailsServiceImpl {}))
+}
+
+fn password_authentication_provider() -> PasswordAuthenticationProvider {
+    PasswordAuthenticationProvider::new(
+        Box::new(BasicUserStoreImpl {}),
+        Box::new(ReversingPasswordEncoder::new()),
+    )
+}
+
+#[actix_rt::test]
+async fn validate_account_status_with_user_details_service() {
+    for (credentials, expected) in &[
+        ("active:pw", Ok(())),
+        ("disabled:pw", Err(AuthenticationError::AccountDisabled)),
+        ("locked:pw", Err(AuthenticationError::AccountLocked)),
+        ("expired:pw", Err(AuthenticationError::AccountExpired)),
+        (
+            "credentials-expired:pw",
+            Err(AuthenticationError::CredentialsExpired),
+        ),
+        ("disabled:wrong", Err(AuthenticationError::UsernameNotFound)),
+    ] {
+        assert_eq!(
+            expected,
+            &call_endpoint(Box::new(basic_authentication_provider()), credentials).await
+        );
+    }
+}
+
+#[actix_rt::test]
+async fn validate_account_status_with_user_store() {
+    for (credentials, expected) in &[
+        ("active:pw", Ok(())),
+        ("disabled:pw", Err(AuthenticationError::AccountDisabled)),
+        ("locked:pw", Err(AuthenticationError::AccountLocked)),
+        ("locked:wrong", Err(AuthenticationError::UsernameNotFound)),
+        ("disabled:wrong", Err(AuthenticationError::UsernameNotFound)),
+        ("expired:pw", Err(AuthenticationError::AccountExpired)),
+        (
+            "credentials-expired:pw",
+            Err(AuthenticationError::CredentialsExpired),
+        ),
+        (
+            "credentials-expired:wrong",
+            Err(AuthenticationError::UsernameNotFound),
+        ),
+    ] {
+        assert_eq!(
+            expected,
+            &call_endpoint(Box::new(password_authentication_provider()), credentials).await
+        );
+    }
+}
+
+#[actix_rt::test]
+async fn validate_custom_account_status_checker() {
+    let basic_authentication_provider = basic_authentication_provider()
+        .with_account_status_checker(Box::new(LockedAccountStatusChecker {}));
+    let password_authentication_provider = password_authentication_provider()
+        .with_account_status_checker(Box::new(LockedAccountStatusChecker {}));
+
+    for provider in &[
+        Box::new(basic_authentication_provider) as Box<dyn AuthenticationProvider>,
+        Box::new(password_authentication_provider),
+    ] {
+        assert_eq!(Ok(()), call_endpoint(provider.clone(), "disabled:pw").await);
+        assert_eq!(
+            Ok(()),
+            call_endpoint(provider.clone(), "credentials-expired:pw").await
+        );
+        assert_eq!(
+            Err(AuthenticationError::AccountLocked),
+            call_endpoint(provider.clone(), "locked:pw").await
+        );
+    }
+}
+
+#[test]
+fn validate_account_status_error_responses() {
+    for (error, message) in &[
+        (AuthenticationError::AccountDisabled, "account disabled"),
+        (AuthenticationError::AccountLocked, "account locked"),
+        (AuthenticationError::AccountExpired, "account expired"),
+        (
+            AuthenticationError::CredentialsExpired,
+            "credentials expired",
+        ),
+    ] {
+        let response = error.error_response();
+        assert_eq!(StatusCode::UNAUTHORIZED, response.status());
+        match response.body().as_ref() {
+            Some(actix_web::body::Body::Bytes(bytes)) => assert_eq!(message.as_bytes(), &bytes[..]),
+            body => panic!("Unexpected body: {:?}", body),
+        }
+    }
+}
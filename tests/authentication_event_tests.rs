@@ -0,0 +1,275 @@
+use std::io::{self, Write};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use actix_web::dev::Service;
+use actix_web::http::header;
+use actix_web::{get, test, App, HttpResponse, Responder};
+use async_trait::async_trait;
+use serde_json::{json, Value};
+
+use actix_web_security::authentication::brute_force::in_memory_store::InMemoryLoginAttemptStore;
+use actix_web_security::authentication::brute_force::BruteForceProtection;
+use actix_web_security::authentication::endpoint_matcher::AllEndpointsMatcher;
+use actix_web_security::authentication::error::error_type::AuthenticationError;
+use actix_web_security::authentication::event::json_lines_sink::JsonLinesAuditSink;
+use actix_web_security::authentication::event::{
+    AuthenticationEvent, AuthenticationEventListener, AuthenticationOutcome,
+};
+use actix_web_security::authentication::middleware::HttpAuthenticationMiddleware;
+use actix_web_security::authentication::scheme::basic::authentication_provider::BasicAuthenticationProvider;
+use actix_web_security::authentication::scheme::basic::header_extractor::BasicAuthenticationExtractor;
+use actix_web_security::authentication::scheme::basic::user_details_service::BasicUserDetailsService;
+use actix_web_security::authentication::ProviderManager;
+use actix_web_security::user_details::UserDetails;
+
+use common::User;
+
+mod common;
+
+#[derive(Clone)]
+struct BasicUserDetailsServiceImpl {}
+
+#[async_trait]
+impl BasicUserDetailsService for BasicUserDetailsServiceImpl {
+    async fn find_user(&self, username: &str, password: &str) -> Option<Box<dyn UserDetails>> {
+        if username == "test" && password == "pw" {
+            Some(Box::new(User {
+                username: username.to_string(),
+            }))
+        } else {
+            None
+        }
+    }
+}
+
+/// Test listener collecting the events
+#[derive(Clone, Default)]
+struct CollectingEventListener {
+    events: Arc<Mutex<Vec<AuthenticationEvent>>>,
+}
+
+impl AuthenticationEventListener for CollectingEventListener {
+    fn on_event(&self, event: &AuthenticationEvent) {
+        self.events.lock().unwrap().push(event.clone());
+    }
+}
+
+/// Test writer collecting the written bytes
+#[derive(Clone, Default)]
+struct SharedBuffer {
+    buffer: Arc<Mutex<Vec<u8>>>,
+}
+
+impl SharedBuffer {
+    fn lines(&self) -> Vec<Value> {
+        String::from_utf8(self.buffer.lock().unwrap().clone())
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).expect("JSON line expected"))
+            .collect()
+    }
+}
+
+impl Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.buffer.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[get("/test")]
+async fn test_endpoint() -> impl Responder {
+    HttpResponse::Ok().finish()
+}
+
+async fn call_endpoint(
+    middleware: HttpAuthenticationMiddleware<BasicAuthenticationExtractor, AllEndpointsMatcher>,
+    authorization: Option<&str>,
+) {
+    let mut service = test::init_service(App::new().wrap(middleware).service(test_endpoint)).await;
+
+    let mut req = test::TestRequest::get()
+        .uri("/test")
+        .peer_addr("10.0.0.1:54321".parse().unwrap());
+    if let Some(authorization) = authorization {
+        req = req.header(header::AUTHORIZATION, authorization);
+    }
+    let _ = service.call(req.to_request()).await;
+}
+
+fn middleware() -> HttpAuthenticationMiddleware<BasicAuthenticationExtractor, AllEndpointsMatcher> {
+    HttpAuthenticationMiddleware::new(
+        ProviderManager::new(vec![Box::new(BasicAuthenticationProvider::new(Box::new(
+            BasicUserDetailsServiceImpl {},
+        )))]),
+        Box::new(BasicAuthenticationExtractor::new()),
+        Box::new(AllEndpointsMatcher::new()),
+    )
+}
+
+fn basic(credentials: &str) -> String {
+    format!("Basic {}", base64::encode(credentials))
+}
+
+#[actix_rt::test]
+async fn validate_events_are_published() {
+    let listener = CollectingEventListener::default();
+
+    for authorization in &[Some(basic("test:pw")), Some(basic("test:wrong")), None] {
+        let middleware = middleware().with_event_listener(Box::new(listener.clone()));
+        call_endpoint(middleware, authorization.as_deref()).await;
+    }
+
+    let events = listener.events.lock().unwrap();
+    assert_eq!(3, events.len());
+
+    assert_eq!(AuthenticationOutcome::Success, events[0].outcome);
+    assert_eq!(Some("test".to_string()), events[0].principal);
+    assert_eq!(Some("Basic".to_string()), events[0].scheme);
+    assert_eq!("GET", events[0].method);
+    assert_eq!("/test", events[0].endpoint);
+    assert_eq!(Some("10.0.0.1".parse().unwrap()), events[0].client_ip);
+    assert!(events[0].timestamp <= SystemTime::now());
+
+    assert_eq!(
+        AuthenticationOutcome::Failure(AuthenticationError::UsernameNotFound),
+        events[1].outcome
+    );
+    assert_eq!(Some("test".to_string()), events[1].principal);
+
+    assert_eq!(
+        AuthenticationOutcome::Failure(AuthenticationError::AuthorizationHeaderNotSet),
+        events[2].outcome
+    );
+    assert_eq!(None, events[2].principal);
+    assert_eq!(None, events[2].scheme);
+}
+
+#[actix_rt::test]
+async fn validate_blocked_attempts_are_published() {
+    let listener = CollectingEventListener::default();
+    let brute_force_protection =
+        BruteForceProtection::new(Box::new(InMemoryLoginAttemptStore::new()))
+            .with_lockout(1, Duration::from_secs(60));
+
+    for _ in 0..2 {
+        let middleware = middleware()
+            .with_brute_force_protection(brute_force_protection.clone())
+            .with_event_listener(Box::new(listener.clone()));
+        call_endpoint(middleware, Some(&basic("test:wrong"))).await;
+    }
+
+    let events = listener.events.lock().unwrap();
+    assert_eq!(
+        AuthenticationOutcome::Failure(AuthenticationError::UsernameNotFound),
+        events[0].outcome
+    );
+    assert!(matches!(
+        events[1].outcome,
+        AuthenticationOutcome::Blocked { .. }
+    ));
+}
+
+#[actix_rt::test]
+async fn validate_forwarded_client_ip_is_published() {
+    let listener = CollectingEventListener::default();
+    let middleware = middleware()
+        .with_forwarded_client_ip(true)
+        .with_event_listener(Box::new(listener.clone()));
+    let mut service = test::init_service(App::new().wrap(middleware).service(test_endpoint)).await;
+
+    let req = test::TestRequest::get()
+        .uri("/test")
+        .peer_addr("10.0.0.1:54321".parse().unwrap())
+        .header("X-Forwarded-For", "192.168.1.7")
+        .header(header::AUTHORIZATION, basic("test:pw"))
+        .to_request();
+    let _ = service.call(req).await;
+
+    let events = listener.events.lock().unwrap();
+    assert_eq!(Some("192.168.1.7".parse().unwrap()), events[0].client_ip);
+}
+
+#[actix_rt::test]
+async fn validate_json_lines_audit_sink() {
+    let buffer = SharedBuffer::default();
+    let sink = JsonLinesAuditSink::new(buffer.clone());
+
+    call_endpoint(
+        middleware().with_event_listener(Box::new(sink.clone())),
+        Some(&basic("test:pw")),
+    )
+    .await;
+    call_endpoint(
+        middleware().with_event_listener(Box::new(sink.clone())),
+        Some("Basic invalid!"),
+    )
+    .await;
+    sink.on_event(&AuthenticationEvent {
+        outcome: AuthenticationOutcome::Blocked {
+            retry_after: Duration::from_secs(30),
+        },
+        principal: Some("admin".to_string()),
+        scheme: Some("Basic".to_string()),
+        method: "POST".to_string(),
+        endpoint: "/login".to_string(),
+        client_ip: None,
+        timestamp: UNIX_EPOCH + Duration::from_millis(951_782_400_250),
+    });
+
+    let lines = buffer.lines();
+    assert_eq!(3, lines.len());
+
+    assert_eq!(json!("success"), lines[0]["outcome"]);
+    assert_eq!(json!("test"), lines[0]["principal"]);
+    assert_eq!(json!("Basic"), lines[0]["scheme"]);
+    assert_eq!(json!("GET"), lines[0]["method"]);
+    assert_eq!(json!("/test"), lines[0]["endpoint"]);
+    assert_eq!(json!("10.0.0.1"), lines[0]["client_ip"]);
+    assert!(lines[0].get("error").is_none());
+
+    assert_eq!(json!("failure"), lines[1]["outcome"]);
+    assert_eq!(json!("InvalidAuthorizationHeader"), lines[1]["error"]);
+    assert_eq!(Value::Null, lines[1]["principal"]);
+
+    assert_eq!(
+        json!({
+            "timestamp": "2000-02-29T00:00:00.250Z",
+            "outcome": "blocked",
+            "retry_after": 30,
+            "principal": "admin",
+            "scheme": "Basic",
+            "method": "POST",
+            "endpoint": "/login",
+            "client_ip": null
+        }),
+        lines[2]
+    );
+}
+
+#[actix_rt::test]
+async fn validate_json_lines_audit_sink_appends_to_file() {
+    let directory = tempfile::tempdir().unwrap();
+    let path = directory.path().join("audit.log");
+
+    for _ in 0..2 {
+        let sink = JsonLinesAuditSink::to_file(&path).unwrap();
+        call_endpoint(
+            middleware().with_event_listener(Box::new(sink)),
+            Some(&basic("test:pw")),
+        )
+        .await;
+    }
+
+    let content = std::fs::read_to_string(&path).unwrap();
+    let lines: Vec<Value> = content
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(2, lines.len());
+    assert!(lines.iter().all(|line| line["outcome"] == json!("success")));
+}
@@ -0,0 +1,163 @@
+use actix_web::dev::Service;
+use actix_web::http::header;
+use actix_web::{get, test, App, HttpResponse, Responder};
+use async_trait::async_trait;
+use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
+use serde_json::json;
+
+use actix_web_security::authentication::endpoint_matcher::AllEndpointsMatcher;
+use actix_web_security::authentication::error::error_type::AuthenticationError;
+use actix_web_security::authentication::middleware::HttpAuthenticationMiddleware;
+use actix_web_security::authentication::scheme::bearer::jwt::authentication_provider::JwtAuthenticationProvider;
+use actix_web_security::authentication::scheme::bearer::jwt::default_jwt::DefaultJwt;
+use actix_web_security::authentication::scheme::bearer::jwt::header_extractor::BearerAuthenticationExtractor;
+use actix_web_security::authentication::scheme::bearer::jwt::token::decoder::TokenDecoder;
+use actix_web_security::authentication::scheme::bearer::jwt::token::Claims;
+use actix_web_security::authentication::scheme::bearer::jwt::user_details_service::JwtUserDetailsService;
+use actix_web_security::authentication::ProviderManager;
+use actix_web_security::authorization::authority::{
+    Authorities, AuthorityMapper, CaseNormalization, ClaimAuthorityMapper,
+};
+use actix_web_security::user_details::UserDetails;
+
+use common::deserialize_json;
+use common::User;
+
+mod common;
+
+#[derive(Clone)]
+struct JwtUserDetailsServiceImpl {}
+
+#[async_trait]
+impl JwtUserDetailsService for JwtUserDetailsServiceImpl {
+    async fn find_user(&self, token: &Box<dyn Claims>) -> Option<Box<dyn UserDetails>> {
+        token
+            .downcast_ref::<DefaultJwt>()
+            .and_then(|claims| claims.sub.clone())
+            .map(|sub| Box::new(User { username: sub }) as Box<dyn UserDetails>)
+    }
+}
+
+#[derive(Clone)]
+struct SimpleTokenDecoder {}
+
+impl TokenDecoder<DefaultJwt> for SimpleTokenDecoder {
+    fn decode_token(&self, token: &str) -> Result<Box<DefaultJwt>, AuthenticationError> {
+        let key = &DecodingKey::from_secret("secret".as_ref());
+        match decode::<DefaultJwt>(token, key, &Validation::default()) {
+            Ok(token_data) => Ok(Box::new(token_data.claims)),
+            Err(_) => Err(AuthenticationError::InvalidToken),
+        }
+    }
+}
+
+#[get("/authorities")]
+async fn authorities_endpoint(authorities: Authorities) -> impl Responder {
+    HttpResponse::Ok().json(authorities)
+}
+
+fn init_middleware(
+    authority_mapper: ClaimAuthorityMapper,
+) -> HttpAuthenticationMiddleware<BearerAuthenticationExtractor<DefaultJwt>, AllEndpointsMatcher> {
+    let authentication_provider =
+        JwtAuthenticationProvider::new(Box::new(JwtUserDetailsServiceImpl {}));
+
+    let provider_manager = ProviderManager::new(vec![Box::new(authentication_provider)]);
+
+    let authentication_extractor =
+        BearerAuthenticationExtractor::new(vec![Box::new(SimpleTokenDecoder {})])
+            .with_authority_mapper(Box::new(authority_mapper));
+
+    HttpAuthenticationMiddleware::new(
+        provider_manager,
+        Box::new(authentication_extractor),
+        Box::new(AllEndpointsMatcher::new()),
+    )
+}
+
+async fn call_endpoint(
+    authority_mapper: ClaimAuthorityMapper,
+    claims: serde_json::Value,
+) -> Vec<String> {
+    let mut service = test::init_service(
+        App::new()
+            .wrap(init_middleware(authority_mapper))
+            .service(authorities_endpoint),
+    )
+    .await;
+
+    let key = &EncodingKey::from_secret("secret".as_ref());
+    let token = encode(&Header::default(), &claims, key).expect("Token couldn't be encoded");
+
+    let req = test::TestRequest::get()
+        .uri("/authorities")
+        .header(header::AUTHORIZATION, format!("Bearer {}", token))
+        .to_request();
+
+    match service.call(req).await {
+        Ok(mut service_response) => {
+            assert!(&service_response.status().is_success());
+            deserialize_json(&mut service_response.take_body()).await
+        }
+        Err(e) => panic!("Error occurred: {}", e),
+    }
+}
+
+#[actix_rt::test]
+async fn validate_keycloak_roles_are_mapped() {
+    let claims = json!({
+        "sub": "test",
+        "exp": 10000000000u64,
+        "realm_access": { "roles": ["offline_access", "user"] },
+        "resource_access": {
+            "orders-api": { "roles": ["orders-admin"] },
+            "billing-api": { "roles": ["billing-admin"] }
+        }
+    });
+
+    assert_eq!(
+        vec!["offline_access", "orders-admin", "user"],
+        call_endpoint(ClaimAuthorityMapper::keycloak(Some("orders-api")), claims).await
+    );
+}
+
+#[actix_rt::test]
+async fn validate_namespaced_claim_with_prefix_and_case_normalization() {
+    let claims = json!({
+        "sub": "test",
+        "exp": 10000000000u64,
+        "https://example.com/roles": ["Admin", "Editor"],
+        "scope": "orders:read"
+    });
+    let authority_mapper = ClaimAuthorityMapper::new()
+        .with_claim("https://example.com/roles", "ROLE_")
+        .with_claim("scope", "SCOPE_")
+        .with_case_normalization(CaseNormalization::Uppercase);
+
+    assert_eq!(
+        vec!["ROLE_ADMIN", "ROLE_EDITOR", "SCOPE_ORDERS:READ"],
+        call_endpoint(authority_mapper, claims).await
+    );
+}
+
+#[test]
+fn validate_azure_ad_roles_and_groups_are_mapped() {
+    let claims = json!({
+        "roles": ["Orders.Write"],
+        "groups": ["6f1b3c2e-group"],
+        "wids": ["b79fbf4d-role-template"]
+    });
+
+    let authorities = ClaimAuthorityMapper::azure_ad().map_authorities(&claims);
+
+    assert!(authorities.contains_all(&["Orders.Write", "6f1b3c2e-group"]));
+    assert!(!authorities.contains("b79fbf4d-role-template"));
+}
+
+#[test]
+fn validate_missing_claims_are_ignored() {
+    let authorities = ClaimAuthorityMapper::keycloak(Some("orders-api"))
+        .map_authorities(&json!({ "realm_access": { "roles": "user" } }));
+
+    assert_eq!(vec!["user"], authorities.iter().collect::<Vec<&String>>());
+}
@@ -1,3 +1,5 @@
+#![allow(clippy::let_and_return)]
+
 use actix_web::dev::Service;
 use actix_web::http::header;
 use actix_web::{get, test, App, HttpResponse, Responder};
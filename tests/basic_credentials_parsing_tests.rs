@@ -0,0 +1,172 @@
+use actix_web::http::header;
+use actix_web::test;
+
+use actix_web_security::authentication::error::error_type::AuthenticationError;
+use actix_web_security::authentication::scheme::basic::header_extractor::{
+    BasicAuthenticationExtractor, UsernameNormalization,
+};
+use actix_web_security::authentication::scheme::basic::BasicAuthentication;
+use actix_web_security::authentication::scheme::header_extractor::AuthorizationHeaderExtractor;
+
+async fn extract(
+    extractor: &BasicAuthenticationExtractor,
+    header_value: &str,
+) -> Result<(String, String), AuthenticationError> {
+    let req = test::TestRequest::get()
+        .header(header::AUTHORIZATION, header_value)
+        .to_http_request();
+
+    let authentication = extractor.extract_token(req.headers()).await?;
+    let basic_auth = authentication
+        .downcast_ref::<BasicAuthentication>()
+        .expect("basic authentication expected");
+    Ok((
+        basic_auth.username.clone(),
+        basic_auth.password.expose_secret().to_string(),
+    ))
+}
+
+fn credentials(username: &str, password: &str) -> (String, String) {
+    (username.to_string(), password.to_string())
+}
+
+#[actix_rt::test]
+async fn validate_scheme_is_case_insensitive_and_whitespace_tolerant() {
+    let extractor = BasicAuthenticationExtractor::new();
+    let encoded_credentials = base64::encode("test:pw");
+
+    for header_value in &[
+        format!("Basic {}", encoded_credentials),
+        format!("basic {}", encoded_credentials),
+        format!("BASIC {}", encoded_credentials),
+        format!("Basic   {}", encoded_credentials),
+        format!("Basic\t{}", encoded_credentials),
+        format!("  Basic {}  ", encoded_credentials),
+    ] {
+        assert_eq!(
+            Ok(credentials("test", "pw")),
+            extract(&extractor, header_value).await
+        );
+    }
+}
+
+#[actix_rt::test]
+async fn validate_invalid_basic_headers_are_rejected() {
+    let extractor = BasicAuthenticationExtractor::new();
+    let encoded_credentials = base64::encode("test:pw");
+
+    for header_value in &[
+        format!("Bearer {}", encoded_credentials),
+        format!("Basic{}", encoded_credentials),
+        format!("Basics {}", encoded_credentials),
+        "Basic not-base64!".to_string(),
+        format!("Basic {}", base64::encode("no-colon")),
+    ] {
+        assert_eq!(
+            Err(AuthenticationError::InvalidAuthorizationHeader),
+            extract(&extractor, header_value).await
+        );
+    }
+}
+
+#[actix_rt::test]
+async fn validate_utf8_credentials() {
+    let extractor = BasicAuthenticationExtractor::new();
+
+    assert_eq!(
+        Ok(credentials("Zoë", "pässwörd:with:colons")),
+        extract(
+            &extractor,
+            &format!("Basic {}", base64::encode("Zoë:pässwörd:with:colons"))
+        )
+        .await
+    );
+}
+
+#[actix_rt::test]
+async fn validate_iso_8859_1_fallback() {
+    // "Zoë:pässwörd" encoded with ISO-8859-1
+    let latin1_credentials = format!(
+        "Basic {}",
+        base64::encode(b"Zo\xeb:p\xe4ssw\xf6rd".as_ref())
+    );
+
+    assert_eq!(
+        Err(AuthenticationError::InvalidAuthorizationHeader),
+        extract(&BasicAuthenticationExtractor::new(), &latin1_credentials).await
+    );
+
+    let extractor = BasicAuthenticationExtractor::new().with_iso_8859_1_fallback();
+    assert_eq!(
+        Ok(credentials("Zoë", "pässwörd")),
+        extract(&extractor, &latin1_credentials).await
+    );
+    // Valid UTF-8 is still decoded as UTF-8
+    assert_eq!(
+        Ok(credentials("Zoë", "pässwörd")),
+        extract(
+            &extractor,
+            &format!("Basic {}", base64::encode("Zoë:pässwörd"))
+        )
+        .await
+    );
+}
+
+#[actix_rt::test]
+async fn validate_nfc_username_normalization() {
+    // "Zoë" with a combining diaeresis (NFD) and with a precomposed character (NFC)
+    let decomposed = format!("Basic {}", base64::encode("Zoe\u{308}:pw"));
+    let precomposed = format!("Basic {}", base64::encode("Zo\u{eb}:pw"));
+
+    assert_eq!(
+        Ok(credentials("Zoe\u{308}", "pw")),
+        extract(&BasicAuthenticationExtractor::new(), &decomposed).await
+    );
+
+    let extractor =
+        BasicAuthenticationExtractor::new().with_username_normalization(UsernameNormalization::Nfc);
+    assert_eq!(
+        Ok(credentials("Zo\u{eb}", "pw")),
+        extract(&extractor, &decomposed).await
+    );
+    assert_eq!(
+        Ok(credentials("Zo\u{eb}", "pw")),
+        extract(&extractor, &precomposed).await
+    );
+}
+
+#[actix_rt::test]
+async fn validate_username_lowercase_normalization() {
+    let extractor = BasicAuthenticationExtractor::new()
+        .with_username_normalization(UsernameNormalization::Lowercase);
+
+    assert_eq!(
+        Ok(credentials("zo\u{eb}", "Pw")),
+        extract(
+            &extractor,
+            &format!("Basic {}", base64::encode("ZOE\u{308}:Pw"))
+        )
+        .await
+    );
+    // Fullwidth characters are mapped to their ASCII counterparts
+    assert_eq!(
+        Ok(credentials("admin", "pw")),
+        extract(
+            &extractor,
+            &format!("Basic {}", base64::encode("\u{ff21}dmin:pw"))
+        )
+        .await
+    );
+    assert_eq!(
+        Err(AuthenticationError::InvalidAuthorizationHeader),
+        extract(
+            &extractor,
+            &format!("Basic {}", base64::encode("zoe admin:pw"))
+        )
+        .await
+    );
+    assert_eq!(
+        Err(AuthenticationError::InvalidAuthorizationHeader),
+        extract(&extractor, &format!("Basic {}", base64::encode(":pw"))).await
+    );
+}
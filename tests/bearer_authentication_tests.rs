@@ -1,3 +1,7 @@
+#![allow(clippy::let_and_return, clippy::needless_borrow)]
+
+use std::collections::HashMap;
+
 use actix_web::dev::Service;
 use actix_web::http::header;
 use actix_web::{get, test, App, HttpResponse, Responder};
@@ -78,6 +82,9 @@ async fn validate_bearer_auth_succeeds() {
         nbf: None,
         iat: None,
         jti: None,
+        scope: None,
+        scp: None,
+        additional_claims: HashMap::new(),
     };
     let key = &EncodingKey::from_secret("secret".as_ref());
     let token = encode(&Header::default(), &claims, key).expect("Token couldn't be encoded");
@@ -114,6 +121,9 @@ async fn validate_bearer_auth_invalid_credentials() {
         nbf: None,
         iat: None,
         jti: None,
+        scope: None,
+        scp: None,
+        additional_claims: HashMap::new(),
     };
     let token = encode(
         &Header::new(Algorithm::default()),
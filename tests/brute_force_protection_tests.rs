@@ -0,0 +1,402 @@
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use actix_web::dev::Service;
+use actix_web::http::{header, StatusCode};
+use actix_web::{get, test, App, HttpResponse, Responder, ResponseError};
+use async_trait::async_trait;
+use futures_timer::Delay;
+
+use actix_web_security::authentication::brute_force::in_memory_store::InMemoryLoginAttemptStore;
+use actix_web_security::authentication::brute_force::{
+    BruteForceProtection, LoginAttemptKey, LoginAttemptStore, TooManyAttemptsError,
+};
+use actix_web_security::authentication::endpoint_matcher::AllEndpointsMatcher;
+use actix_web_security::authentication::error::error_type::AuthenticationError;
+use actix_web_security::authentication::middleware::HttpAuthenticationMiddleware;
+use actix_web_security::authentication::scheme::basic::authentication_provider::BasicAuthenticationProvider;
+use actix_web_security::authentication::scheme::basic::header_extractor::BasicAuthenticationExtractor;
+use actix_web_security::authentication::scheme::basic::user_details_service::BasicUserDetailsService;
+use actix_web_security::authentication::ProviderManager;
+use actix_web_security::user_details::UserDetails;
+
+use common::User;
+
+mod common;
+
+#[derive(Clone)]
+struct BasicUserDetailsServiceImpl {}
+
+#[async_trait]
+impl BasicUserDetailsService for BasicUserDetailsServiceImpl {
+    async fn find_user(&self, username: &str, password: &str) -> Option<Box<dyn UserDetails>> {
+        if password == "pw" {
+            Some(Box::new(User {
+                username: username.to_string(),
+            }))
+        } else {
+            None
+        }
+    }
+}
+
+/// Test service counting the credential verifications, which take a while
+#[derive(Clone)]
+struct SlowBasicUserDetailsServiceImpl {
+    calls: Arc<AtomicUsize>,
+}
+
+#[async_trait]
+impl BasicUserDetailsService for SlowBasicUserDetailsServiceImpl {
+    async fn find_user(&self, username: &str, password: &str) -> Option<Box<dyn UserDetails>> {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        Delay::new(Duration::from_millis(50)).await;
+        if password == "pw" {
+            Some(Box::new(User {
+                username: username.to_string(),
+            }))
+        } else {
+            None
+        }
+    }
+}
+
+#[get("/test")]
+async fn test_endpoint() -> impl Responder {
+    HttpResponse::Ok().finish()
+}
+
+#[derive(Debug, PartialEq)]
+enum Outcome {
+    Success,
+    Rejected(AuthenticationError),
+    Blocked(u64),
+}
+
+struct TestClient<S> {
+    service: S,
+}
+
+impl<S, B> TestClient<S>
+where
+    S: Service<
+        Request = actix_http::Request,
+        Response = actix_web::dev::ServiceResponse<B>,
+        Error = actix_web::Error,
+    >,
+{
+    async fn login(&mut self, credentials: &str, client_ip: &str) -> Outcome {
+        let req = test::TestRequest::get()
+            .uri("/test")
+            .peer_addr(
+                format!("{}:40000", client_ip)
+                    .parse::<SocketAddr>()
+                    .unwrap(),
+            )
+            .header(
+                header::AUTHORIZATION,
+                format!("Basic {}", base64::encode(credentials)),
+            )
+            .to_request();
+
+        match self.service.call(req).await {
+            Ok(_) => Outcome::Success,
+            Err(e) => match e.as_error::<TooManyAttemptsError>() {
+                Some(error) => {
+                    let response = error.error_response();
+                    assert_eq!(StatusCode::TOO_MANY_REQUESTS, response.status());
+                    Outcome::Blocked(
+                        response
+                            .headers()
+                            .get(header::RETRY_AFTER)
+                            .expect("Retry-After expected")
+                            .to_str()
+                            .unwrap()
+                            .parse()
+                            .unwrap(),
+                    )
+                }
+                None => Outcome::Rejected(e.as_error::<AuthenticationError>().unwrap().clone()),
+            },
+        }
+    }
+}
+
+async fn init_client(
+    brute_force_protection: BruteForceProtection,
+) -> TestClient<
+    impl Service<
+        Request = actix_http::Request,
+        Response = actix_web::dev::ServiceResponse<actix_web::body::Body>,
+        Error = actix_web::Error,
+    >,
+> {
+    let middleware = HttpAuthenticationMiddleware::new(
+        ProviderManager::new(vec![Box::new(BasicAuthenticationProvider::new(Box::new(
+            BasicUserDetailsServiceImpl {},
+        )))]),
+        Box::new(BasicAuthenticationExtractor::new()),
+        Box::new(AllEndpointsMatcher::new()),
+    )
+    .with_brute_force_protection(brute_force_protection);
+
+    TestClient {
+        service: test::init_service(App::new().wrap(middleware).service(test_endpoint)).await,
+    }
+}
+
+fn rejected() -> Outcome {
+    Outcome::Rejected(AuthenticationError::UsernameNotFound)
+}
+
+#[actix_rt::test]
+async fn validate_login_is_blocked_after_failed_attempts() {
+    let brute_force_protection = BruteForceProtection::new(Box::new(
+        InMemoryLoginAttemptStore::new(),
+    ))
+    .with_backoff(2, Duration::from_secs(60), Duration::from_secs(600));
+    let mut client = init_client(brute_force_protection).await;
+
+    assert_eq!(rejected(), client.login("test:wrong", "10.0.0.1").await);
+    assert_eq!(rejected(), client.login("test:wrong", "10.0.0.1").await);
+    assert_eq!(
+        Outcome::Blocked(60),
+        client.login("test:pw", "10.0.0.1").await
+    );
+
+    // Other clients and users are not affected
+    assert_eq!(Outcome::Success, client.login("test:pw", "10.0.0.2").await);
+    assert_eq!(Outcome::Success, client.login("other:pw", "10.0.0.1").await);
+}
+
+#[actix_rt::test]
+async fn validate_successful_login_resets_failed_attempts() {
+    let brute_force_protection = BruteForceProtection::new(Box::new(
+        InMemoryLoginAttemptStore::new(),
+    ))
+    .with_backoff(2, Duration::from_secs(60), Duration::from_secs(600));
+    let mut client = init_client(brute_force_protection).await;
+
+    assert_eq!(rejected(), client.login("test:wrong", "10.0.0.1").await);
+    assert_eq!(Outcome::Success, client.login("test:pw", "10.0.0.1").await);
+    assert_eq!(rejected(), client.login("test:wrong", "10.0.0.1").await);
+    assert_eq!(Outcome::Success, client.login("test:pw", "10.0.0.1").await);
+}
+
+#[actix_rt::test]
+async fn validate_login_is_locked_after_failed_attempts() {
+    let brute_force_protection =
+        BruteForceProtection::new(Box::new(InMemoryLoginAttemptStore::new()))
+            .with_backoff(1, Duration::from_millis(1), Duration::from_millis(5))
+            .with_lockout(4, Duration::from_secs(900));
+    let mut client = init_client(brute_force_protection).await;
+
+    for _ in 0..4 {
+        assert_eq!(rejected(), client.login("test:wrong", "10.0.0.1").await);
+        actix_rt::time::sleep(Duration::from_millis(10)).await;
+    }
+    assert_eq!(
+        Outcome::Blocked(900),
+        client.login("test:pw", "10.0.0.1").await
+    );
+}
+
+#[actix_rt::test]
+async fn validate_exponential_backoff() {
+    let brute_force_protection =
+        BruteForceProtection::new(Box::new(InMemoryLoginAttemptStore::new()))
+            .with_backoff(2, Duration::from_secs(10), Duration::from_secs(60))
+            .with_lockout(8, Duration::from_secs(3600));
+    let key = LoginAttemptKey {
+        username: "test".to_string(),
+        client_ip: None,
+    };
+
+    let mut blocked_for = Vec::new();
+    for _ in 0..8 {
+        brute_force_protection.record_failure(&key).await;
+        blocked_for.push(
+            brute_force_protection
+                .check(&key)
+                .await
+                .map(|duration| (duration.as_secs_f64()).round() as u64),
+        );
+    }
+    assert_eq!(
+        vec![
+            None,
+            Some(10),
+            Some(20),
+            Some(40),
+            Some(60),
+            Some(60),
+            Some(60),
+            Some(3600)
+        ],
+        blocked_for
+    );
+
+    brute_force_protection.record_success(&key).await;
+    assert_eq!(None, brute_force_protection.check(&key).await);
+}
+
+#[actix_rt::test]
+async fn validate_failed_attempts_are_reset_after_inactivity() {
+    let brute_force_protection =
+        BruteForceProtection::new(Box::new(InMemoryLoginAttemptStore::new()))
+            .with_backoff(1, Duration::from_secs(10), Duration::from_secs(60))
+            .with_reset_after(Duration::from_millis(20));
+    let key = LoginAttemptKey {
+        username: "test".to_string(),
+        client_ip: None,
+    };
+
+    brute_force_protection.record_failure(&key).await;
+    assert!(brute_force_protection.check(&key).await.is_some());
+    actix_rt::time::sleep(Duration::from_millis(30)).await;
+    assert_eq!(None, brute_force_protection.check(&key).await);
+}
+
+#[actix_rt::test]
+async fn validate_forwarded_client_ip() {
+    let brute_force_protection = BruteForceProtection::new(Box::new(
+        InMemoryLoginAttemptStore::new(),
+    ))
+    .with_backoff(1, Duration::from_secs(60), Duration::from_secs(600));
+    let middleware = HttpAuthenticationMiddleware::new(
+        ProviderManager::new(vec![Box::new(BasicAuthenticationProvider::new(Box::new(
+            BasicUserDetailsServiceImpl {},
+        )))]),
+        Box::new(BasicAuthenticationExtractor::new()),
+        Box::new(AllEndpointsMatcher::new()),
+    )
+    .with_brute_force_protection(brute_force_protection)
+    .with_forwarded_client_ip(true);
+    let mut service = test::init_service(App::new().wrap(middleware).service(test_endpoint)).await;
+
+    let request = |forwarded_for: &str| {
+        test::TestRequest::get()
+            .uri("/test")
+            .header("X-Forwarded-For", forwarded_for)
+            .header(
+                header::AUTHORIZATION,
+                format!("Basic {}", base64::encode("test:wrong")),
+            )
+            .to_request()
+    };
+
+    let e = service.call(request("192.168.0.1")).await.unwrap_err();
+    assert!(e.as_error::<AuthenticationError>().is_some());
+    let e = service.call(request("192.168.0.1")).await.unwrap_err();
+    assert!(e.as_error::<TooManyAttemptsError>().is_some());
+    let e = service.call(request("192.168.0.2")).await.unwrap_err();
+    assert!(e.as_error::<AuthenticationError>().is_some());
+}
+
+/// Sends the requests concurrently and returns the number of blocked requests.
+async fn call_concurrently(
+    brute_force_protection: BruteForceProtection,
+    calls: &Arc<AtomicUsize>,
+    credentials: &str,
+    requests: usize,
+) -> usize {
+    let middleware = HttpAuthenticationMiddleware::new(
+        ProviderManager::new(vec![Box::new(BasicAuthenticationProvider::new(Box::new(
+            SlowBasicUserDetailsServiceImpl {
+                calls: Arc::clone(calls),
+            },
+        )))]),
+        Box::new(BasicAuthenticationExtractor::new()),
+        Box::new(AllEndpointsMatcher::new()),
+    )
+    .with_brute_force_protection(brute_force_protection);
+    let mut service = test::init_service(App::new().wrap(middleware).service(test_endpoint)).await;
+
+    let responses = (0..requests)
+        .map(|_| {
+            service.call(
+                test::TestRequest::get()
+                    .uri("/test")
+                    .header(
+                        header::AUTHORIZATION,
+                        format!("Basic {}", base64::encode(credentials)),
+                    )
+                    .to_request(),
+            )
+        })
+        .collect::<Vec<_>>();
+    futures_util::future::join_all(responses)
+        .await
+        .into_iter()
+        .filter(|response| {
+            response
+                .as_ref()
+                .err()
+                .and_then(|e| e.as_error::<TooManyAttemptsError>())
+                .is_some()
+        })
+        .count()
+}
+
+#[actix_rt::test]
+async fn validate_concurrent_attempts_are_blocked() {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let brute_force_protection =
+        BruteForceProtection::new(Box::new(InMemoryLoginAttemptStore::new()))
+            .with_backoff(3, Duration::from_secs(60), Duration::from_secs(600))
+            .with_max_pending_attempts(3, Duration::from_secs(60));
+
+    let blocked = call_concurrently(brute_force_protection, &calls, "test:wrong", 10).await;
+
+    assert_eq!(3, calls.load(Ordering::SeqCst));
+    assert_eq!(7, blocked);
+}
+
+#[actix_rt::test]
+async fn validate_concurrent_valid_attempts_are_not_blocked() {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let store = InMemoryLoginAttemptStore::new();
+    let brute_force_protection = BruteForceProtection::new(Box::new(store.clone()));
+
+    let blocked = call_concurrently(brute_force_protection, &calls, "test:pw", 10).await;
+
+    assert_eq!(10, calls.load(Ordering::SeqCst));
+    assert_eq!(0, blocked);
+    assert!(store.is_empty());
+}
+
+#[actix_rt::test]
+async fn validate_abandoned_reservations_are_discarded() {
+    let brute_force_protection =
+        BruteForceProtection::new(Box::new(InMemoryLoginAttemptStore::new()))
+            .with_max_pending_attempts(1, Duration::from_millis(20));
+    let key = LoginAttemptKey {
+        username: "test".to_string(),
+        client_ip: None,
+    };
+
+    assert_eq!(None, brute_force_protection.reserve_attempt(&key).await);
+    assert!(brute_force_protection.reserve_attempt(&key).await.is_some());
+    actix_rt::time::sleep(Duration::from_millis(30)).await;
+    assert_eq!(None, brute_force_protection.reserve_attempt(&key).await);
+}
+
+#[actix_rt::test]
+async fn validate_in_memory_store_is_bounded() {
+    let store = InMemoryLoginAttemptStore::new().with_max_entries(100);
+    let brute_force_protection = BruteForceProtection::new(Box::new(store.clone()));
+    let key = |index: usize| LoginAttemptKey {
+        username: format!("user-{}", index),
+        client_ip: None,
+    };
+
+    for index in 0..250 {
+        brute_force_protection.record_failure(&key(index)).await;
+        assert!(store.len() <= 100);
+    }
+
+    // The most recent failures are kept
+    assert!(store.find_attempts(&key(249)).await.is_some());
+}
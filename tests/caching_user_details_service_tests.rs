@@ -0,0 +1,282 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+
+use actix_web_security::authentication::scheme::basic::caching_user_details_service::CachingBasicUserDetailsService;
+use actix_web_security::authentication::scheme::basic::user_details_service::BasicUserDetailsService;
+use actix_web_security::authentication::scheme::bearer::jwt::caching_user_details_service::CachingJwtUserDetailsService;
+use actix_web_security::authentication::scheme::bearer::jwt::default_jwt::DefaultJwt;
+use actix_web_security::authentication::scheme::bearer::jwt::token::scopes::Scopes;
+use actix_web_security::authentication::scheme::bearer::jwt::token::Claims;
+use actix_web_security::authentication::scheme::bearer::jwt::user_details_service::JwtUserDetailsService;
+use actix_web_security::user_details::UserDetails;
+
+use common::User;
+
+mod common;
+
+/// Test user details service counting the lookups. Only the user `test` (with password `pw`) exists.
+#[derive(Clone, Default)]
+struct CountingUserDetailsService {
+    lookups: Arc<AtomicUsize>,
+}
+
+impl CountingUserDetailsService {
+    fn lookups(&self) -> usize {
+        self.lookups.load(Ordering::SeqCst)
+    }
+
+    fn find(&self, username: Option<&str>) -> Option<Box<dyn UserDetails>> {
+        self.lookups.fetch_add(1, Ordering::SeqCst);
+        match username {
+            Some("test") => Some(Box::new(User {
+                username: "test".to_string(),
+            })),
+            _ => None,
+        }
+    }
+}
+
+#[async_trait]
+impl JwtUserDetailsService for CountingUserDetailsService {
+    #[allow(clippy::borrowed_box)]
+    async fn find_user(&self, token: &Box<dyn Claims>) -> Option<Box<dyn UserDetails>> {
+        self.find(token.subject())
+    }
+}
+
+#[async_trait]
+impl BasicUserDetailsService for CountingUserDetailsService {
+    async fn find_user(&self, username: &str, password: &str) -> Option<Box<dyn UserDetails>> {
+        if password == "pw" {
+            self.find(Some(username))
+        } else {
+            self.find(None)
+        }
+    }
+}
+
+fn claims(issuer: Option<&str>, subject: Option<&str>) -> Box<dyn Claims> {
+    Box::new(DefaultJwt {
+        iss: issuer.map(String::from),
+        sub: subject.map(String::from),
+        ..DefaultJwt::default()
+    })
+}
+
+fn username(user_details: Option<Box<dyn UserDetails>>) -> Option<String> {
+    user_details.map(|user_details| {
+        user_details
+            .downcast_ref::<User>()
+            .expect("user expected")
+            .username
+            .clone()
+    })
+}
+
+#[actix_rt::test]
+async fn validate_jwt_user_details_are_cached_by_subject() {
+    let delegate = CountingUserDetailsService::default();
+    let service = CachingJwtUserDetailsService::new(Box::new(delegate.clone()));
+
+    for _ in 0..3 {
+        assert_eq!(
+            Some("test".to_string()),
+            username(service.find_user(&claims(None, Some("test"))).await)
+        );
+    }
+    assert_eq!(1, delegate.lookups());
+
+    // Other issuers are cached separately
+    service
+        .clone()
+        .find_user(&claims(Some("https://issuer"), Some("test")))
+        .await;
+    assert_eq!(2, delegate.lookups());
+
+    // Tokens without subject are not cached
+    for _ in 0..2 {
+        assert_eq!(None, username(service.find_user(&claims(None, None)).await));
+    }
+    assert_eq!(4, delegate.lookups());
+}
+
+#[actix_rt::test]
+async fn validate_jwt_user_details_eviction() {
+    let delegate = CountingUserDetailsService::default();
+    let service = CachingJwtUserDetailsService::new(Box::new(delegate.clone()));
+
+    service.find_user(&claims(None, Some("test"))).await;
+    service
+        .find_user(&claims(Some("https://issuer"), Some("test")))
+        .await;
+    assert_eq!(2, delegate.lookups());
+
+    // The cache is shared between clones
+    service.clone().evict("test");
+    service.find_user(&claims(None, Some("test"))).await;
+    service
+        .find_user(&claims(Some("https://issuer"), Some("test")))
+        .await;
+    assert_eq!(4, delegate.lookups());
+
+    service.evict_all();
+    service.find_user(&claims(None, Some("test"))).await;
+    assert_eq!(5, delegate.lookups());
+}
+
+#[actix_rt::test]
+async fn validate_jwt_user_details_expire() {
+    let delegate = CountingUserDetailsService::default();
+    let service = CachingJwtUserDetailsService::new(Box::new(delegate.clone()))
+        .with_time_to_live(Duration::from_millis(50));
+
+    service.find_user(&claims(None, Some("test"))).await;
+    service.find_user(&claims(None, Some("test"))).await;
+    assert_eq!(1, delegate.lookups());
+
+    actix_rt::time::sleep(Duration::from_millis(100)).await;
+    service.find_user(&claims(None, Some("test"))).await;
+    assert_eq!(2, delegate.lookups());
+}
+
+#[actix_rt::test]
+async fn validate_negative_caching() {
+    let delegate = CountingUserDetailsService::default();
+    let service = CachingJwtUserDetailsService::new(Box::new(delegate.clone()));
+
+    service.find_user(&claims(None, Some("unknown"))).await;
+    service.find_user(&claims(None, Some("unknown"))).await;
+    assert_eq!(2, delegate.lookups());
+
+    let delegate = CountingUserDetailsService::default();
+    let service = CachingJwtUserDetailsService::new(Box::new(delegate.clone()))
+        .with_negative_time_to_live(Duration::from_secs(60));
+
+    assert_eq!(
+        None,
+        username(service.find_user(&claims(None, Some("unknown"))).await)
+    );
+    assert_eq!(
+        None,
+        username(service.find_user(&claims(None, Some("unknown"))).await)
+    );
+    assert_eq!(1, delegate.lookups());
+}
+
+#[actix_rt::test]
+async fn validate_additional_cache_key() {
+    let delegate = CountingUserDetailsService::default();
+    let service = CachingJwtUserDetailsService::new(Box::new(delegate.clone()))
+        .with_additional_cache_key(|token| Some(token.scopes().to_delimited()));
+    let scoped_claims = |scope: &str| -> Box<dyn Claims> {
+        Box::new(DefaultJwt {
+            sub: Some("test".to_string()),
+            scope: Some(Scopes::from_delimited(scope)),
+            ..DefaultJwt::default()
+        })
+    };
+
+    service.find_user(&scoped_claims("read write")).await;
+    service.find_user(&scoped_claims("write read")).await;
+    assert_eq!(1, delegate.lookups());
+
+    // A token of the same subject with other scopes is not answered from the cache
+    service.find_user(&scoped_claims("read")).await;
+    assert_eq!(2, delegate.lookups());
+
+    service.evict("test");
+    service.find_user(&scoped_claims("read")).await;
+    assert_eq!(3, delegate.lookups());
+}
+
+#[actix_rt::test]
+async fn validate_max_size() {
+    let delegate = CountingUserDetailsService::default();
+    let service = CachingJwtUserDetailsService::new(Box::new(delegate.clone()))
+        .with_time_to_live(Duration::from_secs(60))
+        .with_negative_time_to_live(Duration::from_secs(300))
+        .with_max_size(2);
+
+    service.find_user(&claims(None, Some("test"))).await;
+    service.find_user(&claims(None, Some("first"))).await;
+    service.find_user(&claims(None, Some("second"))).await;
+    assert_eq!(3, delegate.lookups());
+
+    // The entry expiring first has been replaced, the others are still cached
+    service.find_user(&claims(None, Some("first"))).await;
+    service.find_user(&claims(None, Some("second"))).await;
+    assert_eq!(3, delegate.lookups());
+    service.find_user(&claims(None, Some("test"))).await;
+    assert_eq!(4, delegate.lookups());
+}
+
+#[actix_rt::test]
+async fn validate_full_cache_evicts_entries_expiring_first() {
+    let delegate = CountingUserDetailsService::default();
+    let service = CachingJwtUserDetailsService::new(Box::new(delegate.clone()))
+        .with_time_to_live(Duration::from_secs(60))
+        .with_negative_time_to_live(Duration::from_secs(60))
+        .with_max_size(20);
+
+    let users: Vec<String> = (0..20).map(|i| format!("user{}", i)).collect();
+    for user in &users {
+        service.find_user(&claims(None, Some(user))).await;
+    }
+    service.find_user(&claims(None, Some("test"))).await;
+    assert_eq!(21, delegate.lookups());
+
+    // The entries expiring first have been evicted together, the others are still cached
+    for user in &users[3..] {
+        service.find_user(&claims(None, Some(user))).await;
+    }
+    service.find_user(&claims(None, Some("test"))).await;
+    assert_eq!(21, delegate.lookups());
+    for user in &users[..3] {
+        service.find_user(&claims(None, Some(user))).await;
+    }
+    assert_eq!(24, delegate.lookups());
+}
+
+#[actix_rt::test]
+async fn validate_basic_user_details_are_cached_by_credentials() {
+    let delegate = CountingUserDetailsService::default();
+    let service = CachingBasicUserDetailsService::new(Box::new(delegate.clone()));
+
+    for _ in 0..3 {
+        assert_eq!(
+            Some("test".to_string()),
+            username(service.find_user("test", "pw").await)
+        );
+    }
+    assert_eq!(1, delegate.lookups());
+
+    // A different password is never answered from the cache of the correct password
+    assert_eq!(None, username(service.find_user("test", "wrong").await));
+    assert_eq!(None, username(service.find_user("test", "wrong").await));
+    assert_eq!(3, delegate.lookups());
+
+    service.evict("test");
+    assert_eq!(
+        Some("test".to_string()),
+        username(service.find_user("test", "pw").await)
+    );
+    assert_eq!(4, delegate.lookups());
+}
+
+#[actix_rt::test]
+async fn validate_basic_negative_caching() {
+    let delegate = CountingUserDetailsService::default();
+    let service = CachingBasicUserDetailsService::new(Box::new(delegate.clone()))
+        .with_negative_time_to_live(Duration::from_secs(60));
+
+    assert_eq!(None, username(service.find_user("test", "wrong").await));
+    assert_eq!(None, username(service.find_user("test", "wrong").await));
+    assert_eq!(1, delegate.lookups());
+
+    service.evict_all();
+    assert_eq!(None, username(service.find_user("test", "wrong").await));
+    assert_eq!(2, delegate.lookups());
+}
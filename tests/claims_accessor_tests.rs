@@ -0,0 +1,72 @@
+use serde_json::json;
+
+use actix_web_security::authentication::scheme::bearer::jwt::access_token_jwt::AccessTokenJwt;
+use actix_web_security::authentication::scheme::bearer::jwt::default_jwt::DefaultJwt;
+use actix_web_security::authentication::scheme::bearer::jwt::token::audience::Audience;
+use actix_web_security::authentication::scheme::bearer::jwt::token::Claims;
+
+fn boxed_claims(claims: serde_json::Value) -> Box<dyn Claims> {
+    Box::new(serde_json::from_value::<DefaultJwt>(claims).expect("claims expected"))
+}
+
+#[test]
+fn validate_registered_claims_of_default_jwt() {
+    let claims = boxed_claims(json!({
+        "iss": "https://authorization-server",
+        "sub": "test",
+        "aud": "orders-api",
+        "exp": 10000000000u64,
+        "nbf": 1600000000,
+        "iat": 1600000000,
+        "jti": "a8f5f167"
+    }));
+
+    assert_eq!(Some("https://authorization-server"), claims.issuer());
+    assert_eq!(Some("test"), claims.subject());
+    assert_eq!(
+        Some(Audience::Single("orders-api".to_string())),
+        claims.audience()
+    );
+    assert_eq!(Some(10000000000), claims.expiration_time());
+    assert_eq!(Some(1600000000), claims.not_before());
+    assert_eq!(Some(1600000000), claims.issued_at());
+    assert_eq!(Some("a8f5f167"), claims.jwt_id());
+}
+
+#[test]
+fn validate_audience_array_and_private_claims_of_default_jwt() {
+    let claims = boxed_claims(json!({
+        "sub": "test",
+        "aud": ["orders-api", "billing-api"],
+        "tenant": "tenant-a"
+    }));
+
+    assert!(claims
+        .audience()
+        .expect("audience expected")
+        .contains("billing-api"));
+    assert_eq!(Some(json!("tenant-a")), claims.get("tenant"));
+    assert_eq!(Some(json!("test")), claims.get("sub"));
+    assert_eq!(None, claims.get("iss"));
+    assert_eq!(None, claims.issuer());
+}
+
+#[test]
+fn validate_registered_claims_of_access_token_jwt() {
+    let claims: Box<dyn Claims> = Box::new(
+        serde_json::from_value::<AccessTokenJwt>(json!({
+            "iss": "https://authorization-server",
+            "exp": 10000000000u64,
+            "aud": "orders-api",
+            "sub": "test",
+            "client_id": "client",
+            "iat": 1600000000,
+            "jti": "a8f5f167"
+        }))
+        .expect("claims expected"),
+    );
+
+    assert_eq!(Some("test"), claims.subject());
+    assert_eq!(None, claims.not_before());
+    assert_eq!(Some(json!("client")), claims.get("client_id"));
+}
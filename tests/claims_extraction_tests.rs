@@ -0,0 +1,141 @@
+use actix_web::dev::Service;
+use actix_web::http::header;
+use actix_web::{get, test, App, HttpResponse, Responder};
+use async_trait::async_trait;
+use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
+use serde_json::json;
+
+use actix_web_security::authentication::endpoint_matcher::AllEndpointsMatcher;
+use actix_web_security::authentication::error::error_type::AuthenticationError;
+use actix_web_security::authentication::middleware::HttpAuthenticationMiddleware;
+use actix_web_security::authentication::scheme::bearer::jwt::access_token_jwt::AccessTokenJwt;
+use actix_web_security::authentication::scheme::bearer::jwt::authentication_provider::JwtAuthenticationProvider;
+use actix_web_security::authentication::scheme::bearer::jwt::default_jwt::DefaultJwt;
+use actix_web_security::authentication::scheme::bearer::jwt::header_extractor::BearerAuthenticationExtractor;
+use actix_web_security::authentication::scheme::bearer::jwt::request_extension::{
+    BearerToken, Claims,
+};
+use actix_web_security::authentication::scheme::bearer::jwt::token;
+use actix_web_security::authentication::scheme::bearer::jwt::token::decoder::TokenDecoder;
+use actix_web_security::authentication::scheme::bearer::jwt::user_details_service::JwtUserDetailsService;
+use actix_web_security::authentication::ProviderManager;
+use actix_web_security::user_details::UserDetails;
+
+use common::deserialize_json;
+use common::User;
+
+mod common;
+
+#[derive(Clone)]
+struct JwtUserDetailsServiceImpl {}
+
+#[async_trait]
+impl JwtUserDetailsService for JwtUserDetailsServiceImpl {
+    async fn find_user(&self, token: &Box<dyn token::Claims>) -> Option<Box<dyn UserDetails>> {
+        token
+            .downcast_ref::<DefaultJwt>()
+            .and_then(|claims| claims.sub.clone())
+            .map(|sub| Box::new(User { username: sub }) as Box<dyn UserDetails>)
+    }
+}
+
+#[derive(Clone)]
+struct SimpleTokenDecoder {}
+
+impl TokenDecoder<DefaultJwt> for SimpleTokenDecoder {
+    fn decode_token(&self, token: &str) -> Result<Box<DefaultJwt>, AuthenticationError> {
+        let key = &DecodingKey::from_secret("secret".as_ref());
+        match decode::<DefaultJwt>(token, key, &Validation::default()) {
+            Ok(token_data) => Ok(Box::new(token_data.claims)),
+            Err(_) => Err(AuthenticationError::InvalidToken),
+        }
+    }
+}
+
+#[get("/claims")]
+async fn claims_endpoint(
+    user: User,
+    claims: Claims<DefaultJwt>,
+    bearer_token: BearerToken,
+) -> impl Responder {
+    HttpResponse::Ok().json(json!({
+        "username": user.username,
+        "tenant": claims.additional_claims["tenant"],
+        "token": bearer_token.as_str(),
+    }))
+}
+
+#[get("/access-token-claims")]
+async fn access_token_claims_endpoint(claims: Claims<AccessTokenJwt>) -> impl Responder {
+    HttpResponse::Ok().json(&claims.client_id)
+}
+
+fn init_middleware(
+) -> HttpAuthenticationMiddleware<BearerAuthenticationExtractor<DefaultJwt>, AllEndpointsMatcher> {
+    let authentication_provider =
+        JwtAuthenticationProvider::new(Box::new(JwtUserDetailsServiceImpl {}));
+
+    let provider_manager = ProviderManager::new(vec![Box::new(authentication_provider)]);
+
+    let authentication_extractor =
+        BearerAuthenticationExtractor::new(vec![Box::new(SimpleTokenDecoder {})]);
+
+    HttpAuthenticationMiddleware::new(
+        provider_manager,
+        Box::new(authentication_extractor),
+        Box::new(AllEndpointsMatcher::new()),
+    )
+}
+
+fn create_token() -> String {
+    let claims = json!({"sub": "test", "exp": 10000000000u64, "tenant": "tenant-a"});
+    let key = &EncodingKey::from_secret("secret".as_ref());
+    encode(&Header::default(), &claims, key).expect("Token couldn't be encoded")
+}
+
+#[actix_rt::test]
+async fn validate_claims_and_bearer_token_are_injected() {
+    let mut service =
+        test::init_service(App::new().wrap(init_middleware()).service(claims_endpoint)).await;
+
+    let token = create_token();
+    let req = test::TestRequest::get()
+        .uri("/claims")
+        .header(header::AUTHORIZATION, format!("Bearer {}", token))
+        .to_request();
+
+    match service.call(req).await {
+        Ok(mut service_response) => {
+            assert!(&service_response.status().is_success());
+            assert_eq!(
+                json!({"username": "test", "tenant": "tenant-a", "token": token}),
+                deserialize_json::<serde_json::Value, _>(&mut service_response.take_body()).await
+            );
+        }
+        Err(e) => panic!("Error occurred: {}", e),
+    }
+}
+
+#[actix_rt::test]
+async fn validate_claims_of_other_type_are_not_injected() {
+    let mut service = test::init_service(
+        App::new()
+            .wrap(init_middleware())
+            .service(access_token_claims_endpoint),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/access-token-claims")
+        .header(header::AUTHORIZATION, format!("Bearer {}", create_token()))
+        .to_request();
+
+    let service_response = service.call(req).await.expect("response expected");
+    assert_eq!(
+        Some(&AuthenticationError::UsernameNotFound),
+        service_response
+            .response()
+            .error()
+            .and_then(|e| e.as_error::<AuthenticationError>())
+    );
+}
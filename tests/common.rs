@@ -1,5 +1,9 @@
+#![allow(dead_code)]
+
 use std::future::Future;
 use std::pin::Pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
 
 use actix_http::body::ResponseBody;
 use actix_web::dev::PayloadStream;
@@ -10,9 +14,11 @@ use futures_util::StreamExt;
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 
-use actix_web_security::{
-    authentication::error::error_type::AuthenticationError, user_details::UserDetails,
+use actix_web_security::authentication::error::error_type::{
+    AuthenticationError, PasswordEncoderError,
 };
+use actix_web_security::authentication::scheme::basic::password_encoder::PasswordEncoder;
+use actix_web_security::user_details::UserDetails;
 
 #[derive(Clone, Eq, PartialEq, Debug, Deserialize, Serialize)]
 pub struct User {
@@ -62,3 +68,39 @@ impl FromRequest for User {
         })
     }
 }
+
+/// Test encoder storing the reversed password with a `rev:` prefix and counting the verifications
+/// (not to be used in production). Passwords stored in plain text (`plain:` prefix) are accepted and upgraded.
+#[derive(Clone, Default)]
+pub struct ReversingPasswordEncoder {
+    verifications: Arc<AtomicUsize>,
+}
+
+impl ReversingPasswordEncoder {
+    pub fn new() -> ReversingPasswordEncoder {
+        ReversingPasswordEncoder::default()
+    }
+
+    pub fn verifications(&self) -> usize {
+        self.verifications.load(Ordering::SeqCst)
+    }
+}
+
+impl PasswordEncoder for ReversingPasswordEncoder {
+    fn encode(&self, raw_password: &str) -> Result<String, PasswordEncoderError> {
+        Ok(format!(
+            "rev:{}",
+            raw_password.chars().rev().collect::<String>()
+        ))
+    }
+
+    fn matches(&self, raw_password: &str, encoded_password: &str) -> bool {
+        self.verifications.fetch_add(1, Ordering::SeqCst);
+        self.encode(raw_password).unwrap() == encoded_password
+            || format!("plain:{}", raw_password) == encoded_password
+    }
+
+    fn upgrade_encoding(&self, encoded_password: &str) -> bool {
+        !encoded_password.starts_with("rev:")
+    }
+}
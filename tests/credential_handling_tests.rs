@@ -0,0 +1,127 @@
+use actix_web::http::header;
+use actix_web::test;
+
+use actix_web_security::authentication::error::error_type::AuthenticationError;
+use actix_web_security::authentication::scheme::authentication::Authentication;
+use actix_web_security::authentication::scheme::authentication_provider::AuthenticationProvider;
+use actix_web_security::authentication::scheme::basic::header_extractor::BasicAuthenticationExtractor;
+use actix_web_security::authentication::scheme::basic::in_memory_user_details_service::InMemoryUserDetailsService;
+use actix_web_security::authentication::scheme::basic::password_authentication_provider::PasswordAuthenticationProvider;
+use actix_web_security::authentication::scheme::basic::secret::{constant_time_eq, Secret};
+use actix_web_security::authentication::scheme::basic::user_details_service::BasicUserDetailsService;
+use actix_web_security::authentication::scheme::basic::BasicAuthentication;
+use actix_web_security::authentication::scheme::header_extractor::AuthorizationHeaderExtractor;
+
+use common::ReversingPasswordEncoder;
+
+mod common;
+
+#[test]
+fn validate_secret_is_redacted_in_debug_output() {
+    let secret = Secret::new("top-secret".to_string());
+
+    assert_eq!("top-secret", secret.expose_secret());
+    assert!(!format!("{:?}", secret).contains("top-secret"));
+}
+
+#[test]
+fn validate_secret_comparison() {
+    assert_eq!(
+        Secret::new("password".to_string()),
+        Secret::new("password".to_string())
+    );
+    assert_ne!(
+        Secret::new("password".to_string()),
+        Secret::new("passwort".to_string())
+    );
+    assert_ne!(
+        Secret::new("password".to_string()),
+        Secret::new("password1".to_string())
+    );
+    assert!(constant_time_eq(b"hash", b"hash"));
+    assert!(!constant_time_eq(b"hash", b"hasH"));
+    assert!(!constant_time_eq(b"hash", b""));
+}
+
+#[actix_rt::test]
+async fn validate_extracted_password_is_redacted_in_debug_output() {
+    let req = test::TestRequest::get()
+        .header(
+            header::AUTHORIZATION,
+            format!("Basic {}", base64::encode("admin:top-secret")),
+        )
+        .to_http_request();
+
+    let authentication = BasicAuthenticationExtractor::new()
+        .extract_token(req.headers())
+        .await
+        .expect("authentication expected");
+    let basic_auth = authentication
+        .downcast_ref::<BasicAuthentication>()
+        .expect("basic authentication expected");
+
+    assert_eq!("admin", basic_auth.username);
+    assert_eq!("top-secret", basic_auth.password.expose_secret());
+    let debug_output = format!("{:?}", basic_auth);
+    assert!(debug_output.contains("admin"));
+    assert!(!debug_output.contains("top-secret"));
+}
+
+#[actix_rt::test]
+async fn validate_dummy_password_verification_for_unknown_users_in_password_authentication_provider(
+) {
+    let password_encoder = ReversingPasswordEncoder::new();
+    let authentication_provider = PasswordAuthenticationProvider::new(
+        Box::new(
+            InMemoryUserDetailsService::new(Box::new(password_encoder.clone())).with_user(
+                "admin",
+                "rev:nimda",
+                vec![],
+            ),
+        ),
+        Box::new(password_encoder.clone()),
+    );
+
+    let authentication: Box<dyn Authentication> = Box::new(BasicAuthentication {
+        username: "unknown".to_string(),
+        password: Secret::new("admin".to_string()),
+    });
+    assert_eq!(
+        Err(AuthenticationError::UsernameNotFound),
+        authentication_provider
+            .authenticate(&authentication)
+            .await
+            .map(|_| ())
+    );
+    assert_eq!(1, password_encoder.verifications());
+
+    let authentication: Box<dyn Authentication> = Box::new(BasicAuthentication {
+        username: "admin".to_string(),
+        password: Secret::new("admin".to_string()),
+    });
+    assert!(authentication_provider
+        .authenticate(&authentication)
+        .await
+        .is_ok());
+    assert_eq!(2, password_encoder.verifications());
+}
+
+#[actix_rt::test]
+async fn validate_dummy_password_verification_for_unknown_users_in_in_memory_user_details_service()
+{
+    let password_encoder = ReversingPasswordEncoder::new();
+    let user_details_service = InMemoryUserDetailsService::new(Box::new(password_encoder.clone()))
+        .with_user("admin", "rev:nimda", vec![]);
+
+    assert!(user_details_service
+        .find_user("unknown", "admin")
+        .await
+        .is_none());
+    assert_eq!(1, password_encoder.verifications());
+
+    assert!(user_details_service
+        .find_user("admin", "admin")
+        .await
+        .is_some());
+    assert_eq!(2, password_encoder.verifications());
+}
@@ -0,0 +1,96 @@
+#![cfg(feature = "derive")]
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use actix_web_security::authentication::scheme::bearer::jwt::token::audience::Audience;
+use actix_web_security::authentication::scheme::bearer::jwt::token::scopes::Scopes;
+use actix_web_security::authentication::scheme::bearer::jwt::token::Claims;
+
+#[derive(Clone, Debug, Serialize, Deserialize, Claims)]
+struct CustomClaims {
+    iss: String,
+    sub: Option<String>,
+    aud: Vec<String>,
+    exp: u64,
+    iat: Option<u64>,
+    scope: Option<Scopes>,
+    tenant: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Claims)]
+struct RenamedClaims {
+    #[serde(rename = "iss")]
+    issuer: String,
+    #[serde(rename = "tenant_subject")]
+    sub: String,
+    #[serde(rename(serialize = "subject", deserialize = "sub"), default)]
+    subject: Option<String>,
+    exp: i64,
+    nbf: Option<i64>,
+}
+
+#[test]
+fn validate_derived_claims() {
+    let claims: Box<dyn Claims> = Box::new(
+        serde_json::from_value::<CustomClaims>(json!({
+            "iss": "https://authorization-server",
+            "sub": "test",
+            "aud": ["orders-api"],
+            "exp": 10000000000u64,
+            "scope": "orders:read orders:write",
+            "tenant": "tenant-a"
+        }))
+        .expect("claims expected"),
+    );
+
+    assert_eq!(Some("https://authorization-server"), claims.issuer());
+    assert_eq!(Some("test"), claims.subject());
+    assert_eq!(
+        Some(Audience::Multiple(vec!["orders-api".to_string()])),
+        claims.audience()
+    );
+    assert_eq!(Some(10000000000), claims.expiration_time());
+    assert_eq!(None, claims.issued_at());
+    assert_eq!(None, claims.jwt_id());
+    assert!(claims
+        .scopes()
+        .contains_all(&["orders:read", "orders:write"]));
+    assert_eq!(Some(json!("tenant-a")), claims.get("tenant"));
+}
+
+#[test]
+fn validate_derived_claims_with_renamed_fields() {
+    let claims: Box<dyn Claims> = Box::new(
+        serde_json::from_value::<RenamedClaims>(json!({
+            "iss": "https://authorization-server",
+            "tenant_subject": "tenant-a",
+            "sub": "test",
+            "exp": 10000000000u64,
+            "nbf": 1600000000
+        }))
+        .expect("claims expected"),
+    );
+
+    assert_eq!(Some("https://authorization-server"), claims.issuer());
+    assert_eq!(Some("test"), claims.subject());
+    assert_eq!(Some(10000000000), claims.expiration_time());
+    assert_eq!(Some(1600000000), claims.not_before());
+}
+
+#[test]
+fn validate_derived_claims_with_negative_values() {
+    let claims: Box<dyn Claims> = Box::new(
+        serde_json::from_value::<RenamedClaims>(json!({
+            "iss": "https://authorization-server",
+            "tenant_subject": "tenant-a",
+            "exp": -1,
+            "nbf": -1
+        }))
+        .expect("claims expected"),
+    );
+
+    assert_eq!(None, claims.subject());
+    assert_eq!(None, claims.expiration_time());
+    assert_eq!(None, claims.not_before());
+}
@@ -0,0 +1,173 @@
+#![cfg(feature = "htpasswd")]
+
+use std::fs;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use actix_web::dev::Service;
+use actix_web::http::header;
+use actix_web::{get, test, App, HttpResponse, Responder};
+use tempfile::NamedTempFile;
+
+use actix_web_security::authentication::endpoint_matcher::AllEndpointsMatcher;
+use actix_web_security::authentication::error::error_type::AuthenticationError;
+use actix_web_security::authentication::middleware::HttpAuthenticationMiddleware;
+use actix_web_security::authentication::scheme::basic::authentication_provider::BasicAuthenticationProvider;
+use actix_web_security::authentication::scheme::basic::basic_user::BasicUser;
+use actix_web_security::authentication::scheme::basic::header_extractor::BasicAuthenticationExtractor;
+use actix_web_security::authentication::scheme::basic::htpasswd_user_details_service::HtpasswdUserDetailsService;
+use actix_web_security::authentication::scheme::basic::password_encoder::htpasswd_encoder::HtpasswdPasswordEncoder;
+use actix_web_security::authentication::scheme::basic::password_encoder::PasswordEncoder;
+use actix_web_security::authentication::ProviderManager;
+
+// Generated with htpasswd -B (bcrypt), -s (SHA-1) and -m (apr1). The password of all users is "password".
+const HTPASSWD: &str = "# operator accounts
+bcrypt:$2y$05$NUPJfiS1PucNzwu5R4JetO27zIq2BtwHy.SK/dKbtL.qQ3o5a9LBC
+sha:{SHA}W6ph5Mm5Pz8GgiULbPgzG37mj9g=
+
+apr1:$apr1$r31.....$ARC3pREO82RIm0aQ2zszC0
+plain:password
+";
+
+#[get("/test")]
+async fn test_endpoint(user: BasicUser) -> impl Responder {
+    HttpResponse::Ok().body(user.username)
+}
+
+fn write_htpasswd_file(content: &str) -> NamedTempFile {
+    let file = NamedTempFile::new().expect("Failed to create temporary file");
+    fs::write(file.path(), content).expect("Failed to write htpasswd file");
+    file
+}
+
+async fn call_endpoint(
+    user_details_service: HtpasswdUserDetailsService,
+    credentials: &str,
+) -> Result<(), AuthenticationError> {
+    let middleware = HttpAuthenticationMiddleware::new(
+        ProviderManager::new(vec![Box::new(BasicAuthenticationProvider::new(Box::new(
+            user_details_service,
+        )))]),
+        Box::new(BasicAuthenticationExtractor::new()),
+        Box::new(AllEndpointsMatcher::new()),
+    );
+    let mut service = test::init_service(App::new().wrap(middleware).service(test_endpoint)).await;
+
+    let req = test::TestRequest::get()
+        .uri("/test")
+        .header(
+            header::AUTHORIZATION,
+            format!("Basic {}", base64::encode(credentials)),
+        )
+        .to_request();
+
+    match service.call(req).await {
+        Ok(service_response) => {
+            assert!(&service_response.status().is_success());
+            Ok(())
+        }
+        Err(e) => Err(e
+            .as_error::<AuthenticationError>()
+            .expect("error expected")
+            .clone()),
+    }
+}
+
+#[actix_rt::test]
+async fn validate_htpasswd_user_details_service_succeeds() {
+    let file = write_htpasswd_file(HTPASSWD);
+    let user_details_service = HtpasswdUserDetailsService::from_file(file.path()).unwrap();
+
+    for username in &["bcrypt", "sha", "apr1"] {
+        assert_eq!(
+            Ok(()),
+            call_endpoint(
+                user_details_service.clone(),
+                &format!("{}:password", username)
+            )
+            .await
+        );
+    }
+}
+
+#[actix_rt::test]
+async fn validate_htpasswd_user_details_service_with_invalid_credentials() {
+    let file = write_htpasswd_file(HTPASSWD);
+    let user_details_service = HtpasswdUserDetailsService::from_file(file.path()).unwrap();
+
+    for credentials in &[
+        "bcrypt:wrong",
+        "sha:wrong",
+        "apr1:wrong",
+        "plain:password",
+        "unknown:password",
+    ] {
+        assert_eq!(
+            Err(AuthenticationError::UsernameNotFound),
+            call_endpoint(user_details_service.clone(), credentials).await
+        );
+    }
+}
+
+#[actix_rt::test]
+async fn validate_htpasswd_file_is_reloaded() {
+    let file = write_htpasswd_file("sha:{SHA}W6ph5Mm5Pz8GgiULbPgzG37mj9g=\n");
+    let user_details_service = HtpasswdUserDetailsService::from_file(file.path()).unwrap();
+    let encoder = HtpasswdPasswordEncoder::new();
+
+    assert!(call_endpoint(user_details_service.clone(), "added:secret")
+        .await
+        .is_err());
+
+    fs::write(
+        file.path(),
+        format!("added:{}\n", encoder.encode("secret").unwrap()),
+    )
+    .unwrap();
+    user_details_service.reload().unwrap();
+
+    assert!(call_endpoint(user_details_service.clone(), "added:secret")
+        .await
+        .is_ok());
+    assert!(call_endpoint(user_details_service, "sha:password")
+        .await
+        .is_err());
+}
+
+#[actix_rt::test]
+async fn validate_htpasswd_file_is_watched() {
+    let file = write_htpasswd_file("");
+    let user_details_service = HtpasswdUserDetailsService::from_file(file.path())
+        .unwrap()
+        .with_file_watch(Duration::from_millis(10));
+
+    // Ensure that the modification time differs on file systems with a coarse timestamp resolution
+    thread::sleep(Duration::from_millis(1100));
+    fs::write(file.path(), "sha:{SHA}W6ph5Mm5Pz8GgiULbPgzG37mj9g=\n").unwrap();
+
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while call_endpoint(user_details_service.clone(), "sha:password")
+        .await
+        .is_err()
+    {
+        assert!(Instant::now() < deadline, "htpasswd file not reloaded");
+        actix_rt::time::sleep(Duration::from_millis(10)).await;
+    }
+}
+
+#[test]
+fn validate_htpasswd_password_encoder() {
+    let encoder = HtpasswdPasswordEncoder::new();
+    let encoded_password = encoder.encode("password").unwrap();
+
+    assert!(encoded_password.starts_with("$2y$12$"));
+    assert!(encoder.matches("password", &encoded_password));
+    assert!(encoder.matches("myPassword", "$apr1$r31.....$HqJZimcKQFAMYayBlzkrA/"));
+    assert!(encoder.matches(
+        "a-much-longer-password-over-16",
+        "$apr1$abcdefgh$Qr.8AiuE3oW/AjCs.ezhl/"
+    ));
+    assert!(!encoder.upgrade_encoding(&encoded_password));
+    assert!(encoder.upgrade_encoding("{SHA}W6ph5Mm5Pz8GgiULbPgzG37mj9g="));
+    assert!(encoder.upgrade_encoding("$apr1$r31.....$HqJZimcKQFAMYayBlzkrA/"));
+}
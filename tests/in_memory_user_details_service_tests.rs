@@ -0,0 +1,108 @@
+use actix_web::dev::Service;
+use actix_web::http::header;
+use actix_web::{get, test, App, HttpResponse, Responder};
+use serde_json::{json, Value};
+
+use actix_web_security::authentication::endpoint_matcher::AllEndpointsMatcher;
+use actix_web_security::authentication::error::error_type::AuthenticationError;
+use actix_web_security::authentication::middleware::HttpAuthenticationMiddleware;
+use actix_web_security::authentication::scheme::authentication_provider::AuthenticationProvider;
+use actix_web_security::authentication::scheme::basic::authentication_provider::BasicAuthenticationProvider;
+use actix_web_security::authentication::scheme::basic::basic_user::BasicUser;
+use actix_web_security::authentication::scheme::basic::header_extractor::BasicAuthenticationExtractor;
+use actix_web_security::authentication::scheme::basic::in_memory_user_details_service::InMemoryUserDetailsService;
+use actix_web_security::authentication::scheme::basic::password_authentication_provider::PasswordAuthenticationProvider;
+use actix_web_security::authentication::ProviderManager;
+
+use common::{deserialize_json, ReversingPasswordEncoder};
+
+mod common;
+
+fn user_details_service() -> InMemoryUserDetailsService {
+    InMemoryUserDetailsService::new(Box::new(ReversingPasswordEncoder::new()))
+        .with_user("admin", "rev:nimda", vec!["ADMIN", "USER"])
+        .with_user("operator", "rev:rotarepo", vec![])
+}
+
+#[get("/test")]
+async fn test_endpoint(user: BasicUser) -> impl Responder {
+    HttpResponse::Ok().json(user)
+}
+
+async fn call_endpoint(
+    authentication_provider: Box<dyn AuthenticationProvider>,
+    credentials: &str,
+) -> Result<Value, AuthenticationError> {
+    let middleware = HttpAuthenticationMiddleware::new(
+        ProviderManager::new(vec![authentication_provider]),
+        Box::new(BasicAuthenticationExtractor::new()),
+        Box::new(AllEndpointsMatcher::new()),
+    );
+    let mut service = test::init_service(App::new().wrap(middleware).service(test_endpoint)).await;
+
+    let req = test::TestRequest::get()
+        .uri("/test")
+        .header(
+            header::AUTHORIZATION,
+            format!("Basic {}", base64::encode(credentials)),
+        )
+        .to_request();
+
+    match service.call(req).await {
+        Ok(mut service_response) => {
+            assert!(&service_response.status().is_success());
+            Ok(deserialize_json(&mut service_response.take_body()).await)
+        }
+        Err(e) => Err(e
+            .as_error::<AuthenticationError>()
+            .expect("error expected")
+            .clone()),
+    }
+}
+
+#[actix_rt::test]
+async fn validate_in_memory_user_details_service_succeeds() {
+    let authentication_provider =
+        BasicAuthenticationProvider::new(Box::new(user_details_service()));
+
+    assert_eq!(
+        Ok(json!({"username": "admin", "authorities": ["ADMIN", "USER"]})),
+        call_endpoint(Box::new(authentication_provider.clone()), "admin:admin").await
+    );
+    assert_eq!(
+        Ok(json!({"username": "operator", "authorities": []})),
+        call_endpoint(Box::new(authentication_provider), "operator:operator").await
+    );
+}
+
+#[actix_rt::test]
+async fn validate_in_memory_user_details_service_with_invalid_credentials() {
+    let authentication_provider =
+        BasicAuthenticationProvider::new(Box::new(user_details_service()));
+
+    assert_eq!(
+        Err(AuthenticationError::UsernameNotFound),
+        call_endpoint(Box::new(authentication_provider.clone()), "admin:nimda").await
+    );
+    assert_eq!(
+        Err(AuthenticationError::UsernameNotFound),
+        call_endpoint(Box::new(authentication_provider), "unknown:unknown").await
+    );
+}
+
+#[actix_rt::test]
+async fn validate_in_memory_user_store_succeeds() {
+    let authentication_provider = PasswordAuthenticationProvider::new(
+        Box::new(user_details_service()),
+        Box::new(ReversingPasswordEncoder::new()),
+    );
+
+    assert_eq!(
+        Ok(json!({"username": "admin", "authorities": ["ADMIN", "USER"]})),
+        call_endpoint(Box::new(authentication_provider.clone()), "admin:admin").await
+    );
+    assert_eq!(
+        Err(AuthenticationError::UsernameNotFound),
+        call_endpoint(Box::new(authentication_provider), "operator:admin").await
+    );
+}
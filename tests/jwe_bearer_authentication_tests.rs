@@ -0,0 +1,264 @@
+#![cfg(feature = "jwe")]
+
+use std::collections::HashMap;
+
+use actix_web::dev::Service;
+use actix_web::http::header;
+use actix_web::{get, test, App, HttpResponse, Responder};
+use aes_gcm::aead::{Aead, KeyInit, Payload};
+use aes_gcm::{Aes256Gcm, Nonce};
+use async_trait::async_trait;
+use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
+use p256::elliptic_curve::sec1::ToEncodedPoint;
+use p256::SecretKey;
+use rsa::pkcs8::DecodePrivateKey;
+use rsa::rand_core::{OsRng, RngCore};
+use rsa::{Oaep, RsaPrivateKey, RsaPublicKey};
+use serde_json::json;
+
+use actix_web_security::authentication::endpoint_matcher::AllEndpointsMatcher;
+use actix_web_security::authentication::error::error_type::AuthenticationError;
+use actix_web_security::authentication::middleware::HttpAuthenticationMiddleware;
+use actix_web_security::authentication::scheme::bearer::jwt::authentication_provider::JwtAuthenticationProvider;
+use actix_web_security::authentication::scheme::bearer::jwt::default_jwt::DefaultJwt;
+use actix_web_security::authentication::scheme::bearer::jwt::header_extractor::BearerAuthenticationExtractor;
+use actix_web_security::authentication::scheme::bearer::jwt::token::decoder::jwe_decoder::{
+    JweDecoder, JweDecryptionKey,
+};
+use actix_web_security::authentication::scheme::bearer::jwt::token::decoder::TokenDecoder;
+use actix_web_security::authentication::scheme::bearer::jwt::token::Claims;
+use actix_web_security::authentication::scheme::bearer::jwt::user_details_service::JwtUserDetailsService;
+use actix_web_security::authentication::ProviderManager;
+use actix_web_security::user_details::UserDetails;
+
+use common::deserialize_json;
+use common::User;
+
+mod common;
+
+const RSA_PRIVATE_KEY: &str = include_str!("resources/jwe_rsa_private_key.pem");
+
+#[derive(Clone)]
+struct JwtUserDetailsServiceImpl {}
+
+#[async_trait]
+impl JwtUserDetailsService for JwtUserDetailsServiceImpl {
+    async fn find_user(&self, token: &Box<dyn Claims>) -> Option<Box<dyn UserDetails>> {
+        token
+            .downcast_ref::<DefaultJwt>()
+            .and_then(|claims| claims.sub.clone())
+            .map(|sub| Box::new(User { username: sub }) as Box<dyn UserDetails>)
+    }
+}
+
+#[derive(Clone)]
+struct SimpleTokenDecoder {}
+
+impl TokenDecoder<DefaultJwt> for SimpleTokenDecoder {
+    fn decode_token(&self, token: &str) -> Result<Box<DefaultJwt>, AuthenticationError> {
+        let key = &DecodingKey::from_secret("secret".as_ref());
+        match decode::<DefaultJwt>(token, key, &Validation::default()) {
+            Ok(token_data) => Ok(Box::new(token_data.claims)),
+            Err(_) => Err(AuthenticationError::InvalidToken),
+        }
+    }
+}
+
+#[get("/test")]
+async fn test_endpoint(user: User) -> impl Responder {
+    HttpResponse::Ok().json(user)
+}
+
+fn init_middleware(
+    decryption_keys: Vec<JweDecryptionKey>,
+) -> HttpAuthenticationMiddleware<BearerAuthenticationExtractor<DefaultJwt>, AllEndpointsMatcher> {
+    let authentication_provider =
+        JwtAuthenticationProvider::new(Box::new(JwtUserDetailsServiceImpl {}));
+
+    let provider_manager = ProviderManager::new(vec![Box::new(authentication_provider)]);
+
+    let jwe_decoder = JweDecoder::new(decryption_keys, vec![Box::new(SimpleTokenDecoder {})]);
+
+    let authentication_extractor = BearerAuthenticationExtractor::new(vec![Box::new(jwe_decoder)]);
+
+    HttpAuthenticationMiddleware::new(
+        provider_manager,
+        Box::new(authentication_extractor),
+        Box::new(AllEndpointsMatcher::new()),
+    )
+}
+
+fn create_signed_token() -> String {
+    let claims = DefaultJwt {
+        iss: None,
+        sub: Some("test".to_string()),
+        aud: None,
+        exp: Some(10000000000),
+        nbf: None,
+        iat: None,
+        jti: None,
+        scope: None,
+        scp: None,
+        additional_claims: HashMap::new(),
+    };
+    let key = &EncodingKey::from_secret("secret".as_ref());
+    encode(&Header::default(), &claims, key).expect("Token couldn't be encoded")
+}
+
+fn base64url(value: &[u8]) -> String {
+    base64::encode_config(value, base64::URL_SAFE_NO_PAD)
+}
+
+fn encrypt(header: serde_json::Value, encrypted_key: &[u8], cek: &[u8], plaintext: &str) -> String {
+    let encoded_header = base64url(header.to_string().as_bytes());
+    let mut iv = [0u8; 12];
+    OsRng.fill_bytes(&mut iv);
+    let cipher = Aes256Gcm::new_from_slice(cek).unwrap();
+    let mut ciphertext = cipher
+        .encrypt(
+            Nonce::from_slice(&iv),
+            Payload {
+                msg: plaintext.as_bytes(),
+                aad: encoded_header.as_bytes(),
+            },
+        )
+        .unwrap();
+    let tag = ciphertext.split_off(ciphertext.len() - 16);
+    format!(
+        "{}.{}.{}.{}.{}",
+        encoded_header,
+        base64url(encrypted_key),
+        base64url(&iv),
+        base64url(&ciphertext),
+        base64url(&tag)
+    )
+}
+
+fn encrypt_rsa_oaep(key_id: &str, plaintext: &str) -> String {
+    let private_key = RsaPrivateKey::from_pkcs8_pem(RSA_PRIVATE_KEY).unwrap();
+    let mut cek = [0u8; 32];
+    OsRng.fill_bytes(&mut cek);
+    let encrypted_key = RsaPublicKey::from(&private_key)
+        .encrypt(&mut OsRng, Oaep::new::<sha2::Sha256>(), &cek)
+        .unwrap();
+    let header = json!({"alg": "RSA-OAEP-256", "enc": "A256GCM", "kid": key_id, "cty": "JWT"});
+    encrypt(header, &encrypted_key, &cek, plaintext)
+}
+
+fn encrypt_ecdh_es(recipient_key: &SecretKey, plaintext: &str) -> String {
+    let ephemeral_key = SecretKey::random(&mut OsRng);
+    let ephemeral_point = ephemeral_key.public_key().to_encoded_point(false);
+    let shared_secret = p256::ecdh::diffie_hellman(
+        ephemeral_key.to_nonzero_scalar(),
+        recipient_key.public_key().as_affine(),
+    );
+    let mut other_info = Vec::new();
+    for value in [b"A256GCM".as_ref(), b"", b""] {
+        other_info.extend((value.len() as u32).to_be_bytes());
+        other_info.extend(value);
+    }
+    other_info.extend(256u32.to_be_bytes());
+    let mut cek = [0u8; 32];
+    concat_kdf::derive_key_into::<sha2::Sha256>(
+        shared_secret.raw_secret_bytes(),
+        &other_info,
+        &mut cek,
+    )
+    .unwrap();
+    let header = json!({
+        "alg": "ECDH-ES",
+        "enc": "A256GCM",
+        "cty": "JWT",
+        "epk": {
+            "kty": "EC",
+            "crv": "P-256",
+            "x": base64url(ephemeral_point.x().unwrap()),
+            "y": base64url(ephemeral_point.y().unwrap())
+        }
+    });
+    encrypt(header, &[], &cek, plaintext)
+}
+
+async fn call_endpoint(
+    decryption_keys: Vec<JweDecryptionKey>,
+    token: String,
+) -> Result<User, AuthenticationError> {
+    let mut service = test::init_service(
+        App::new()
+            .wrap(init_middleware(decryption_keys))
+            .service(test_endpoint),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/test")
+        .header(header::AUTHORIZATION, format!("Bearer {}", token))
+        .to_request();
+
+    match service.call(req).await {
+        Ok(mut service_response) => {
+            assert!(&service_response.status().is_success());
+            Ok(deserialize_json(&mut service_response.take_body()).await)
+        }
+        Err(e) => Err(e
+            .as_error::<AuthenticationError>()
+            .expect("error expected")
+            .clone()),
+    }
+}
+
+fn rsa_decryption_key(key_id: &str) -> JweDecryptionKey {
+    JweDecryptionKey::rsa(
+        Some(key_id.to_string()),
+        RsaPrivateKey::from_pkcs8_pem(RSA_PRIVATE_KEY).unwrap(),
+    )
+}
+
+#[actix_rt::test]
+async fn validate_jwe_bearer_auth_with_rsa_oaep_succeeds() {
+    let token = encrypt_rsa_oaep("rsa-1", &create_signed_token());
+
+    assert_eq!(
+        Ok(User {
+            username: "test".to_string()
+        }),
+        call_endpoint(vec![rsa_decryption_key("rsa-1")], token).await
+    );
+}
+
+#[actix_rt::test]
+async fn validate_jwe_bearer_auth_with_ecdh_es_succeeds() {
+    let ec_key = SecretKey::random(&mut OsRng);
+    let token = encrypt_ecdh_es(&ec_key, &create_signed_token());
+
+    let decryption_keys = vec![
+        rsa_decryption_key("rsa-1"),
+        JweDecryptionKey::ec_p256(None, ec_key),
+    ];
+    assert_eq!(
+        Ok(User {
+            username: "test".to_string()
+        }),
+        call_endpoint(decryption_keys, token).await
+    );
+}
+
+#[actix_rt::test]
+async fn validate_jwe_bearer_auth_with_unknown_key_id() {
+    let token = encrypt_rsa_oaep("rsa-2", &create_signed_token());
+
+    assert_eq!(
+        Err(AuthenticationError::InvalidAuthentication),
+        call_endpoint(vec![rsa_decryption_key("rsa-1")], token).await
+    );
+}
+
+#[actix_rt::test]
+async fn validate_jwe_bearer_auth_without_nested_jws() {
+    let token = encrypt_rsa_oaep("rsa-1", r#"{"sub":"test","exp":10000000000}"#);
+
+    assert_eq!(
+        Err(AuthenticationError::InvalidAuthentication),
+        call_endpoint(vec![rsa_decryption_key("rsa-1")], token).await
+    );
+}
@@ -0,0 +1,156 @@
+use actix_web::dev::Service;
+use actix_web::http::header;
+use actix_web::{get, test, App, HttpResponse, Responder};
+use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
+use serde_json::json;
+
+use actix_web_security::authentication::endpoint_matcher::AllEndpointsMatcher;
+use actix_web_security::authentication::error::error_type::AuthenticationError;
+use actix_web_security::authentication::middleware::HttpAuthenticationMiddleware;
+use actix_web_security::authentication::scheme::bearer::jwt::default_jwt::DefaultJwt;
+use actix_web_security::authentication::scheme::bearer::jwt::header_extractor::BearerAuthenticationExtractor;
+use actix_web_security::authentication::scheme::bearer::jwt::principal::JwtPrincipal;
+use actix_web_security::authentication::scheme::bearer::jwt::principal_authentication_provider::JwtPrincipalAuthenticationProvider;
+use actix_web_security::authentication::scheme::bearer::jwt::token::decoder::TokenDecoder;
+use actix_web_security::authentication::ProviderManager;
+use actix_web_security::authorization::authority::{Authorities, ClaimAuthorityMapper};
+
+use common::deserialize_json;
+
+mod common;
+
+#[derive(Clone)]
+struct SimpleTokenDecoder {}
+
+impl TokenDecoder<DefaultJwt> for SimpleTokenDecoder {
+    fn decode_token(&self, token: &str) -> Result<Box<DefaultJwt>, AuthenticationError> {
+        let key = &DecodingKey::from_secret("secret".as_ref());
+        match decode::<DefaultJwt>(token, key, &Validation::default()) {
+            Ok(token_data) => Ok(Box::new(token_data.claims)),
+            Err(_) => Err(AuthenticationError::InvalidToken),
+        }
+    }
+}
+
+#[get("/principal")]
+async fn principal_endpoint(principal: JwtPrincipal) -> impl Responder {
+    HttpResponse::Ok().json(json!({
+        "subject": principal.subject,
+        "issuer": principal.issuer,
+        "authorities": principal.authorities,
+        "tenant": principal.claims["tenant"],
+        "token": principal.token,
+    }))
+}
+
+fn init_middleware(
+) -> HttpAuthenticationMiddleware<BearerAuthenticationExtractor<DefaultJwt>, AllEndpointsMatcher> {
+    let provider_manager =
+        ProviderManager::new(vec![Box::new(JwtPrincipalAuthenticationProvider::new())]);
+
+    let authentication_extractor =
+        BearerAuthenticationExtractor::new(vec![Box::new(SimpleTokenDecoder {})])
+            .with_authority_mapper(Box::new(
+                ClaimAuthorityMapper::new().with_claim("roles", ""),
+            ));
+
+    HttpAuthenticationMiddleware::new(
+        provider_manager,
+        Box::new(authentication_extractor),
+        Box::new(AllEndpointsMatcher::new()),
+    )
+}
+
+fn create_token(claims: serde_json::Value) -> String {
+    let key = &EncodingKey::from_secret("secret".as_ref());
+    encode(&Header::default(), &claims, key).expect("Token couldn't be encoded")
+}
+
+async fn call_endpoint(token: &str) -> Result<serde_json::Value, AuthenticationError> {
+    let mut service = test::init_service(
+        App::new()
+            .wrap(init_middleware())
+            .service(principal_endpoint),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/principal")
+        .header(header::AUTHORIZATION, format!("Bearer {}", token))
+        .to_request();
+
+    match service.call(req).await {
+        Ok(mut service_response) => {
+            assert!(&service_response.status().is_success());
+            Ok(deserialize_json(&mut service_response.take_body()).await)
+        }
+        Err(e) => Err(e
+            .as_error::<AuthenticationError>()
+            .expect("error expected")
+            .clone()),
+    }
+}
+
+#[actix_rt::test]
+async fn validate_jwt_principal_is_created_from_claims() {
+    let token = create_token(json!({
+        "iss": "https://authorization-server",
+        "sub": "test",
+        "exp": 10000000000u64,
+        "roles": ["admin"],
+        "tenant": "tenant-a"
+    }));
+
+    assert_eq!(
+        Ok(json!({
+            "subject": "test",
+            "issuer": "https://authorization-server",
+            "authorities": ["admin"],
+            "tenant": "tenant-a",
+            "token": token,
+        })),
+        call_endpoint(&token).await
+    );
+}
+
+#[actix_rt::test]
+async fn validate_jwt_principal_without_issuer() {
+    let token = create_token(json!({"sub": "test", "exp": 10000000000u64}));
+
+    assert_eq!(
+        Ok(json!({
+            "subject": "test",
+            "issuer": null,
+            "authorities": [],
+            "tenant": null,
+            "token": token,
+        })),
+        call_endpoint(&token).await
+    );
+}
+
+#[actix_rt::test]
+async fn validate_jwt_principal_without_subject() {
+    let token = create_token(json!({"iss": "https://authorization-server", "exp": 10000000000u64}));
+
+    assert_eq!(
+        Err(AuthenticationError::UsernameNotFound),
+        call_endpoint(&token).await
+    );
+}
+
+#[test]
+fn validate_jwt_principal_debug_redacts_token() {
+    let principal = JwtPrincipal {
+        subject: "test".to_string(),
+        issuer: None,
+        authorities: Authorities::default(),
+        claims: json!({"sub": "test"}),
+        token: "secret.token.value".to_string(),
+    };
+
+    let debug = format!("{:?}", principal);
+
+    assert!(debug.contains("subject: \"test\""));
+    assert!(!debug.contains("secret.token.value"));
+}
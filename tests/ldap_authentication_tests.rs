@@ -0,0 +1,331 @@
+#![cfg(feature = "ldap")]
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use actix_web::dev::Service;
+use actix_web::http::header;
+use actix_web::{get, test, App, HttpResponse, Responder};
+use serde_json::{json, Value};
+
+use actix_web_security::authentication::endpoint_matcher::AllEndpointsMatcher;
+use actix_web_security::authentication::error::error_type::{AuthenticationError, LdapError};
+use actix_web_security::authentication::middleware::HttpAuthenticationMiddleware;
+use actix_web_security::authentication::scheme::basic::header_extractor::BasicAuthenticationExtractor;
+use actix_web_security::authentication::scheme::basic::ldap::authentication_provider::LdapAuthenticationProvider;
+use actix_web_security::authentication::scheme::basic::ldap::ldap_user::LdapUser;
+use actix_web_security::authentication::scheme::basic::ldap::{
+    escape_dn_value, escape_filter_value, LdapConnection, LdapConnector, LdapEntry, LdapSearchScope,
+};
+use actix_web_security::authentication::ProviderManager;
+use actix_web_security::authorization::authority::CaseNormalization;
+
+use common::deserialize_json;
+
+mod common;
+
+const SERVICE_ACCOUNT_DN: &str = "cn=service,dc=example,dc=com";
+
+/// An in-process stand-in for an LDAP directory supporting equality filters (`(attribute=value)`)
+/// and presence filters (`(attribute=*)`).
+#[derive(Clone)]
+struct InMemoryDirectory {
+    available: bool,
+    entries: Vec<(LdapEntry, Option<String>)>,
+    searches: Arc<Mutex<Vec<String>>>,
+}
+
+impl InMemoryDirectory {
+    fn new() -> InMemoryDirectory {
+        InMemoryDirectory {
+            available: true,
+            entries: Vec::new(),
+            searches: Arc::new(Mutex::new(Vec::new())),
+        }
+        .with_entry(SERVICE_ACCOUNT_DN, Some("service-pw"), vec![])
+        .with_entry(
+            "uid=alice,ou=people,dc=example,dc=com",
+            Some("alice-pw"),
+            vec![
+                ("uid", "alice"),
+                ("memberOf", "cn=Admins,ou=groups,dc=example,dc=com"),
+                ("memberOf", "cn=Users,ou=groups,dc=example,dc=com"),
+            ],
+        )
+        .with_entry(
+            "uid=bob,ou=people,dc=example,dc=com",
+            Some("bob-pw"),
+            vec![("uid", "bob")],
+        )
+        .with_entry(
+            "cn=operators,ou=groups,dc=example,dc=com",
+            None,
+            vec![
+                ("cn", "operators"),
+                ("member", "uid=bob,ou=people,dc=example,dc=com"),
+            ],
+        )
+    }
+
+    fn with_entry(
+        mut self,
+        dn: &str,
+        password: Option<&str>,
+        attributes: Vec<(&str, &str)>,
+    ) -> InMemoryDirectory {
+        let mut entry = LdapEntry {
+            dn: dn.to_string(),
+            attributes: HashMap::new(),
+        };
+        entry
+            .attributes
+            .insert("objectClass".to_string(), vec!["top".to_string()]);
+        for (name, value) in attributes {
+            entry
+                .attributes
+                .entry(name.to_string())
+                .or_default()
+                .push(value.to_string());
+        }
+        self.entries.push((entry, password.map(String::from)));
+        self
+    }
+
+    fn searches(&self) -> Vec<String> {
+        self.searches.lock().unwrap().clone()
+    }
+}
+
+impl LdapConnector for InMemoryDirectory {
+    fn connect(&self) -> Result<Box<dyn LdapConnection>, LdapError> {
+        if self.available {
+            Ok(Box::new(self.clone()))
+        } else {
+            Err(LdapError::ConnectionFailed)
+        }
+    }
+}
+
+impl LdapConnection for InMemoryDirectory {
+    fn simple_bind(&mut self, dn: &str, password: &str) -> Result<(), LdapError> {
+        match self.entries.iter().find(|(entry, _)| entry.dn == dn) {
+            Some((_, Some(stored_password))) if stored_password == password => Ok(()),
+            _ => Err(LdapError::InvalidCredentials),
+        }
+    }
+
+    fn search(
+        &mut self,
+        base: &str,
+        scope: LdapSearchScope,
+        filter: &str,
+        attributes: &[&str],
+    ) -> Result<Vec<LdapEntry>, LdapError> {
+        self.searches.lock().unwrap().push(filter.to_string());
+        let (name, value) = filter
+            .strip_prefix('(')
+            .and_then(|filter| filter.strip_suffix(')'))
+            .and_then(|filter| filter.split_once('='))
+            .ok_or(LdapError::OperationFailed)?;
+        let value = unescape_filter_value(value);
+
+        Ok(self
+            .entries
+            .iter()
+            .map(|(entry, _)| entry)
+            .filter(|entry| match scope {
+                LdapSearchScope::Base => entry.dn == base,
+                LdapSearchScope::Subtree => entry.dn.ends_with(base),
+            })
+            .filter(|entry| {
+                entry.attributes.get(name).is_some_and(|values| {
+                    value == "*" || values.iter().any(|v| v.eq_ignore_ascii_case(&value))
+                })
+            })
+            .map(|entry| LdapEntry {
+                dn: entry.dn.clone(),
+                attributes: entry
+                    .attributes
+                    .iter()
+                    .filter(|(name, _)| attributes.contains(&name.as_str()))
+                    .map(|(name, values)| (name.clone(), values.clone()))
+                    .collect(),
+            })
+            .collect())
+    }
+}
+
+fn unescape_filter_value(value: &str) -> String {
+    let mut unescaped = Vec::new();
+    let bytes = value.as_bytes();
+    let mut index = 0;
+    while index < bytes.len() {
+        if bytes[index] == b'\\' {
+            let hex = std::str::from_utf8(&bytes[index + 1..index + 3]).unwrap();
+            unescaped.push(u8::from_str_radix(hex, 16).unwrap());
+            index += 3;
+        } else {
+            unescaped.push(bytes[index]);
+            index += 1;
+        }
+    }
+    String::from_utf8(unescaped).unwrap()
+}
+
+#[get("/test")]
+async fn test_endpoint(user: LdapUser) -> impl Responder {
+    HttpResponse::Ok().json(user)
+}
+
+async fn call_endpoint(
+    authentication_provider: LdapAuthenticationProvider,
+    credentials: &str,
+) -> Result<Value, AuthenticationError> {
+    let middleware = HttpAuthenticationMiddleware::new(
+        ProviderManager::new(vec![Box::new(authentication_provider)]),
+        Box::new(BasicAuthenticationExtractor::new()),
+        Box::new(AllEndpointsMatcher::new()),
+    );
+    let mut service = test::init_service(App::new().wrap(middleware).service(test_endpoint)).await;
+
+    let req = test::TestRequest::get()
+        .uri("/test")
+        .header(
+            header::AUTHORIZATION,
+            format!("Basic {}", base64::encode(credentials)),
+        )
+        .to_request();
+
+    match service.call(req).await {
+        Ok(mut service_response) => {
+            assert!(&service_response.status().is_success());
+            Ok(deserialize_json(&mut service_response.take_body()).await)
+        }
+        Err(e) => Err(e
+            .as_error::<AuthenticationError>()
+            .expect("error expected")
+            .clone()),
+    }
+}
+
+fn direct_bind_provider(directory: InMemoryDirectory) -> LdapAuthenticationProvider {
+    LdapAuthenticationProvider::direct_bind(
+        Box::new(directory),
+        "uid={username},ou=people,dc=example,dc=com",
+    )
+    .with_member_of_attribute("memberOf")
+    .with_authority_prefix("ROLE_")
+    .with_case_normalization(CaseNormalization::Uppercase)
+}
+
+fn search_and_bind_provider(directory: InMemoryDirectory) -> LdapAuthenticationProvider {
+    LdapAuthenticationProvider::search_and_bind(
+        Box::new(directory),
+        "ou=people,dc=example,dc=com",
+        "(uid={username})",
+    )
+    .with_service_account(SERVICE_ACCOUNT_DN, "service-pw")
+    .with_group_search("ou=groups,dc=example,dc=com", "(member={dn})", "cn")
+}
+
+#[actix_rt::test]
+async fn validate_ldap_direct_bind_succeeds() {
+    assert_eq!(
+        Ok(json!({
+            "username": "alice",
+            "dn": "uid=alice,ou=people,dc=example,dc=com",
+            "authorities": ["ROLE_ADMINS", "ROLE_USERS"]
+        })),
+        call_endpoint(
+            direct_bind_provider(InMemoryDirectory::new()),
+            "alice:alice-pw"
+        )
+        .await
+    );
+}
+
+#[actix_rt::test]
+async fn validate_ldap_search_and_bind_succeeds() {
+    let directory = InMemoryDirectory::new();
+
+    assert_eq!(
+        Ok(json!({
+            "username": "bob",
+            "dn": "uid=bob,ou=people,dc=example,dc=com",
+            "authorities": ["operators"]
+        })),
+        call_endpoint(search_and_bind_provider(directory.clone()), "bob:bob-pw").await
+    );
+    assert_eq!(
+        vec![
+            "(uid=bob)".to_string(),
+            "(member=uid=bob,ou=people,dc=example,dc=com)".to_string()
+        ],
+        directory.searches()
+    );
+}
+
+#[actix_rt::test]
+async fn validate_ldap_authentication_with_invalid_credentials() {
+    for credentials in &["alice:wrong", "alice:", "unknown:alice-pw", "*:alice-pw"] {
+        assert_eq!(
+            Err(AuthenticationError::UsernameNotFound),
+            call_endpoint(direct_bind_provider(InMemoryDirectory::new()), credentials).await
+        );
+        assert_eq!(
+            Err(AuthenticationError::UsernameNotFound),
+            call_endpoint(
+                search_and_bind_provider(InMemoryDirectory::new()),
+                credentials
+            )
+            .await
+        );
+    }
+}
+
+#[actix_rt::test]
+async fn validate_ldap_search_and_bind_escapes_username() {
+    let directory = InMemoryDirectory::new();
+
+    assert!(
+        call_endpoint(search_and_bind_provider(directory.clone()), "b*:bob-pw")
+            .await
+            .is_err()
+    );
+    assert_eq!(vec!["(uid=b\\2a)".to_string()], directory.searches());
+}
+
+#[actix_rt::test]
+async fn validate_ldap_authentication_with_invalid_service_account() {
+    let provider = search_and_bind_provider(InMemoryDirectory::new())
+        .with_service_account(SERVICE_ACCOUNT_DN, "wrong");
+
+    assert_eq!(
+        Err(AuthenticationError::ServiceUnavailable),
+        call_endpoint(provider, "bob:bob-pw").await
+    );
+}
+
+#[actix_rt::test]
+async fn validate_ldap_authentication_with_unavailable_directory() {
+    let directory = InMemoryDirectory {
+        available: false,
+        ..InMemoryDirectory::new()
+    };
+
+    assert_eq!(
+        Err(AuthenticationError::ServiceUnavailable),
+        call_endpoint(direct_bind_provider(directory), "alice:alice-pw").await
+    );
+}
+
+#[test]
+fn validate_ldap_escaping() {
+    assert_eq!("a\\2a\\28b\\29\\5c\\00", escape_filter_value("a*(b)\\\0"));
+    assert_eq!("Zoë", escape_filter_value("Zoë"));
+    assert_eq!(
+        r#"\#doe\, john\+\=\<\>\;\"\\\ "#,
+        escape_dn_value("#doe, john+=<>;\"\\ ")
+    );
+    assert_eq!("\\ a", escape_dn_value(" a"));
+}
@@ -0,0 +1,326 @@
+#![cfg(feature = "metrics")]
+
+use std::future::Future;
+
+use actix_web::dev::Service;
+use actix_web::http::header;
+use actix_web::{get, test, App, HttpResponse, Responder};
+use async_trait::async_trait;
+use metrics_util::debugging::{DebugValue, DebuggingRecorder};
+use metrics_util::{CompositeKey, MetricKind};
+
+use actix_web_security::authentication::endpoint_matcher::AllEndpointsMatcher;
+use actix_web_security::authentication::error::error_type::AuthenticationError;
+use actix_web_security::authentication::middleware::HttpAuthenticationMiddleware;
+use actix_web_security::authentication::scheme::basic::authentication_provider::BasicAuthenticationProvider;
+use actix_web_security::authentication::scheme::basic::header_extractor::BasicAuthenticationExtractor;
+use actix_web_security::authentication::scheme::basic::user_details_service::BasicUserDetailsService;
+use actix_web_security::authentication::scheme::bearer::introspection::header_extractor::HybridBearerAuthenticationExtractor;
+use actix_web_security::authentication::scheme::bearer::introspection::TokenIntrospector;
+use actix_web_security::authentication::scheme::bearer::jwt::authentication_provider::JwtAuthenticationProvider;
+use actix_web_security::authentication::scheme::bearer::jwt::default_jwt::DefaultJwt;
+use actix_web_security::authentication::scheme::bearer::jwt::header_extractor::BearerAuthenticationExtractor;
+use actix_web_security::authentication::scheme::bearer::jwt::token::decoder::TokenDecoder;
+use actix_web_security::authentication::scheme::bearer::jwt::token::Claims;
+use actix_web_security::authentication::scheme::bearer::jwt::user_details_service::JwtUserDetailsService;
+use actix_web_security::authentication::scheme::header_extractor::AuthorizationHeaderExtractor;
+use actix_web_security::authentication::ProviderManager;
+use actix_web_security::user_details::UserDetails;
+
+use common::User;
+
+mod common;
+
+#[derive(Clone)]
+struct UserDetailsServiceImpl {}
+
+#[async_trait]
+impl BasicUserDetailsService for UserDetailsServiceImpl {
+    async fn find_user(&self, username: &str, password: &str) -> Option<Box<dyn UserDetails>> {
+        if username == "test" && password == "test" {
+            Some(Box::new(User {
+                username: username.to_string(),
+            }))
+        } else {
+            None
+        }
+    }
+}
+
+#[async_trait]
+impl JwtUserDetailsService for UserDetailsServiceImpl {
+    #[allow(clippy::borrowed_box)]
+    async fn find_user(&self, _token: &Box<dyn Claims>) -> Option<Box<dyn UserDetails>> {
+        None
+    }
+}
+
+/// Test decoder rejecting all tokens
+#[derive(Clone)]
+struct RejectingTokenDecoder {}
+
+impl TokenDecoder<DefaultJwt> for RejectingTokenDecoder {
+    fn decode_token(&self, _token: &str) -> Result<Box<DefaultJwt>, AuthenticationError> {
+        Err(AuthenticationError::InvalidToken)
+    }
+}
+
+/// Test introspector rejecting all tokens
+#[derive(Clone)]
+struct RejectingTokenIntrospector {}
+
+#[async_trait]
+impl TokenIntrospector<DefaultJwt> for RejectingTokenIntrospector {
+    async fn introspect(&self, _token: &str) -> Result<Box<DefaultJwt>, AuthenticationError> {
+        Err(AuthenticationError::InvalidToken)
+    }
+}
+
+#[get("/test")]
+async fn test_endpoint() -> impl Responder {
+    HttpResponse::Ok().finish()
+}
+
+/// The recorded metrics
+type Metrics = Vec<(CompositeKey, DebugValue)>;
+
+/// Executes the function with a local recorder and returns the recorded metrics.
+fn record<T, F: FnOnce() -> T>(f: F) -> (T, Metrics) {
+    let recorder = DebuggingRecorder::new();
+    let snapshotter = recorder.snapshotter();
+    let result = metrics::with_local_recorder(&recorder, f);
+    let metrics = snapshotter
+        .snapshot()
+        .into_vec()
+        .into_iter()
+        .map(|(key, _, _, value)| (key, value))
+        .collect();
+    (result, metrics)
+}
+
+/// Executes the future with a local recorder and returns the recorded metrics.
+fn record_async<F: Future<Output = ()>>(future: F) -> Metrics {
+    record(|| actix_rt::System::new().block_on(future)).1
+}
+
+/// Returns the value of the metric with the given kind, name and labels.
+fn metric_value<'a>(
+    metrics: &'a Metrics,
+    kind: MetricKind,
+    name: &str,
+    labels: &[(&str, &str)],
+) -> Option<&'a DebugValue> {
+    metrics
+        .iter()
+        .find(|(key, _)| {
+            key.kind() == kind
+                && key.key().name() == name
+                && labels.iter().all(|(label, value)| {
+                    key.key()
+                        .labels()
+                        .any(|l| l.key() == *label && l.value() == *value)
+                })
+        })
+        .map(|(_, value)| value)
+}
+
+fn counter(metrics: &Metrics, name: &str, labels: &[(&str, &str)]) -> u64 {
+    match metric_value(metrics, MetricKind::Counter, name, labels) {
+        Some(DebugValue::Counter(value)) => *value,
+        _ => 0,
+    }
+}
+
+fn histogram_samples(metrics: &Metrics, name: &str, labels: &[(&str, &str)]) -> usize {
+    match metric_value(metrics, MetricKind::Histogram, name, labels) {
+        Some(DebugValue::Histogram(samples)) => samples.len(),
+        _ => 0,
+    }
+}
+
+async fn call_endpoint<T>(
+    middleware: HttpAuthenticationMiddleware<T, AllEndpointsMatcher>,
+    authorization: &str,
+) where
+    T: AuthorizationHeaderExtractor + Clone + 'static,
+{
+    let mut service = test::init_service(App::new().wrap(middleware).service(test_endpoint)).await;
+    let req = test::TestRequest::get()
+        .uri("/test")
+        .header(header::AUTHORIZATION, authorization)
+        .to_request();
+    let _ = service.call(req).await;
+}
+
+fn basic_middleware(
+) -> HttpAuthenticationMiddleware<BasicAuthenticationExtractor, AllEndpointsMatcher> {
+    HttpAuthenticationMiddleware::new(
+        ProviderManager::new(vec![Box::new(BasicAuthenticationProvider::new(Box::new(
+            UserDetailsServiceImpl {},
+        )))]),
+        Box::new(BasicAuthenticationExtractor::new()),
+        Box::new(AllEndpointsMatcher::new()),
+    )
+}
+
+#[test]
+fn validate_authentication_outcomes_are_counted() {
+    let metrics = record_async(async {
+        let basic = format!("Basic {}", base64::encode("test:test"));
+        call_endpoint(basic_middleware(), &basic).await;
+        call_endpoint(basic_middleware(), &basic).await;
+        call_endpoint(
+            basic_middleware(),
+            &format!("Basic {}", base64::encode("test:wrong")),
+        )
+        .await;
+        call_endpoint(basic_middleware(), "Digest username=\"test\"").await;
+    });
+
+    let authentications = "actix_web_security_authentications_total";
+    assert_eq!(
+        2,
+        counter(
+            &metrics,
+            authentications,
+            &[
+                ("scheme", "basic"),
+                ("outcome", "success"),
+                ("error", "none")
+            ]
+        )
+    );
+    assert_eq!(
+        1,
+        counter(
+            &metrics,
+            authentications,
+            &[
+                ("scheme", "basic"),
+                ("outcome", "failure"),
+                ("error", "UsernameNotFound")
+            ]
+        )
+    );
+    assert_eq!(
+        1,
+        counter(
+            &metrics,
+            authentications,
+            &[
+                ("scheme", "other"),
+                ("outcome", "failure"),
+                ("error", "InvalidAuthorizationHeader")
+            ]
+        )
+    );
+    assert_eq!(
+        2,
+        histogram_samples(
+            &metrics,
+            "actix_web_security_provider_duration_seconds",
+            &[("provider", "0"), ("result", "success")]
+        )
+    );
+    assert_eq!(
+        1,
+        histogram_samples(
+            &metrics,
+            "actix_web_security_provider_duration_seconds",
+            &[("provider", "0"), ("result", "failure")]
+        )
+    );
+}
+
+#[test]
+fn validate_token_decode_latency_is_recorded() {
+    let metrics = record_async(async {
+        let middleware = HttpAuthenticationMiddleware::new(
+            ProviderManager::new(vec![Box::new(JwtAuthenticationProvider::new(Box::new(
+                UserDetailsServiceImpl {},
+            )))]),
+            Box::new(BearerAuthenticationExtractor::new(vec![
+                Box::new(RejectingTokenDecoder {}),
+                Box::new(RejectingTokenDecoder {}),
+            ])),
+            Box::new(AllEndpointsMatcher::new()),
+        );
+        call_endpoint(middleware, "Bearer a.b.c").await;
+    });
+
+    let decode_duration = "actix_web_security_token_decode_duration_seconds";
+    assert_eq!(
+        1,
+        histogram_samples(
+            &metrics,
+            decode_duration,
+            &[("decoder", "0"), ("result", "failure")]
+        )
+    );
+    assert_eq!(
+        1,
+        histogram_samples(
+            &metrics,
+            decode_duration,
+            &[("decoder", "1"), ("result", "failure")]
+        )
+    );
+    assert_eq!(
+        1,
+        counter(
+            &metrics,
+            "actix_web_security_authentications_total",
+            &[
+                ("scheme", "bearer"),
+                ("outcome", "failure"),
+                ("error", "InvalidAuthentication")
+            ]
+        )
+    );
+}
+
+#[test]
+fn validate_token_introspection_latency_is_recorded() {
+    let metrics = record_async(async {
+        let middleware = HttpAuthenticationMiddleware::new(
+            ProviderManager::new(vec![Box::new(JwtAuthenticationProvider::new(Box::new(
+                UserDetailsServiceImpl {},
+            )))]),
+            Box::new(HybridBearerAuthenticationExtractor::new(
+                vec![Box::new(RejectingTokenDecoder {})],
+                Box::new(RejectingTokenIntrospector {}),
+            )),
+            Box::new(AllEndpointsMatcher::new()),
+        );
+        call_endpoint(middleware, "Bearer opaque-token").await;
+    });
+
+    assert_eq!(
+        1,
+        histogram_samples(
+            &metrics,
+            "actix_web_security_token_decode_duration_seconds",
+            &[("decoder", "introspector"), ("result", "failure")]
+        )
+    );
+}
+
+#[cfg(feature = "jwk-loader")]
+#[test]
+fn validate_failed_jwks_refresh_is_counted() {
+    use actix_web_security::authentication::scheme::bearer::jwk::default_jwk::DefaultJwks;
+    use actix_web_security::authentication::scheme::bearer::jwk::JwkLoader;
+
+    let (result, metrics) =
+        record(|| JwkLoader::<DefaultJwks>::from_url("http://127.0.0.1:1/jwks.json".to_string()));
+
+    assert!(result.is_err());
+    assert_eq!(
+        1,
+        counter(
+            &metrics,
+            "actix_web_security_jwks_refreshes_total",
+            &[("result", "failure"), ("error", "JwksCouldNotBeDownloaded")]
+        )
+    );
+}
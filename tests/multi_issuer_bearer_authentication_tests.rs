@@ -0,0 +1,231 @@
+use std::collections::HashMap;
+
+use actix_web::dev::Service;
+use actix_web::http::header;
+use actix_web::{get, test, App, HttpResponse, Responder};
+use async_trait::async_trait;
+use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
+
+use actix_web_security::authentication::endpoint_matcher::AllEndpointsMatcher;
+use actix_web_security::authentication::error::error_type::AuthenticationError;
+use actix_web_security::authentication::middleware::HttpAuthenticationMiddleware;
+use actix_web_security::authentication::scheme::bearer::issuer::authentication_provider::MultiIssuerJwtAuthenticationProvider;
+use actix_web_security::authentication::scheme::bearer::issuer::header_extractor::MultiIssuerBearerAuthenticationExtractor;
+use actix_web_security::authentication::scheme::bearer::issuer::{
+    IssuerConfiguration, IssuerRegistry,
+};
+use actix_web_security::authentication::scheme::bearer::jwt::default_jwt::DefaultJwt;
+use actix_web_security::authentication::scheme::bearer::jwt::token::decoder::TokenDecoder;
+use actix_web_security::authentication::scheme::bearer::jwt::token::Claims;
+use actix_web_security::authentication::scheme::bearer::jwt::user_details_service::JwtUserDetailsService;
+use actix_web_security::authentication::ProviderManager;
+use actix_web_security::user_details::UserDetails;
+
+use common::deserialize_json;
+use common::User;
+
+mod common;
+
+#[derive(Clone)]
+struct JwtUserDetailsServiceImpl {
+    prefix: String,
+}
+
+#[async_trait]
+impl JwtUserDetailsService for JwtUserDetailsServiceImpl {
+    async fn find_user(&self, token: &Box<dyn Claims>) -> Option<Box<dyn UserDetails>> {
+        token
+            .downcast_ref::<DefaultJwt>()
+            .and_then(|claims| claims.sub.clone())
+            .map(|sub| {
+                Box::new(User {
+                    username: format!("{}{}", self.prefix, sub),
+                }) as Box<dyn UserDetails>
+            })
+    }
+}
+
+#[derive(Clone)]
+struct SecretTokenDecoder {
+    secret: String,
+}
+
+impl TokenDecoder<DefaultJwt> for SecretTokenDecoder {
+    fn decode_token(&self, token: &str) -> Result<Box<DefaultJwt>, AuthenticationError> {
+        let key = &DecodingKey::from_secret(self.secret.as_ref());
+        match decode::<DefaultJwt>(token, key, &Validation::default()) {
+            Ok(token_data) => Ok(Box::new(token_data.claims)),
+            Err(_) => Err(AuthenticationError::InvalidToken),
+        }
+    }
+}
+
+#[get("/test")]
+async fn test_endpoint(user: User) -> impl Responder {
+    HttpResponse::Ok().json(user)
+}
+
+fn issuer_configuration(secret: &str) -> IssuerConfiguration<DefaultJwt> {
+    IssuerConfiguration::new(vec![Box::new(SecretTokenDecoder {
+        secret: secret.to_string(),
+    })])
+}
+
+fn init_registry() -> IssuerRegistry<DefaultJwt> {
+    let issuer_registry = IssuerRegistry::new();
+    issuer_registry.add_issuer(
+        "https://tenant-a".to_string(),
+        issuer_configuration("secret-a"),
+    );
+    issuer_registry.add_issuer(
+        "https://tenant-b".to_string(),
+        issuer_configuration("secret-b").with_user_details_service(Box::new(
+            JwtUserDetailsServiceImpl {
+                prefix: "b:".to_string(),
+            },
+        )),
+    );
+    issuer_registry
+}
+
+fn init_middleware(
+    issuer_registry: IssuerRegistry<DefaultJwt>,
+) -> HttpAuthenticationMiddleware<
+    MultiIssuerBearerAuthenticationExtractor<DefaultJwt>,
+    AllEndpointsMatcher,
+> {
+    let authentication_provider = MultiIssuerJwtAuthenticationProvider::new(
+        issuer_registry.clone(),
+        Box::new(JwtUserDetailsServiceImpl {
+            prefix: "".to_string(),
+        }),
+    );
+
+    let provider_manager = ProviderManager::new(vec![Box::new(authentication_provider)]);
+
+    let authentication_extractor = MultiIssuerBearerAuthenticationExtractor::new(issuer_registry);
+
+    HttpAuthenticationMiddleware::new(
+        provider_manager,
+        Box::new(authentication_extractor),
+        Box::new(AllEndpointsMatcher::new()),
+    )
+}
+
+fn create_token(issuer: &str, secret: &str) -> String {
+    let claims = DefaultJwt {
+        iss: Some(issuer.to_string()),
+        sub: Some("test".to_string()),
+        aud: None,
+        exp: Some(10000000000),
+        nbf: None,
+        iat: None,
+        jti: None,
+        scope: None,
+        scp: None,
+        additional_claims: HashMap::new(),
+    };
+    let key = &EncodingKey::from_secret(secret.as_ref());
+    encode(&Header::default(), &claims, key).expect("Token couldn't be encoded")
+}
+
+async fn call_endpoint(
+    issuer_registry: IssuerRegistry<DefaultJwt>,
+    token: String,
+) -> Result<User, AuthenticationError> {
+    let mut service = test::init_service(
+        App::new()
+            .wrap(init_middleware(issuer_registry))
+            .service(test_endpoint),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/test")
+        .header(header::AUTHORIZATION, format!("Bearer {}", token))
+        .to_request();
+
+    match service.call(req).await {
+        Ok(mut service_response) => {
+            assert!(&service_response.status().is_success());
+            Ok(deserialize_json(&mut service_response.take_body()).await)
+        }
+        Err(e) => Err(e
+            .as_error::<AuthenticationError>()
+            .expect("error expected")
+            .clone()),
+    }
+}
+
+#[actix_rt::test]
+async fn validate_multi_issuer_bearer_auth_succeeds() {
+    let token = create_token("https://tenant-a", "secret-a");
+
+    assert_eq!(
+        Ok(User {
+            username: "test".to_string()
+        }),
+        call_endpoint(init_registry(), token).await
+    );
+}
+
+#[actix_rt::test]
+async fn validate_multi_issuer_bearer_auth_uses_issuer_user_details_service() {
+    let token = create_token("https://tenant-b", "secret-b");
+
+    assert_eq!(
+        Ok(User {
+            username: "b:test".to_string()
+        }),
+        call_endpoint(init_registry(), token).await
+    );
+}
+
+#[actix_rt::test]
+async fn validate_multi_issuer_bearer_auth_rejects_key_of_other_issuer() {
+    let token = create_token("https://tenant-b", "secret-a");
+
+    assert_eq!(
+        Err(AuthenticationError::InvalidAuthentication),
+        call_endpoint(init_registry(), token).await
+    );
+}
+
+#[actix_rt::test]
+async fn validate_multi_issuer_bearer_auth_rejects_unknown_issuer() {
+    let token = create_token("https://tenant-c", "secret-c");
+
+    assert_eq!(
+        Err(AuthenticationError::InvalidToken),
+        call_endpoint(init_registry(), token).await
+    );
+}
+
+#[actix_rt::test]
+async fn validate_multi_issuer_bearer_auth_with_issuer_added_at_runtime() {
+    let issuer_registry = init_registry();
+    let mut service = test::init_service(
+        App::new()
+            .wrap(init_middleware(issuer_registry.clone()))
+            .service(test_endpoint),
+    )
+    .await;
+
+    issuer_registry.add_issuer(
+        "https://tenant-c".to_string(),
+        issuer_configuration("secret-c"),
+    );
+
+    let req = test::TestRequest::get()
+        .uri("/test")
+        .header(
+            header::AUTHORIZATION,
+            format!("Bearer {}", create_token("https://tenant-c", "secret-c")),
+        )
+        .to_request();
+
+    match service.call(req).await {
+        Ok(service_response) => assert!(&service_response.status().is_success()),
+        Err(e) => panic!("Error occurred: {}", e),
+    }
+}
@@ -0,0 +1,188 @@
+#![cfg(feature = "paseto")]
+
+use actix_web::dev::Service;
+use actix_web::http::header;
+use actix_web::{get, test, App, HttpResponse, Responder};
+use async_trait::async_trait;
+use ed25519_dalek::{Signer, SigningKey};
+use serde_json::json;
+
+use actix_web_security::authentication::endpoint_matcher::AllEndpointsMatcher;
+use actix_web_security::authentication::error::error_type::AuthenticationError;
+use actix_web_security::authentication::middleware::HttpAuthenticationMiddleware;
+use actix_web_security::authentication::scheme::bearer::jwt::authentication_provider::JwtAuthenticationProvider;
+use actix_web_security::authentication::scheme::bearer::jwt::default_jwt::DefaultJwt;
+use actix_web_security::authentication::scheme::bearer::jwt::header_extractor::BearerAuthenticationExtractor;
+use actix_web_security::authentication::scheme::bearer::jwt::token::decoder::paseto_decoder::{
+    PasetoDecoder, PasetoPublicKey,
+};
+use actix_web_security::authentication::scheme::bearer::jwt::token::Claims;
+use actix_web_security::authentication::scheme::bearer::jwt::user_details_service::JwtUserDetailsService;
+use actix_web_security::authentication::ProviderManager;
+use actix_web_security::user_details::UserDetails;
+
+use common::deserialize_json;
+use common::User;
+
+mod common;
+
+#[derive(Clone)]
+struct JwtUserDetailsServiceImpl {}
+
+#[async_trait]
+impl JwtUserDetailsService for JwtUserDetailsServiceImpl {
+    async fn find_user(&self, token: &Box<dyn Claims>) -> Option<Box<dyn UserDetails>> {
+        token
+            .downcast_ref::<DefaultJwt>()
+            .filter(|claims| matches!(claims.exp, Some(4102444800) | Some(1577836800)))
+            .and_then(|claims| claims.sub.clone())
+            .map(|sub| Box::new(User { username: sub }) as Box<dyn UserDetails>)
+    }
+}
+
+#[get("/test")]
+async fn test_endpoint(user: User) -> impl Responder {
+    HttpResponse::Ok().json(user)
+}
+
+fn signing_key() -> SigningKey {
+    SigningKey::from_bytes(&[7u8; 32])
+}
+
+fn init_middleware(
+    leeway: u64,
+) -> HttpAuthenticationMiddleware<BearerAuthenticationExtractor<DefaultJwt>, AllEndpointsMatcher> {
+    let authentication_provider =
+        JwtAuthenticationProvider::new(Box::new(JwtUserDetailsServiceImpl {}));
+
+    let provider_manager = ProviderManager::new(vec![Box::new(authentication_provider)]);
+
+    let paseto_decoder = PasetoDecoder::new(vec![PasetoPublicKey::new(
+        Some("key-1".to_string()),
+        signing_key().verifying_key(),
+    )])
+    .with_implicit_assertion(b"resource-server".to_vec())
+    .with_leeway(leeway);
+
+    let authentication_extractor =
+        BearerAuthenticationExtractor::new(vec![Box::new(paseto_decoder)]);
+
+    HttpAuthenticationMiddleware::new(
+        provider_manager,
+        Box::new(authentication_extractor),
+        Box::new(AllEndpointsMatcher::new()),
+    )
+}
+
+fn pre_authentication_encoding(pieces: &[&[u8]]) -> Vec<u8> {
+    let mut output = (pieces.len() as u64).to_le_bytes().to_vec();
+    for piece in pieces {
+        output.extend((piece.len() as u64).to_le_bytes());
+        output.extend(*piece);
+    }
+    output
+}
+
+fn create_token(exp: &str, key_id: &str, implicit_assertion: &[u8]) -> String {
+    let message = json!({"sub": "test", "exp": exp}).to_string();
+    let footer = json!({ "kid": key_id }).to_string();
+    let signature = signing_key().sign(&pre_authentication_encoding(&[
+        b"v4.public.",
+        message.as_bytes(),
+        footer.as_bytes(),
+        implicit_assertion,
+    ]));
+    let mut signed_message = message.into_bytes();
+    signed_message.extend(signature.to_bytes());
+    format!(
+        "v4.public.{}.{}",
+        base64::encode_config(signed_message, base64::URL_SAFE_NO_PAD),
+        base64::encode_config(footer, base64::URL_SAFE_NO_PAD)
+    )
+}
+
+async fn call_endpoint(token: String) -> Result<User, AuthenticationError> {
+    call_endpoint_with_leeway(token, 0).await
+}
+
+async fn call_endpoint_with_leeway(
+    token: String,
+    leeway: u64,
+) -> Result<User, AuthenticationError> {
+    let mut service = test::init_service(
+        App::new()
+            .wrap(init_middleware(leeway))
+            .service(test_endpoint),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/test")
+        .header(header::AUTHORIZATION, format!("Bearer {}", token))
+        .to_request();
+
+    match service.call(req).await {
+        Ok(mut service_response) => {
+            assert!(&service_response.status().is_success());
+            Ok(deserialize_json(&mut service_response.take_body()).await)
+        }
+        Err(e) => Err(e
+            .as_error::<AuthenticationError>()
+            .expect("error expected")
+            .clone()),
+    }
+}
+
+#[actix_rt::test]
+async fn validate_paseto_bearer_auth_succeeds() {
+    let token = create_token("2100-01-01T00:00:00+00:00", "key-1", b"resource-server");
+
+    assert_eq!(
+        Ok(User {
+            username: "test".to_string()
+        }),
+        call_endpoint(token).await
+    );
+}
+
+#[actix_rt::test]
+async fn validate_paseto_bearer_auth_with_expired_token() {
+    let token = create_token("2020-01-01T00:00:00+00:00", "key-1", b"resource-server");
+
+    assert_eq!(
+        Err(AuthenticationError::InvalidAuthentication),
+        call_endpoint(token).await
+    );
+}
+
+#[actix_rt::test]
+async fn validate_paseto_bearer_auth_with_expired_token_and_maximum_leeway() {
+    let token = create_token("2020-01-01T00:00:00+00:00", "key-1", b"resource-server");
+
+    assert_eq!(
+        Ok(User {
+            username: "test".to_string()
+        }),
+        call_endpoint_with_leeway(token, u64::MAX).await
+    );
+}
+
+#[actix_rt::test]
+async fn validate_paseto_bearer_auth_with_unknown_key_id() {
+    let token = create_token("2100-01-01T00:00:00+00:00", "key-2", b"resource-server");
+
+    assert_eq!(
+        Err(AuthenticationError::InvalidAuthentication),
+        call_endpoint(token).await
+    );
+}
+
+#[actix_rt::test]
+async fn validate_paseto_bearer_auth_with_invalid_implicit_assertion() {
+    let token = create_token("2100-01-01T00:00:00+00:00", "key-1", b"other-server");
+
+    assert_eq!(
+        Err(AuthenticationError::InvalidAuthentication),
+        call_endpoint(token).await
+    );
+}
@@ -0,0 +1,296 @@
+#![cfg(feature = "password-hashing")]
+
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+use actix_web::dev::Service;
+use actix_web::http::header;
+use actix_web::{get, test, App, HttpResponse, Responder};
+use async_trait::async_trait;
+
+use actix_web_security::authentication::endpoint_matcher::AllEndpointsMatcher;
+use actix_web_security::authentication::error::error_type::{
+    AuthenticationError, PasswordEncoderError,
+};
+use actix_web_security::authentication::middleware::HttpAuthenticationMiddleware;
+use actix_web_security::authentication::scheme::basic::header_extractor::BasicAuthenticationExtractor;
+use actix_web_security::authentication::scheme::basic::password_authentication_provider::PasswordAuthenticationProvider;
+use actix_web_security::authentication::scheme::basic::password_encoder::argon2_encoder::Argon2PasswordEncoder;
+use actix_web_security::authentication::scheme::basic::password_encoder::bcrypt_encoder::BcryptPasswordEncoder;
+use actix_web_security::authentication::scheme::basic::password_encoder::pbkdf2_encoder::Pbkdf2PasswordEncoder;
+use actix_web_security::authentication::scheme::basic::password_encoder::scrypt_encoder::ScryptPasswordEncoder;
+use actix_web_security::authentication::scheme::basic::password_encoder::{
+    DelegatingPasswordEncoder, PasswordEncoder,
+};
+use actix_web_security::authentication::scheme::basic::user_store::{
+    BasicUserStore, UserCredentials,
+};
+use actix_web_security::authentication::ProviderManager;
+
+use common::deserialize_json;
+use common::User;
+
+mod common;
+
+fn password_encoder() -> DelegatingPasswordEncoder {
+    let mut encoders: HashMap<String, Box<dyn PasswordEncoder>> = HashMap::new();
+    encoders.insert(
+        "argon2".to_string(),
+        Box::new(Argon2PasswordEncoder::with_params(1024, 1, 1).unwrap()),
+    );
+    encoders.insert(
+        "bcrypt".to_string(),
+        Box::new(BcryptPasswordEncoder::with_cost(4).unwrap()),
+    );
+    DelegatingPasswordEncoder::new("argon2", encoders).unwrap()
+}
+
+#[derive(Clone)]
+struct BasicUserStoreImpl {
+    password_hashes: Arc<RwLock<HashMap<String, String>>>,
+}
+
+impl BasicUserStoreImpl {
+    fn new() -> BasicUserStoreImpl {
+        let bcrypt_hash = BcryptPasswordEncoder::with_cost(4)
+            .unwrap()
+            .encode("legacy-pw")
+            .unwrap();
+        let mut password_hashes = HashMap::new();
+        password_hashes.insert("test".to_string(), password_encoder().encode("pw").unwrap());
+        password_hashes.insert("legacy".to_string(), format!("{{bcrypt}}{}", bcrypt_hash));
+        password_hashes.insert("plain".to_string(), "pw".to_string());
+        BasicUserStoreImpl {
+            password_hashes: Arc::new(RwLock::new(password_hashes)),
+        }
+    }
+
+    fn password_hash(&self, username: &str) -> String {
+        self.password_hashes.read().unwrap()[username].clone()
+    }
+}
+
+#[async_trait]
+impl BasicUserStore for BasicUserStoreImpl {
+    async fn find_user_by_username(
+        &self,
+        username: &str,
+    ) -> Result<Option<UserCredentials>, AuthenticationError> {
+        Ok(self
+            .password_hashes
+            .read()
+            .unwrap()
+            .get(username)
+            .map(|password_hash| UserCredentials {
+                user_details: Box::new(User {
+                    username: username.to_string(),
+                }),
+                password_hash: password_hash.clone(),
+            }))
+    }
+
+    async fn update_password_hash(&self, username: &str, password_hash: &str) {
+        self.password_hashes
+            .write()
+            .unwrap()
+            .insert(username.to_string(), password_hash.to_string());
+    }
+}
+
+#[get("/test")]
+async fn test_endpoint(user: User) -> impl Responder {
+    HttpResponse::Ok().json(user)
+}
+
+fn init_middleware(
+    user_store: BasicUserStoreImpl,
+) -> HttpAuthenticationMiddleware<BasicAuthenticationExtractor, AllEndpointsMatcher> {
+    let authentication_provider =
+        PasswordAuthenticationProvider::new(Box::new(user_store), Box::new(password_encoder()));
+
+    let provider_manager = ProviderManager::new(vec![Box::new(authentication_provider)]);
+
+    HttpAuthenticationMiddleware::new(
+        provider_manager,
+        Box::new(BasicAuthenticationExtractor::new()),
+        Box::new(AllEndpointsMatcher::new()),
+    )
+}
+
+async fn call_endpoint(
+    user_store: BasicUserStoreImpl,
+    credentials: &str,
+) -> Result<User, AuthenticationError> {
+    let mut service = test::init_service(
+        App::new()
+            .wrap(init_middleware(user_store))
+            .service(test_endpoint),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/test")
+        .header(
+            header::AUTHORIZATION,
+            format!("Basic {}", base64::encode(credentials)),
+        )
+        .to_request();
+
+    match service.call(req).await {
+        Ok(mut service_response) => {
+            assert!(&service_response.status().is_success());
+            Ok(deserialize_json(&mut service_response.take_body()).await)
+        }
+        Err(e) => Err(e
+            .as_error::<AuthenticationError>()
+            .expect("error expected")
+            .clone()),
+    }
+}
+
+#[actix_rt::test]
+async fn validate_password_authentication_succeeds() {
+    assert_eq!(
+        Ok(User {
+            username: "test".to_string()
+        }),
+        call_endpoint(BasicUserStoreImpl::new(), "test:pw").await
+    );
+    assert_eq!(
+        Ok(User {
+            username: "legacy".to_string()
+        }),
+        call_endpoint(BasicUserStoreImpl::new(), "legacy:legacy-pw").await
+    );
+}
+
+#[actix_rt::test]
+async fn validate_password_authentication_with_invalid_credentials() {
+    assert_eq!(
+        Err(AuthenticationError::UsernameNotFound),
+        call_endpoint(BasicUserStoreImpl::new(), "test:wrong").await
+    );
+    assert_eq!(
+        Err(AuthenticationError::UsernameNotFound),
+        call_endpoint(BasicUserStoreImpl::new(), "unknown:pw").await
+    );
+    assert_eq!(
+        Err(AuthenticationError::UsernameNotFound),
+        call_endpoint(BasicUserStoreImpl::new(), "plain:pw").await
+    );
+}
+
+#[actix_rt::test]
+async fn validate_password_hash_is_upgraded() {
+    let user_store = BasicUserStoreImpl::new();
+    let password_hash = user_store.password_hash("test");
+
+    assert!(call_endpoint(user_store.clone(), "legacy:wrong")
+        .await
+        .is_err());
+    assert!(user_store.password_hash("legacy").starts_with("{bcrypt}"));
+
+    assert!(call_endpoint(user_store.clone(), "legacy:legacy-pw")
+        .await
+        .is_ok());
+    assert!(user_store.password_hash("legacy").starts_with("{argon2}"));
+    assert!(call_endpoint(user_store.clone(), "legacy:legacy-pw")
+        .await
+        .is_ok());
+
+    assert!(call_endpoint(user_store.clone(), "test:pw").await.is_ok());
+    assert_eq!(password_hash, user_store.password_hash("test"));
+}
+
+#[test]
+fn validate_upgrade_encoding() {
+    let argon2_encoder = Argon2PasswordEncoder::with_params(1024, 1, 1).unwrap();
+    let argon2_hash = argon2_encoder.encode("pw").unwrap();
+    assert!(!argon2_encoder.upgrade_encoding(&argon2_hash));
+    assert!(Argon2PasswordEncoder::with_params(2048, 1, 1)
+        .unwrap()
+        .upgrade_encoding(&argon2_hash));
+
+    let bcrypt_hash = BcryptPasswordEncoder::with_cost(4)
+        .unwrap()
+        .encode("pw")
+        .unwrap();
+    assert!(!BcryptPasswordEncoder::with_cost(4)
+        .unwrap()
+        .upgrade_encoding(&bcrypt_hash));
+    assert!(BcryptPasswordEncoder::with_cost(5)
+        .unwrap()
+        .upgrade_encoding(&bcrypt_hash));
+
+    let pbkdf2_hash = Pbkdf2PasswordEncoder::with_rounds(1000)
+        .unwrap()
+        .encode("pw")
+        .unwrap();
+    assert!(!Pbkdf2PasswordEncoder::with_rounds(1000)
+        .unwrap()
+        .upgrade_encoding(&pbkdf2_hash));
+    assert!(Pbkdf2PasswordEncoder::new().upgrade_encoding(&pbkdf2_hash));
+
+    let scrypt_hash = ScryptPasswordEncoder::with_params(4, 8, 1)
+        .unwrap()
+        .encode("pw")
+        .unwrap();
+    assert!(!ScryptPasswordEncoder::with_params(4, 8, 1)
+        .unwrap()
+        .upgrade_encoding(&scrypt_hash));
+    assert!(ScryptPasswordEncoder::new().upgrade_encoding(&scrypt_hash));
+
+    let delegating_encoder = password_encoder();
+    assert!(!delegating_encoder.upgrade_encoding(&format!("{{argon2}}{}", argon2_hash)));
+    assert!(delegating_encoder.upgrade_encoding(&format!("{{bcrypt}}{}", bcrypt_hash)));
+}
+
+#[test]
+fn validate_password_encoders() {
+    let encoders: Vec<Box<dyn PasswordEncoder>> = vec![
+        Box::new(Argon2PasswordEncoder::with_params(1024, 1, 1).unwrap()),
+        Box::new(BcryptPasswordEncoder::with_cost(4).unwrap()),
+        Box::new(Pbkdf2PasswordEncoder::with_rounds(1000).unwrap()),
+        Box::new(ScryptPasswordEncoder::with_params(4, 8, 1).unwrap()),
+    ];
+
+    for encoder in encoders {
+        let encoded_password = encoder.encode("pw").unwrap();
+        assert_ne!("pw", encoded_password);
+        assert_ne!(encoded_password, encoder.encode("pw").unwrap());
+        assert!(encoder.matches("pw", &encoded_password));
+        assert!(!encoder.matches("wrong", &encoded_password));
+        assert!(!encoder.matches("pw", "invalid"));
+    }
+}
+
+#[test]
+fn validate_delegating_password_encoder() {
+    let encoded_password = password_encoder().encode("pw").unwrap();
+    assert!(encoded_password.starts_with("{argon2}$argon2id$"));
+
+    let scrypt_hash = ScryptPasswordEncoder::with_params(4, 8, 1)
+        .unwrap()
+        .encode("pw")
+        .unwrap();
+    let default_encoder = DelegatingPasswordEncoder::default();
+    assert!(default_encoder.matches("pw", &encoded_password));
+    assert!(default_encoder.matches("pw", &format!("{{scrypt}}{}", scrypt_hash)));
+    assert!(!default_encoder.matches("pw", &format!("{{bcrypt}}{}", scrypt_hash)));
+    assert!(!default_encoder.matches("pw", &format!("{{unknown}}{}", scrypt_hash)));
+    assert!(!default_encoder.matches("pw", &scrypt_hash));
+}
+
+#[test]
+fn validate_delegating_password_encoder_without_encoder_for_encoding_id() {
+    let mut encoders: HashMap<String, Box<dyn PasswordEncoder>> = HashMap::new();
+    encoders.insert(
+        "bcrypt".to_string(),
+        Box::new(BcryptPasswordEncoder::with_cost(4).unwrap()),
+    );
+
+    assert!(matches!(
+        DelegatingPasswordEncoder::new("argon2", encoders),
+        Err(PasswordEncoderError::InvalidParameters)
+    ));
+}
@@ -0,0 +1,261 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use actix_web_security::authentication::error::error_type::AuthenticationError;
+use actix_web_security::authentication::scheme::authentication::Authentication;
+use actix_web_security::authentication::scheme::authentication_provider::AuthenticationProvider;
+use actix_web_security::authentication::scheme::basic::secret::Secret;
+use actix_web_security::authentication::scheme::basic::BasicAuthentication;
+use actix_web_security::authentication::{ProviderManager, ProviderStrategy};
+use actix_web_security::user_details::UserDetails;
+
+use common::User;
+
+mod common;
+
+/// Another `Authentication` type not supported by the basic providers
+struct ApiKeyAuthentication {}
+
+impl Authentication for ApiKeyAuthentication {}
+
+/// Test provider returning a fixed result (a user with the provider name on success) and counting the calls.
+#[derive(Clone)]
+struct FixedResultProvider {
+    name: &'static str,
+    error: Option<AuthenticationError>,
+    basic_only: bool,
+    calls: Arc<AtomicUsize>,
+}
+
+impl FixedResultProvider {
+    fn succeeding(name: &'static str) -> FixedResultProvider {
+        FixedResultProvider {
+            name,
+            error: None,
+            basic_only: false,
+            calls: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    fn failing(name: &'static str, error: AuthenticationError) -> FixedResultProvider {
+        FixedResultProvider {
+            error: Some(error),
+            ..FixedResultProvider::succeeding(name)
+        }
+    }
+
+    fn basic_only(mut self) -> FixedResultProvider {
+        self.basic_only = true;
+        self
+    }
+
+    fn calls(&self) -> usize {
+        self.calls.load(Ordering::SeqCst)
+    }
+}
+
+#[async_trait]
+impl AuthenticationProvider for FixedResultProvider {
+    fn supports(&self, authentication: &dyn Authentication) -> bool {
+        !self.basic_only || authentication.is::<BasicAuthentication>()
+    }
+
+    #[allow(clippy::borrowed_box)]
+    async fn authenticate(
+        &self,
+        _authentication: &Box<dyn Authentication>,
+    ) -> Result<Box<dyn UserDetails>, AuthenticationError> {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        match &self.error {
+            Some(error) => Err(error.clone()),
+            None => Ok(Box::new(User {
+                username: self.name.to_string(),
+            })),
+        }
+    }
+}
+
+fn basic_authentication() -> Box<dyn Authentication> {
+    Box::new(BasicAuthentication {
+        username: "test".to_string(),
+        password: Secret::new("pw".to_string()),
+    })
+}
+
+#[allow(clippy::borrowed_box)]
+async fn authenticate(
+    provider_manager: &ProviderManager,
+    authentication: &Box<dyn Authentication>,
+) -> Result<String, AuthenticationError> {
+    provider_manager
+        .authenticate(authentication)
+        .await
+        .map(|user| user.downcast_ref::<User>().unwrap().username.clone())
+}
+
+fn manager(providers: &[&FixedResultProvider], strategy: ProviderStrategy) -> ProviderManager {
+    ProviderManager::new(
+        providers
+            .iter()
+            .map(|provider| Box::new((*provider).clone()) as Box<dyn AuthenticationProvider>)
+            .collect(),
+    )
+    .with_strategy(strategy)
+}
+
+#[actix_rt::test]
+async fn validate_first_success_strategy() {
+    let rejecting = FixedResultProvider::failing("first", AuthenticationError::UsernameNotFound);
+    let succeeding = FixedResultProvider::succeeding("second");
+    let unused = FixedResultProvider::succeeding("third");
+    let provider_manager = manager(
+        &[&rejecting, &succeeding, &unused],
+        ProviderStrategy::FirstSuccess,
+    );
+
+    assert_eq!(
+        Ok("second".to_string()),
+        authenticate(&provider_manager, &basic_authentication()).await
+    );
+    assert_eq!(0, unused.calls());
+}
+
+#[actix_rt::test]
+async fn validate_rejection_is_not_masked_by_abstaining_providers() {
+    let rejecting = FixedResultProvider::failing("first", AuthenticationError::UsernameNotFound);
+    let abstaining =
+        FixedResultProvider::failing("second", AuthenticationError::InvalidAuthentication);
+    let unavailable =
+        FixedResultProvider::failing("third", AuthenticationError::ServiceUnavailable);
+    let provider_manager = manager(
+        &[&abstaining, &unavailable, &rejecting, &abstaining],
+        ProviderStrategy::FirstSuccess,
+    );
+
+    assert_eq!(
+        Err(AuthenticationError::UsernameNotFound),
+        authenticate(&provider_manager, &basic_authentication()).await
+    );
+
+    // The first rejection takes precedence over later rejections
+    let locked = FixedResultProvider::failing("fourth", AuthenticationError::AccountLocked);
+    let provider_manager = manager(&[&locked, &rejecting], ProviderStrategy::FirstSuccess);
+    assert_eq!(
+        Err(AuthenticationError::AccountLocked),
+        authenticate(&provider_manager, &basic_authentication()).await
+    );
+}
+
+#[actix_rt::test]
+async fn validate_error_precedence_without_rejections() {
+    let abstaining =
+        FixedResultProvider::failing("first", AuthenticationError::InvalidAuthentication);
+    let unavailable =
+        FixedResultProvider::failing("second", AuthenticationError::ServiceUnavailable);
+
+    let provider_manager = manager(&[&unavailable, &abstaining], ProviderStrategy::FirstSuccess);
+    assert_eq!(
+        Err(AuthenticationError::ServiceUnavailable),
+        authenticate(&provider_manager, &basic_authentication()).await
+    );
+
+    let provider_manager = manager(&[&abstaining], ProviderStrategy::FirstSuccess);
+    assert_eq!(
+        Err(AuthenticationError::InvalidAuthentication),
+        authenticate(&provider_manager, &basic_authentication()).await
+    );
+
+    let provider_manager = manager(&[], ProviderStrategy::FirstSuccess);
+    assert_eq!(
+        Err(AuthenticationError::InvalidAuthentication),
+        authenticate(&provider_manager, &basic_authentication()).await
+    );
+}
+
+#[actix_rt::test]
+async fn validate_stop_on_first_rejection_strategy() {
+    let abstaining =
+        FixedResultProvider::failing("first", AuthenticationError::InvalidAuthentication);
+    let rejecting = FixedResultProvider::failing("second", AuthenticationError::AccountDisabled);
+    let succeeding = FixedResultProvider::succeeding("third");
+    let provider_manager = manager(
+        &[&abstaining, &rejecting, &succeeding],
+        ProviderStrategy::StopOnFirstRejection,
+    );
+
+    assert_eq!(
+        Err(AuthenticationError::AccountDisabled),
+        authenticate(&provider_manager, &basic_authentication()).await
+    );
+    assert_eq!(0, succeeding.calls());
+
+    let provider_manager = manager(
+        &[&abstaining, &succeeding, &rejecting],
+        ProviderStrategy::StopOnFirstRejection,
+    );
+    assert_eq!(
+        Ok("third".to_string()),
+        authenticate(&provider_manager, &basic_authentication()).await
+    );
+}
+
+#[actix_rt::test]
+async fn validate_all_must_succeed_strategy() {
+    let first = FixedResultProvider::succeeding("first");
+    let second = FixedResultProvider::succeeding("second");
+    let abstaining =
+        FixedResultProvider::failing("third", AuthenticationError::InvalidAuthentication);
+    let rejecting = FixedResultProvider::failing("fourth", AuthenticationError::UsernameNotFound);
+
+    let provider_manager = manager(
+        &[&first, &abstaining, &second],
+        ProviderStrategy::AllMustSucceed,
+    );
+    assert_eq!(
+        Ok("first".to_string()),
+        authenticate(&provider_manager, &basic_authentication()).await
+    );
+    assert_eq!(1, second.calls());
+
+    let provider_manager = manager(
+        &[&first, &rejecting, &second],
+        ProviderStrategy::AllMustSucceed,
+    );
+    assert_eq!(
+        Err(AuthenticationError::UsernameNotFound),
+        authenticate(&provider_manager, &basic_authentication()).await
+    );
+    assert_eq!(1, second.calls());
+
+    // At least one provider has to succeed
+    let provider_manager = manager(&[&abstaining], ProviderStrategy::AllMustSucceed);
+    assert_eq!(
+        Err(AuthenticationError::InvalidAuthentication),
+        authenticate(&provider_manager, &basic_authentication()).await
+    );
+}
+
+#[actix_rt::test]
+async fn validate_providers_are_only_called_for_supported_authentications() {
+    let basic_provider = FixedResultProvider::succeeding("basic").basic_only();
+    let generic_provider = FixedResultProvider::succeeding("generic");
+    let provider_manager = manager(
+        &[&basic_provider, &generic_provider],
+        ProviderStrategy::FirstSuccess,
+    );
+
+    let api_key_authentication: Box<dyn Authentication> = Box::new(ApiKeyAuthentication {});
+    assert_eq!(
+        Ok("generic".to_string()),
+        authenticate(&provider_manager, &api_key_authentication).await
+    );
+    assert_eq!(0, basic_provider.calls());
+
+    assert_eq!(
+        Ok("basic".to_string()),
+        authenticate(&provider_manager, &basic_authentication()).await
+    );
+    assert_eq!(1, basic_provider.calls());
+}
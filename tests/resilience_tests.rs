@@ -0,0 +1,304 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use actix_web::dev::Service;
+use actix_web::http::{header, StatusCode};
+use actix_web::{get, test, App, HttpResponse, Responder, ResponseError};
+use async_trait::async_trait;
+use futures_timer::Delay;
+
+use actix_web_security::authentication::endpoint_matcher::AllEndpointsMatcher;
+use actix_web_security::authentication::error::error_type::AuthenticationError;
+use actix_web_security::authentication::middleware::HttpAuthenticationMiddleware;
+use actix_web_security::authentication::resilience::authentication_provider::ResilientAuthenticationProvider;
+use actix_web_security::authentication::resilience::{CircuitBreaker, CircuitState};
+use actix_web_security::authentication::scheme::authentication::Authentication;
+use actix_web_security::authentication::scheme::authentication_provider::AuthenticationProvider;
+use actix_web_security::authentication::scheme::basic::header_extractor::BasicAuthenticationExtractor;
+use actix_web_security::authentication::scheme::basic::password_authentication_provider::PasswordAuthenticationProvider;
+use actix_web_security::authentication::scheme::basic::secret::Secret;
+use actix_web_security::authentication::scheme::basic::user_store::{
+    BasicUserStore, UserCredentials,
+};
+use actix_web_security::authentication::scheme::basic::BasicAuthentication;
+use actix_web_security::authentication::ProviderManager;
+use actix_web_security::user_details::UserDetails;
+
+use common::{ReversingPasswordEncoder, User};
+
+mod common;
+
+/// Test provider simulating a user store that responds after a delay. Only the user `test` (with password `pw`) exists.
+#[derive(Clone)]
+struct DelayedAuthenticationProvider {
+    delay: Duration,
+    calls: Arc<AtomicUsize>,
+}
+
+impl DelayedAuthenticationProvider {
+    fn new(delay: Duration) -> DelayedAuthenticationProvider {
+        DelayedAuthenticationProvider {
+            delay,
+            calls: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    fn calls(&self) -> usize {
+        self.calls.load(Ordering::SeqCst)
+    }
+}
+
+#[async_trait]
+impl AuthenticationProvider for DelayedAuthenticationProvider {
+    #[allow(clippy::borrowed_box)]
+    async fn authenticate(
+        &self,
+        authentication: &Box<dyn Authentication>,
+    ) -> Result<Box<dyn UserDetails>, AuthenticationError> {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        Delay::new(self.delay).await;
+        let basic_auth = authentication
+            .downcast_ref::<BasicAuthentication>()
+            .ok_or(AuthenticationError::InvalidAuthentication)?;
+        if basic_auth.username == "test" && basic_auth.password.expose_secret() == "pw" {
+            Ok(Box::new(User {
+                username: "test".to_string(),
+            }))
+        } else {
+            Err(AuthenticationError::UsernameNotFound)
+        }
+    }
+}
+
+/// Test user store simulating a database that refuses all connections immediately.
+#[derive(Clone, Default)]
+struct FailingUserStore {
+    calls: Arc<AtomicUsize>,
+}
+
+impl FailingUserStore {
+    fn calls(&self) -> usize {
+        self.calls.load(Ordering::SeqCst)
+    }
+}
+
+#[async_trait]
+impl BasicUserStore for FailingUserStore {
+    async fn find_user_by_username(
+        &self,
+        _username: &str,
+    ) -> Result<Option<UserCredentials>, AuthenticationError> {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        Err(AuthenticationError::ServiceUnavailable)
+    }
+}
+
+fn authentication(username: &str, password: &str) -> Box<dyn Authentication> {
+    Box::new(BasicAuthentication {
+        username: username.to_string(),
+        password: Secret::new(password.to_string()),
+    })
+}
+
+#[get("/test")]
+async fn test_endpoint(user: User) -> impl Responder {
+    HttpResponse::Ok().json(user)
+}
+
+#[actix_rt::test]
+async fn validate_authentication_within_timeout_succeeds() {
+    let provider = ResilientAuthenticationProvider::new(Box::new(
+        DelayedAuthenticationProvider::new(Duration::from_millis(10)),
+    ))
+    .with_timeout(Duration::from_secs(1));
+
+    assert!(provider
+        .authenticate(&authentication("test", "pw"))
+        .await
+        .is_ok());
+    assert_eq!(
+        Err(AuthenticationError::UsernameNotFound),
+        provider
+            .authenticate(&authentication("test", "wrong"))
+            .await
+            .map(|_| ())
+    );
+}
+
+#[actix_rt::test]
+async fn validate_authentication_timeout() {
+    let provider = ResilientAuthenticationProvider::new(Box::new(
+        DelayedAuthenticationProvider::new(Duration::from_secs(10)),
+    ))
+    .with_timeout(Duration::from_millis(50));
+
+    assert_eq!(
+        Err(AuthenticationError::ServiceUnavailable),
+        provider
+            .authenticate(&authentication("test", "pw"))
+            .await
+            .map(|_| ())
+    );
+}
+
+#[actix_rt::test]
+async fn validate_circuit_breaker_opens_after_failures() {
+    let delegate = DelayedAuthenticationProvider::new(Duration::from_secs(10));
+    let circuit_breaker = CircuitBreaker::new(2, Duration::from_secs(60));
+    let provider = ResilientAuthenticationProvider::new(Box::new(delegate.clone()))
+        .with_timeout(Duration::from_millis(20))
+        .with_circuit_breaker(circuit_breaker.clone());
+
+    for _ in 0..2 {
+        assert_eq!(
+            Err(AuthenticationError::ServiceUnavailable),
+            provider
+                .authenticate(&authentication("test", "pw"))
+                .await
+                .map(|_| ())
+        );
+    }
+    assert_eq!(CircuitState::Open, circuit_breaker.state());
+
+    // The open circuit fails fast without calling the provider
+    assert_eq!(
+        Err(AuthenticationError::ServiceUnavailable),
+        provider
+            .authenticate(&authentication("test", "pw"))
+            .await
+            .map(|_| ())
+    );
+    assert_eq!(2, delegate.calls());
+}
+
+#[actix_rt::test]
+async fn validate_circuit_breaker_closes_after_successful_trial() {
+    let circuit_breaker = CircuitBreaker::new(1, Duration::from_millis(50));
+    let failing_provider = ResilientAuthenticationProvider::new(Box::new(
+        DelayedAuthenticationProvider::new(Duration::from_secs(10)),
+    ))
+    .with_timeout(Duration::from_millis(20))
+    .with_circuit_breaker(circuit_breaker.clone());
+    let recovered_provider = ResilientAuthenticationProvider::new(Box::new(
+        DelayedAuthenticationProvider::new(Duration::from_millis(1)),
+    ))
+    .with_circuit_breaker(circuit_breaker.clone());
+
+    assert!(failing_provider
+        .authenticate(&authentication("test", "pw"))
+        .await
+        .is_err());
+    assert_eq!(CircuitState::Open, circuit_breaker.state());
+
+    Delay::new(Duration::from_millis(100)).await;
+    assert_eq!(CircuitState::HalfOpen, circuit_breaker.state());
+
+    // Rejected credentials are a regular result of the provider and close the circuit
+    assert_eq!(
+        Err(AuthenticationError::UsernameNotFound),
+        recovered_provider
+            .authenticate(&authentication("test", "wrong"))
+            .await
+            .map(|_| ())
+    );
+    assert_eq!(CircuitState::Closed, circuit_breaker.state());
+}
+
+#[actix_rt::test]
+async fn validate_failed_trial_opens_circuit_again() {
+    let circuit_breaker = CircuitBreaker::new(3, Duration::from_millis(50));
+    let provider = ResilientAuthenticationProvider::new(Box::new(
+        DelayedAuthenticationProvider::new(Duration::from_secs(10)),
+    ))
+    .with_timeout(Duration::from_millis(20))
+    .with_circuit_breaker(circuit_breaker.clone());
+
+    for _ in 0..3 {
+        assert!(provider
+            .authenticate(&authentication("test", "pw"))
+            .await
+            .is_err());
+    }
+    assert_eq!(CircuitState::Open, circuit_breaker.state());
+
+    Delay::new(Duration::from_millis(100)).await;
+    assert!(provider
+        .authenticate(&authentication("test", "pw"))
+        .await
+        .is_err());
+    assert_eq!(CircuitState::Open, circuit_breaker.state());
+}
+
+#[actix_rt::test]
+async fn validate_service_unavailable_response() {
+    assert_eq!(
+        StatusCode::SERVICE_UNAVAILABLE,
+        AuthenticationError::ServiceUnavailable.status_code()
+    );
+
+    let provider = ResilientAuthenticationProvider::new(Box::new(
+        DelayedAuthenticationProvider::new(Duration::from_secs(10)),
+    ))
+    .with_timeout(Duration::from_millis(20));
+    let middleware = HttpAuthenticationMiddleware::new(
+        ProviderManager::new(vec![Box::new(provider)]),
+        Box::new(BasicAuthenticationExtractor::new()),
+        Box::new(AllEndpointsMatcher::new()),
+    );
+    let mut service = test::init_service(App::new().wrap(middleware).service(test_endpoint)).await;
+
+    let req = test::TestRequest::get()
+        .uri("/test")
+        .header(
+            header::AUTHORIZATION,
+            format!("Basic {}", base64::encode("test:pw")),
+        )
+        .to_request();
+
+    match service.call(req).await {
+        Ok(service_response) => panic!("Error expected: {:?}", service_response),
+        Err(e) => assert_eq!(
+            &AuthenticationError::ServiceUnavailable,
+            e.as_error().expect("error expected")
+        ),
+    }
+}
+
+#[actix_rt::test]
+async fn validate_failing_user_store_opens_circuit() {
+    let user_store = FailingUserStore::default();
+    let circuit_breaker = CircuitBreaker::new(2, Duration::from_secs(60));
+    let provider =
+        ResilientAuthenticationProvider::new(Box::new(PasswordAuthenticationProvider::new(
+            Box::new(user_store.clone()),
+            Box::new(ReversingPasswordEncoder::new()),
+        )))
+        .with_circuit_breaker(circuit_breaker.clone());
+    let middleware = HttpAuthenticationMiddleware::new(
+        ProviderManager::new(vec![Box::new(provider)]),
+        Box::new(BasicAuthenticationExtractor::new()),
+        Box::new(AllEndpointsMatcher::new()),
+    );
+    let mut service = test::init_service(App::new().wrap(middleware).service(test_endpoint)).await;
+
+    // The store fails fast, the errors count as failures without waiting for the timeout
+    for _ in 0..3 {
+        let req = test::TestRequest::get()
+            .uri("/test")
+            .header(
+                header::AUTHORIZATION,
+                format!("Basic {}", base64::encode("test:pw")),
+            )
+            .to_request();
+        match service.call(req).await {
+            Ok(service_response) => panic!("Error expected: {:?}", service_response),
+            Err(e) => assert_eq!(
+                StatusCode::SERVICE_UNAVAILABLE,
+                e.as_response_error().status_code()
+            ),
+        }
+    }
+    assert_eq!(CircuitState::Open, circuit_breaker.state());
+    assert_eq!(2, user_store.calls());
+}
@@ -0,0 +1,186 @@
+use actix_web::dev::Service;
+use actix_web::http::{header, StatusCode};
+use actix_web::{get, test, web, App, HttpResponse, Responder};
+use async_trait::async_trait;
+use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
+
+use actix_web_security::authentication::endpoint_matcher::AllEndpointsMatcher;
+use actix_web_security::authentication::error::error_type::AuthenticationError;
+use actix_web_security::authentication::middleware::HttpAuthenticationMiddleware;
+use actix_web_security::authentication::scheme::bearer::jwt::authentication_provider::JwtAuthenticationProvider;
+use actix_web_security::authentication::scheme::bearer::jwt::default_jwt::DefaultJwt;
+use actix_web_security::authentication::scheme::bearer::jwt::header_extractor::BearerAuthenticationExtractor;
+use actix_web_security::authentication::scheme::bearer::jwt::token::decoder::TokenDecoder;
+use actix_web_security::authentication::scheme::bearer::jwt::token::scopes::Scopes;
+use actix_web_security::authentication::scheme::bearer::jwt::token::Claims;
+use actix_web_security::authentication::scheme::bearer::jwt::user_details_service::JwtUserDetailsService;
+use actix_web_security::authentication::ProviderManager;
+use actix_web_security::authorization::scope::ScopeRequirement;
+use actix_web_security::user_details::UserDetails;
+
+use common::deserialize_json;
+use common::User;
+
+mod common;
+
+#[derive(Clone)]
+struct JwtUserDetailsServiceImpl {}
+
+#[async_trait]
+impl JwtUserDetailsService for JwtUserDetailsServiceImpl {
+    async fn find_user(&self, token: &Box<dyn Claims>) -> Option<Box<dyn UserDetails>> {
+        token
+            .downcast_ref::<DefaultJwt>()
+            .and_then(|claims| claims.sub.clone())
+            .map(|sub| Box::new(User { username: sub }) as Box<dyn UserDetails>)
+    }
+}
+
+#[derive(Clone)]
+struct SimpleTokenDecoder {}
+
+impl TokenDecoder<DefaultJwt> for SimpleTokenDecoder {
+    fn decode_token(&self, token: &str) -> Result<Box<DefaultJwt>, AuthenticationError> {
+        let key = &DecodingKey::from_secret("secret".as_ref());
+        match decode::<DefaultJwt>(token, key, &Validation::default()) {
+            Ok(token_data) => Ok(Box::new(token_data.claims)),
+            Err(_) => Err(AuthenticationError::InvalidToken),
+        }
+    }
+}
+
+#[get("/scopes")]
+async fn scopes_endpoint(scopes: Scopes) -> impl Responder {
+    HttpResponse::Ok().json(scopes)
+}
+
+async fn orders_endpoint(user: User) -> impl Responder {
+    HttpResponse::Ok().json(user)
+}
+
+fn init_middleware(
+) -> HttpAuthenticationMiddleware<BearerAuthenticationExtractor<DefaultJwt>, AllEndpointsMatcher> {
+    let authentication_provider =
+        JwtAuthenticationProvider::new(Box::new(JwtUserDetailsServiceImpl {}));
+
+    let provider_manager = ProviderManager::new(vec![Box::new(authentication_provider)]);
+
+    let authentication_extractor =
+        BearerAuthenticationExtractor::new(vec![Box::new(SimpleTokenDecoder {})]);
+
+    HttpAuthenticationMiddleware::new(
+        provider_manager,
+        Box::new(authentication_extractor),
+        Box::new(AllEndpointsMatcher::new()),
+    )
+}
+
+fn create_token(scope: Option<Scopes>, scp: Option<Scopes>) -> String {
+    let claims = DefaultJwt {
+        sub: Some("test".to_string()),
+        exp: Some(10000000000),
+        scope,
+        scp,
+        ..DefaultJwt::default()
+    };
+    let key = &EncodingKey::from_secret("secret".as_ref());
+    encode(&Header::default(), &claims, key).expect("Token couldn't be encoded")
+}
+
+fn create_request(token: String, uri: &str) -> actix_http::Request {
+    test::TestRequest::get()
+        .uri(uri)
+        .header(header::AUTHORIZATION, format!("Bearer {}", token))
+        .to_request()
+}
+
+#[actix_rt::test]
+async fn validate_scope_requirement_succeeds() {
+    let mut service = test::init_service(
+        App::new().wrap(init_middleware()).service(
+            web::resource("/orders")
+                .wrap(ScopeRequirement::new(vec!["orders:read", "orders:write"]))
+                .route(web::get().to(orders_endpoint)),
+        ),
+    )
+    .await;
+
+    let token = create_token(
+        Some(Scopes::from_delimited("orders:read orders:write")),
+        None,
+    );
+
+    match service.call(create_request(token, "/orders")).await {
+        Ok(mut service_response) => {
+            assert!(&service_response.status().is_success());
+            assert_eq!(
+                User {
+                    username: "test".to_string()
+                },
+                deserialize_json(&mut service_response.take_body()).await
+            );
+        }
+        Err(e) => panic!("Error occurred: {}", e),
+    }
+}
+
+#[actix_rt::test]
+async fn validate_scope_requirement_with_insufficient_scope() {
+    let mut service = test::init_service(
+        App::new().wrap(init_middleware()).service(
+            web::resource("/orders")
+                .wrap(ScopeRequirement::new(vec!["orders:write"]))
+                .route(web::get().to(orders_endpoint)),
+        ),
+    )
+    .await;
+
+    let token = create_token(Some(Scopes::from_delimited("orders:read")), None);
+
+    match service.call(create_request(token, "/orders")).await {
+        Ok(service_response) => panic!("Error expected: {:?}", service_response),
+        Err(e) => {
+            let response = e.as_response_error().error_response();
+            assert_eq!(StatusCode::FORBIDDEN, response.status());
+            assert_eq!(
+                "Bearer error=\"insufficient_scope\", scope=\"orders:write\"",
+                response
+                    .headers()
+                    .get(header::WWW_AUTHENTICATE)
+                    .expect("challenge expected")
+            );
+        }
+    }
+}
+
+#[actix_rt::test]
+async fn validate_scopes_of_scope_and_scp_claims_are_injected() {
+    let mut service =
+        test::init_service(App::new().wrap(init_middleware()).service(scopes_endpoint)).await;
+
+    let token = create_token(
+        Some(Scopes::from_delimited("orders:read")),
+        Some(Scopes::from_delimited("billing:read")),
+    );
+
+    match service.call(create_request(token, "/scopes")).await {
+        Ok(mut service_response) => {
+            assert!(&service_response.status().is_success());
+            assert_eq!(
+                "billing:read orders:read",
+                deserialize_json::<String, _>(&mut service_response.take_body()).await
+            );
+        }
+        Err(e) => panic!("Error occurred: {}", e),
+    }
+}
+
+#[actix_rt::test]
+async fn validate_scp_claim_as_array() {
+    let claims: DefaultJwt =
+        serde_json::from_str(r#"{"sub":"test","scp":["orders:read","orders:write"]}"#).unwrap();
+
+    assert!(claims
+        .scopes()
+        .contains_all(&["orders:read", "orders:write"]));
+}
@@ -0,0 +1,182 @@
+#![cfg(feature = "sqlx-user-store")]
+
+use actix_web::dev::Service;
+use actix_web::http::header;
+use actix_web::{get, test, App, HttpResponse, Responder};
+use serde_json::{json, Value};
+use sqlx::any::AnyPoolOptions;
+use sqlx::AnyPool;
+
+use actix_web_security::authentication::endpoint_matcher::AllEndpointsMatcher;
+use actix_web_security::authentication::error::error_type::AuthenticationError;
+use actix_web_security::authentication::middleware::HttpAuthenticationMiddleware;
+use actix_web_security::authentication::scheme::basic::header_extractor::BasicAuthenticationExtractor;
+use actix_web_security::authentication::scheme::basic::password_authentication_provider::PasswordAuthenticationProvider;
+use actix_web_security::authentication::scheme::basic::sqlx_user_store::{SqlxUser, SqlxUserStore};
+use actix_web_security::authentication::ProviderManager;
+
+use common::{deserialize_json, ReversingPasswordEncoder};
+
+mod common;
+
+async fn init_pool() -> AnyPool {
+    sqlx::any::install_default_drivers();
+    // A single connection, because every connection opens its own in-memory database
+    let pool = AnyPoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .expect("Failed to open database");
+    for statement in &[
+        "CREATE TABLE users (username TEXT PRIMARY KEY, password_hash TEXT NOT NULL, enabled INTEGER NOT NULL)",
+        "CREATE TABLE authorities (username TEXT NOT NULL, authority TEXT NOT NULL)",
+        "INSERT INTO users VALUES ('admin', 'rev:nimda', 1), ('legacy', 'plain:legacy', 1), ('disabled', 'rev:delbasid', 0)",
+        "INSERT INTO authorities VALUES ('admin', 'ADMIN'), ('admin', 'USER'), ('legacy', 'USER')",
+    ] {
+        sqlx::query(statement)
+            .execute(&pool)
+            .await
+            .expect("Failed to initialize database");
+    }
+    pool
+}
+
+#[get("/test")]
+async fn test_endpoint(user: SqlxUser) -> impl Responder {
+    HttpResponse::Ok().json(user)
+}
+
+async fn call_endpoint(
+    user_store: SqlxUserStore,
+    credentials: &str,
+) -> Result<Value, AuthenticationError> {
+    let middleware = HttpAuthenticationMiddleware::new(
+        ProviderManager::new(vec![Box::new(PasswordAuthenticationProvider::new(
+            Box::new(user_store),
+            Box::new(ReversingPasswordEncoder::new()),
+        ))]),
+        Box::new(BasicAuthenticationExtractor::new()),
+        Box::new(AllEndpointsMatcher::new()),
+    );
+    let mut service = test::init_service(App::new().wrap(middleware).service(test_endpoint)).await;
+
+    let req = test::TestRequest::get()
+        .uri("/test")
+        .header(
+            header::AUTHORIZATION,
+            format!("Basic {}", base64::encode(credentials)),
+        )
+        .to_request();
+
+    match service.call(req).await {
+        Ok(mut service_response) => {
+            assert!(&service_response.status().is_success());
+            Ok(deserialize_json(&mut service_response.take_body()).await)
+        }
+        Err(e) => Err(e
+            .as_error::<AuthenticationError>()
+            .expect("error expected")
+            .clone()),
+    }
+}
+
+async fn password_hash(pool: &AnyPool, username: &str) -> String {
+    sqlx::query_scalar("SELECT password_hash FROM users WHERE username = $1")
+        .bind(username)
+        .fetch_one(pool)
+        .await
+        .unwrap()
+}
+
+#[actix_rt::test]
+async fn validate_sqlx_user_store_succeeds() {
+    let user_store = SqlxUserStore::new(init_pool().await);
+
+    assert_eq!(
+        Ok(json!({"username": "admin", "authorities": ["ADMIN", "USER"], "enabled": true})),
+        call_endpoint(user_store, "admin:admin").await
+    );
+}
+
+#[actix_rt::test]
+async fn validate_sqlx_user_store_with_invalid_credentials() {
+    let user_store = SqlxUserStore::new(init_pool().await);
+
+    for credentials in &["admin:wrong", "unknown:unknown"] {
+        assert_eq!(
+            Err(AuthenticationError::UsernameNotFound),
+            call_endpoint(user_store.clone(), credentials).await
+        );
+    }
+}
+
+#[actix_rt::test]
+async fn validate_sqlx_user_store_with_disabled_user() {
+    let user_store = SqlxUserStore::new(init_pool().await);
+
+    assert_eq!(
+        Err(AuthenticationError::AccountDisabled),
+        call_endpoint(user_store, "disabled:disabled").await
+    );
+}
+
+#[actix_rt::test]
+async fn validate_sqlx_user_store_with_unavailable_database() {
+    let pool = init_pool().await;
+    pool.close().await;
+
+    assert_eq!(
+        Err(AuthenticationError::ServiceUnavailable),
+        call_endpoint(SqlxUserStore::new(pool), "admin:admin").await
+    );
+}
+
+#[actix_rt::test]
+async fn validate_sqlx_user_store_with_invalid_authorities_query() {
+    let user_store = SqlxUserStore::new(init_pool().await).with_authorities_query(Some(
+        "SELECT authority FROM missing_table WHERE username = $1",
+    ));
+
+    assert_eq!(
+        Err(AuthenticationError::ServiceUnavailable),
+        call_endpoint(user_store, "admin:admin").await
+    );
+}
+
+#[actix_rt::test]
+async fn validate_sqlx_user_store_with_custom_queries() {
+    let pool = init_pool().await;
+    let user_store = SqlxUserStore::new(pool)
+        .with_user_query(
+            "SELECT password_hash, 1 AS enabled FROM users WHERE username = ?1 AND username <> 'admin'",
+        )
+        .with_authorities_query(None);
+
+    assert_eq!(
+        Ok(json!({"username": "disabled", "authorities": [], "enabled": true})),
+        call_endpoint(user_store.clone(), "disabled:disabled").await
+    );
+    assert_eq!(
+        Err(AuthenticationError::UsernameNotFound),
+        call_endpoint(user_store, "admin:admin").await
+    );
+}
+
+#[actix_rt::test]
+async fn validate_sqlx_user_store_upgrades_password_hash() {
+    let pool = init_pool().await;
+
+    assert_eq!(
+        Ok(json!({"username": "legacy", "authorities": ["USER"], "enabled": true})),
+        call_endpoint(SqlxUserStore::new(pool.clone()), "legacy:legacy").await
+    );
+    assert_eq!("rev:ycagel", password_hash(&pool, "legacy").await);
+
+    let user_store = SqlxUserStore::new(pool.clone()).with_update_password_hash_query(None);
+    sqlx::query("UPDATE users SET password_hash = 'plain:legacy' WHERE username = 'legacy'")
+        .execute(&pool)
+        .await
+        .unwrap();
+    assert!(call_endpoint(user_store, "legacy:legacy").await.is_ok());
+    assert_eq!("plain:legacy", password_hash(&pool, "legacy").await);
+}
@@ -0,0 +1,181 @@
+#![cfg(feature = "tracing")]
+
+use std::io::{self, Write};
+use std::sync::{Arc, Mutex};
+
+use actix_web::dev::Service;
+use actix_web::http::header;
+use actix_web::{get, test, App, HttpResponse, Responder};
+use async_trait::async_trait;
+
+use actix_web_security::authentication::endpoint_matcher::AllEndpointsMatcher;
+use actix_web_security::authentication::error::error_type::AuthenticationError;
+use actix_web_security::authentication::middleware::HttpAuthenticationMiddleware;
+use actix_web_security::authentication::scheme::basic::authentication_provider::BasicAuthenticationProvider;
+use actix_web_security::authentication::scheme::basic::header_extractor::BasicAuthenticationExtractor;
+use actix_web_security::authentication::scheme::basic::user_details_service::BasicUserDetailsService;
+use actix_web_security::authentication::scheme::bearer::jwt::authentication_provider::JwtAuthenticationProvider;
+use actix_web_security::authentication::scheme::bearer::jwt::default_jwt::DefaultJwt;
+use actix_web_security::authentication::scheme::bearer::jwt::header_extractor::BearerAuthenticationExtractor;
+use actix_web_security::authentication::scheme::bearer::jwt::token::decoder::TokenDecoder;
+use actix_web_security::authentication::scheme::bearer::jwt::token::Claims;
+use actix_web_security::authentication::scheme::bearer::jwt::user_details_service::JwtUserDetailsService;
+use actix_web_security::authentication::ProviderManager;
+use actix_web_security::user_details::UserDetails;
+
+use common::User;
+
+mod common;
+
+#[derive(Clone)]
+struct UserDetailsServiceImpl {}
+
+#[async_trait]
+impl BasicUserDetailsService for UserDetailsServiceImpl {
+    async fn find_user(&self, username: &str, password: &str) -> Option<Box<dyn UserDetails>> {
+        if username == "test" && password == "secret-password" {
+            Some(Box::new(User {
+                username: username.to_string(),
+            }))
+        } else {
+            None
+        }
+    }
+}
+
+#[async_trait]
+impl JwtUserDetailsService for UserDetailsServiceImpl {
+    #[allow(clippy::borrowed_box)]
+    async fn find_user(&self, _token: &Box<dyn Claims>) -> Option<Box<dyn UserDetails>> {
+        None
+    }
+}
+
+/// Test decoder rejecting all tokens
+#[derive(Clone)]
+struct RejectingTokenDecoder {}
+
+impl TokenDecoder<DefaultJwt> for RejectingTokenDecoder {
+    fn decode_token(&self, _token: &str) -> Result<Box<DefaultJwt>, AuthenticationError> {
+        Err(AuthenticationError::InvalidToken)
+    }
+}
+
+/// Test writer collecting the log output
+#[derive(Clone, Default)]
+struct SharedBuffer {
+    buffer: Arc<Mutex<Vec<u8>>>,
+}
+
+impl SharedBuffer {
+    fn content(&self) -> String {
+        String::from_utf8(self.buffer.lock().unwrap().clone()).unwrap()
+    }
+}
+
+impl Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.buffer.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[get("/test")]
+async fn test_endpoint() -> impl Responder {
+    HttpResponse::Ok().finish()
+}
+
+/// Calls the endpoint with the authorization header and returns the log output.
+async fn call_endpoint<T>(
+    middleware: HttpAuthenticationMiddleware<T, AllEndpointsMatcher>,
+    authorization: &str,
+) -> String
+where
+    T: actix_web_security::authentication::scheme::header_extractor::AuthorizationHeaderExtractor
+        + Clone
+        + 'static,
+{
+    let buffer = SharedBuffer::default();
+    let writer = buffer.clone();
+    let subscriber = tracing_subscriber::fmt()
+        .with_max_level(tracing::Level::TRACE)
+        .with_ansi(false)
+        .with_writer(move || writer.clone())
+        .finish();
+    let _guard = tracing::subscriber::set_default(subscriber);
+
+    let mut service = test::init_service(App::new().wrap(middleware).service(test_endpoint)).await;
+    let req = test::TestRequest::get()
+        .uri("/test")
+        .header(header::AUTHORIZATION, authorization)
+        .to_request();
+    let _ = service.call(req).await;
+
+    buffer.content()
+}
+
+fn basic_middleware(
+) -> HttpAuthenticationMiddleware<BasicAuthenticationExtractor, AllEndpointsMatcher> {
+    HttpAuthenticationMiddleware::new(
+        ProviderManager::new(vec![Box::new(BasicAuthenticationProvider::new(Box::new(
+            UserDetailsServiceImpl {},
+        )))]),
+        Box::new(BasicAuthenticationExtractor::new()),
+        Box::new(AllEndpointsMatcher::new()),
+    )
+}
+
+#[actix_rt::test]
+async fn validate_successful_authentication_is_traced() {
+    let output = call_endpoint(
+        basic_middleware(),
+        &format!("Basic {}", base64::encode("test:secret-password")),
+    )
+    .await;
+
+    assert!(output.contains("authorization header extracted"));
+    assert!(output.contains("authentication{method=GET path=/test principal=\"test\"}"));
+    assert!(output.contains("authentication provider succeeded provider=0"));
+    assert!(output.contains("authentication succeeded"));
+    assert!(!output.contains("secret-password"));
+}
+
+#[actix_rt::test]
+async fn validate_failed_authentication_is_traced() {
+    let output = call_endpoint(
+        basic_middleware(),
+        &format!("Basic {}", base64::encode("test:wrong-secret-password")),
+    )
+    .await;
+
+    assert!(output.contains("authentication provider failed"));
+    assert!(output.contains("authentication failed error=UsernameNotFound"));
+    assert!(!output.contains("principal=\"test\""));
+    assert!(!output.contains("secret-password"));
+    assert!(!output.contains(&base64::encode("test:wrong-secret-password")));
+}
+
+#[actix_rt::test]
+async fn validate_token_decoder_attempts_are_traced() {
+    let middleware = HttpAuthenticationMiddleware::new(
+        ProviderManager::new(vec![Box::new(JwtAuthenticationProvider::new(Box::new(
+            UserDetailsServiceImpl {},
+        )))]),
+        Box::new(BearerAuthenticationExtractor::new(vec![
+            Box::new(RejectingTokenDecoder {}),
+            Box::new(RejectingTokenDecoder {}),
+        ])),
+        Box::new(AllEndpointsMatcher::new()),
+    );
+    let output = call_endpoint(middleware, "Bearer secret.token.value").await;
+
+    assert!(output.contains("token_decoder{decoder=0}"));
+    assert!(output.contains("token_decoder{decoder=1}"));
+    assert!(output.contains("token decoder rejected the token error=InvalidToken"));
+    assert!(output.contains("authorization header extraction failed"));
+    assert!(output.contains("authentication failed error=InvalidAuthentication"));
+    assert!(!output.contains("secret.token.value"));
+}